@@ -1,7 +1,15 @@
 //! BAM I/O.
 
 pub mod indexed_reader;
+pub mod indexed_writer;
 pub mod reader;
+pub mod slice_reader;
 pub mod writer;
 
-pub use self::{indexed_reader::IndexedReader, reader::Reader, writer::Writer};
+pub use self::{
+    indexed_reader::IndexedReader,
+    indexed_writer::IndexedWriter,
+    reader::Reader,
+    slice_reader::{BlockReader, SliceReader},
+    writer::Writer,
+};
@@ -6,7 +6,10 @@ use std::io::{self, Read};
 
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_csi::{
+    binning_index::verifier::{AlignmentContext, RecordReader},
+    BinningIndex,
+};
 use noodles_sam::{self as sam, alignment::RecordBuf};
 
 pub use self::builder::Builder;
@@ -113,3 +116,92 @@ where
         self.inner.query_unmapped(&self.index)
     }
 }
+
+impl<R> RecordReader for IndexedReader<R>
+where
+    R: bgzf::io::BufRead + bgzf::io::Seek,
+{
+    fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<()> {
+        self.inner.seek(pos)
+    }
+
+    fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+
+    fn read_alignment_context(&mut self) -> io::Result<Option<AlignmentContext>> {
+        self.inner.read_alignment_context()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use bgzf::storage::Local;
+    use noodles_core::Position;
+    use noodles_csi::binning_index::chunk_ranges;
+    use noodles_sam::{
+        alignment::{
+            io::Write,
+            record::cigar::{op::Kind, Op},
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+    use crate::io::IndexedWriter;
+
+    #[test]
+    fn test_query_with_storage() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .build();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_alignment_header(&header)?;
+
+        for (name, reference_sequence_id, start) in [(b"r0", 0, 8), (b"r1", 0, 55), (b"r2", 1, 13)]
+        {
+            let record = RecordBuf::builder()
+                .set_name(name[..].into())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        let index = writer.finish(&header)?;
+        let data = writer.get_ref().get_ref().clone();
+
+        let mut reader = Builder::default()
+            .set_index(index)
+            .build_from_storage(Local::new(io::Cursor::new(data)))?;
+        reader.read_header()?;
+
+        let region: Region = "sq0:50-60".parse()?;
+        let chunks = reader.index().query(0, region.interval())?;
+        reader
+            .get_mut()
+            .get_mut()
+            .prefetch(&chunk_ranges(&chunks, 0))?;
+
+        let names: Vec<_> = reader
+            .query(&header, &region)?
+            .map(|result| result.map(|record| record.name().map(|name| name.as_bytes().to_vec())))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(names, [Some(b"r1".to_vec())]);
+
+        Ok(())
+    }
+}
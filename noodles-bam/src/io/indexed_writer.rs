@@ -0,0 +1,221 @@
+//! Indexed BAM writer.
+
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::binning_index::{
+    index::reference_sequence::{bin::Chunk, index::LinearIndex},
+    Indexer,
+};
+use noodles_sam::{self as sam, alignment::io::Write as _};
+
+use super::Writer;
+use crate::{bai, Record};
+
+/// An indexed BAM writer.
+///
+/// This builds a BAM index (BAI) as records are written, avoiding a second pass over the output.
+/// Records must be written in coordinate-sorted order.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam as bam;
+/// use noodles_csi::BinningIndex;
+/// use noodles_sam as sam;
+///
+/// let mut writer = bam::io::IndexedWriter::new(Vec::new());
+///
+/// let header = sam::Header::default();
+/// writer.write_header(&header)?;
+///
+/// let record = bam::Record::default();
+/// writer.write_record(&header, &record)?;
+///
+/// let index = writer.finish(&header)?;
+/// assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::Writer<W>>,
+    indexer: Indexer<LinearIndex>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexed BAM writer with a default compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::io::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(inner))
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &bgzf::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Writes a SAM header.
+    pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a record.
+    pub fn write_record(&mut self, header: &sam::Header, record: &Record) -> io::Result<()> {
+        self.write_alignment_record(header, record)
+    }
+
+    /// Finishes the output stream and returns the built index.
+    ///
+    /// The index has an entry for each reference sequence in the given header.
+    pub fn finish(&mut self, header: &sam::Header) -> io::Result<bai::Index> {
+        self.inner.try_finish()?;
+
+        let indexer = mem::take(&mut self.indexer);
+        Ok(indexer.build(header.reference_sequences().len()))
+    }
+}
+
+impl<W> From<Writer<bgzf::Writer<W>>> for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: Writer<bgzf::Writer<W>>) -> Self {
+        Self {
+            inner,
+            indexer: Indexer::default(),
+        }
+    }
+}
+
+impl<W> sam::alignment::io::Write for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn sam::alignment::Record,
+    ) -> io::Result<()> {
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_alignment_record(header, record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        let chunk = Chunk::new(start_position, end_position);
+        let alignment_context = alignment_context(header, record)?;
+
+        self.indexer.add_record(alignment_context, chunk)
+    }
+
+    fn finish(&mut self, _: &sam::Header) -> io::Result<()> {
+        self.inner.try_finish()
+    }
+}
+
+fn alignment_context(
+    header: &sam::Header,
+    record: &dyn sam::alignment::Record,
+) -> io::Result<Option<(usize, Position, Position, bool)>> {
+    match (
+        record.reference_sequence_id(header).transpose()?,
+        record.alignment_start().transpose()?,
+        record.alignment_end().transpose()?,
+    ) {
+        (Some(id), Some(start), Some(end)) => {
+            let is_mapped = !record.flags()?.is_unmapped();
+            Ok(Some((id, start, end, is_mapped)))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, num::NonZeroUsize};
+
+    use noodles_csi::{binning_index::ReferenceSequence as _, BinningIndex};
+    use sam::{
+        alignment::{
+            record::{cigar::op::Kind, cigar::Op, Flags},
+            RecordBuf,
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        const SQ0_LN: NonZeroUsize = match NonZeroUsize::new(131072) {
+            Some(n) => n,
+            None => unreachable!(),
+        };
+
+        let header = sam::Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(SQ0_LN))
+            .build();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for start in [8, 13, 65536] {
+            let record = RecordBuf::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        writer.write_alignment_record(&header, &RecordBuf::default())?;
+
+        let index = writer.finish(&header)?;
+
+        assert_eq!(index.reference_sequences().len(), 1);
+        assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+
+        let metadata = index.reference_sequences()[0]
+            .metadata()
+            .expect("missing metadata");
+        assert_eq!(metadata.mapped_record_count(), 3);
+
+        let src = writer.get_ref().get_ref();
+        let mut reader = crate::io::IndexedReader::new(Cursor::new(src), index);
+        reader.read_header()?;
+
+        let region = "sq0:65536-65540".parse()?;
+        let records: Vec<_> = reader.query(&header, &region)?.collect::<io::Result<_>>()?;
+        assert_eq!(records.len(), 1);
+
+        Ok(())
+    }
+}
@@ -16,7 +16,10 @@ use std::{
 use bstr::BString;
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_csi::{
+    binning_index::verifier::{AlignmentContext, RecordReader},
+    BinningIndex,
+};
 use noodles_sam::{self as sam, alignment::RecordBuf, header::ReferenceSequences};
 
 pub use self::{builder::Builder, query::Query, record_bufs::RecordBufs, records::Records};
@@ -376,6 +379,39 @@ impl<R> From<R> for Reader<R> {
     }
 }
 
+impl<R> RecordReader for Reader<R>
+where
+    R: bgzf::io::BufRead + bgzf::io::Seek,
+{
+    fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<()> {
+        self.inner.seek_to_virtual_position(pos)?;
+
+        // A BGZF reader does not move when seeking past the end of the stream.
+        if self.inner.virtual_position() < pos {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "invalid virtual position",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+
+    fn read_alignment_context(&mut self) -> io::Result<Option<AlignmentContext>> {
+        let mut record = Record::default();
+
+        if self.read_record(&mut record)? == 0 {
+            return Ok(None);
+        }
+
+        alignment_context(&record).map(Some)
+    }
+}
+
 impl<R> sam::alignment::io::Read<R> for Reader<R>
 where
     R: Read,
@@ -396,6 +432,20 @@ where
     }
 }
 
+fn alignment_context(record: &Record) -> io::Result<AlignmentContext> {
+    match (
+        record.reference_sequence_id().transpose()?,
+        record.alignment_start().transpose()?,
+        sam::alignment::Record::alignment_end(record).transpose()?,
+    ) {
+        (Some(id), Some(start), Some(end)) => {
+            let is_mapped = !record.flags().is_unmapped();
+            Ok(Some((id, start, end, is_mapped)))
+        }
+        _ => Ok(None),
+    }
+}
+
 pub(crate) fn bytes_with_nul_to_bstring(buf: &[u8]) -> io::Result<BString> {
     CStr::from_bytes_with_nul(buf)
         .map(|c_str| c_str.to_bytes().into())
@@ -417,3 +467,88 @@ pub(crate) fn resolve_region(
             )
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, num::NonZeroUsize};
+
+    use noodles_core::Position;
+    use noodles_csi::binning_index::{verifier::Discrepancy, Verifier};
+    use sam::{
+        alignment::{
+            io::Write,
+            record::{
+                cigar::{op::Kind, Op},
+                Flags,
+            },
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+    use crate::io::IndexedWriter;
+
+    fn build_header() -> sam::Header {
+        const SQ0_LN: NonZeroUsize = match NonZeroUsize::new(1 << 20) {
+            Some(n) => n,
+            None => unreachable!(),
+        };
+
+        sam::Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(SQ0_LN))
+            .build()
+    }
+
+    fn write_records(
+        header: &sam::Header,
+        starts: &[usize],
+    ) -> Result<(Vec<u8>, crate::bai::Index), Box<dyn std::error::Error>> {
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(header)?;
+
+        for &start in starts {
+            let record = RecordBuf::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .build();
+
+            writer.write_alignment_record(header, &record)?;
+        }
+
+        writer.write_alignment_record(header, &RecordBuf::default())?;
+
+        let index = writer.finish(header)?;
+        let data = writer.get_ref().get_ref().clone();
+
+        Ok((data, index))
+    }
+
+    #[test]
+    fn test_verify_with_bai() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let (data, index) = write_records(&header, &[8, 13, 600_000])?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_header()?;
+        assert!(Verifier::new(&index).verify(&mut reader)?.is_empty());
+
+        // The index of a file with the same record sizes but a different last position.
+        let (_, stale_index) = write_records(&header, &[8, 13, 300_000])?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_header()?;
+        let discrepancies = Verifier::new(&stale_index).verify(&mut reader)?;
+
+        assert!(matches!(
+            discrepancies[..],
+            [Discrepancy::RecordOutsideBin {
+                reference_sequence_id: 0,
+                ..
+            }]
+        ));
+
+        Ok(())
+    }
+}
@@ -16,7 +16,7 @@ use noodles_sam::{
 use super::bytes_with_nul_to_bstring;
 use crate::MAGIC_NUMBER;
 
-pub(super) fn read_header<R>(reader: &mut R) -> io::Result<sam::Header>
+pub(crate) fn read_header<R>(reader: &mut R) -> io::Result<sam::Header>
 where
     R: Read,
 {
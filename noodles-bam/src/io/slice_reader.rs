@@ -0,0 +1,323 @@
+//! Zero-copy BAM reader.
+
+use std::io;
+
+use noodles_bgzf as bgzf;
+use noodles_sam as sam;
+
+use super::reader::header::read_header;
+use crate::Record;
+
+const BLOCK_SIZE_LENGTH: usize = 4;
+
+/// A zero-copy BAM reader over uncompressed BAM data in memory.
+///
+/// The source is typically a memory-mapped uncompressed BAM file. Records borrow their data from
+/// the source rather than being copied into a buffer.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs;
+/// use noodles_bam as bam;
+///
+/// // e.g., `samtools view -u`
+/// let src = fs::read("sample.u.bam")?;
+///
+/// let mut reader = bam::io::SliceReader::new(&src);
+/// let header = reader.read_header()?;
+///
+/// for result in reader.records() {
+///     let record = result?;
+///     // ...
+/// }
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct SliceReader<'a> {
+    src: &'a [u8],
+}
+
+impl<'a> SliceReader<'a> {
+    /// Creates a zero-copy BAM reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let reader = bam::io::SliceReader::new(&[]);
+    /// ```
+    pub fn new(src: &'a [u8]) -> Self {
+        Self { src }
+    }
+
+    /// Returns the unread source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let reader = bam::io::SliceReader::new(&[]);
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &'a [u8] {
+        self.src
+    }
+
+    /// Reads the SAM header.
+    ///
+    /// The position of the reader is moved to the start of the first record.
+    pub fn read_header(&mut self) -> io::Result<sam::Header> {
+        read_header(&mut self.src)
+    }
+
+    /// Reads a record.
+    ///
+    /// The returned record borrows its data from the source. This returns `None` when the source
+    /// is exhausted.
+    pub fn read_record(&mut self) -> io::Result<Option<Record<&'a [u8]>>> {
+        match split_record(self.src)? {
+            Some((buf, rest)) => {
+                self.src = rest;
+                Record::try_from_slice(buf).map(Some)
+            }
+            None if self.src.is_empty() => Ok(None),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected EOF in record",
+            )),
+        }
+    }
+
+    /// Returns an iterator over records.
+    pub fn records(&mut self) -> impl Iterator<Item = io::Result<Record<&'a [u8]>>> + '_ {
+        std::iter::from_fn(move || self.read_record().transpose())
+    }
+}
+
+/// A BAM reader that views records in buffers of decompressed BGZF blocks.
+///
+/// Whole decompressed blocks are appended to a shared buffer, and records are read in place from
+/// the buffer instead of being copied into individual record buffers. A record borrows the reader,
+/// so it must be dropped before reading the next one.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// use noodles_bam as bam;
+/// use noodles_bgzf as bgzf;
+///
+/// let mut reader = File::open("sample.bam")
+///     .map(bgzf::Reader::new)
+///     .map(bam::io::BlockReader::new)?;
+///
+/// let header = reader.read_header()?;
+///
+/// while let Some(record) = reader.read_record()? {
+///     // ...
+/// }
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct BlockReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    position: usize,
+}
+
+impl<R> BlockReader<R>
+where
+    R: bgzf::io::BufRead,
+{
+    /// Creates a BAM block reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    /// let reader = bam::io::BlockReader::new(bgzf::Reader::new(&[][..]));
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Any decompressed data that has not yet been read as records is discarded.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads the SAM header.
+    ///
+    /// This must be called before reading any records.
+    pub fn read_header(&mut self) -> io::Result<sam::Header> {
+        read_header(&mut self.inner)
+    }
+
+    /// Reads a record.
+    ///
+    /// The returned record borrows its data from the shared block buffer. This returns `None` when
+    /// the stream is exhausted.
+    pub fn read_record(&mut self) -> io::Result<Option<Record<&[u8]>>> {
+        loop {
+            if let Some((buf, rest)) = split_record(&self.buf[self.position..])? {
+                let start = self.position + BLOCK_SIZE_LENGTH;
+                let end = start + buf.len();
+                self.position = self.buf.len() - rest.len();
+                return Record::try_from_slice(&self.buf[start..end]).map(Some);
+            }
+
+            if self.fill_buf()? == 0 {
+                return if self.position < self.buf.len() {
+                    Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "unexpected EOF in record",
+                    ))
+                } else {
+                    Ok(None)
+                };
+            }
+        }
+    }
+
+    // Discards the read records and appends the next decompressed block.
+    fn fill_buf(&mut self) -> io::Result<usize> {
+        self.buf.drain(..self.position);
+        self.position = 0;
+
+        let src = self.inner.fill_buf()?;
+        let n = src.len();
+        self.buf.extend_from_slice(src);
+        self.inner.consume(n);
+
+        Ok(n)
+    }
+}
+
+// Splits the source into the next record data and the remaining source.
+//
+// This returns `None` if the source does not hold a complete record.
+fn split_record(src: &[u8]) -> io::Result<Option<(&[u8], &[u8])>> {
+    if src.len() < BLOCK_SIZE_LENGTH {
+        return Ok(None);
+    }
+
+    let (buf, rest) = src.split_at(BLOCK_SIZE_LENGTH);
+    // SAFETY: `buf` is 4 bytes.
+    let n = u32::from_le_bytes(buf.try_into().unwrap());
+    let block_size =
+        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    if rest.len() < block_size {
+        Ok(None)
+    } else {
+        Ok(Some(rest.split_at(block_size)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::alignment::{io::Write, RecordBuf};
+
+    use super::*;
+    use crate::io::Writer;
+
+    fn build_records() -> Vec<RecordBuf> {
+        [&b"r0"[..], b"r1", b"r2"]
+            .into_iter()
+            .map(|name| RecordBuf::builder().set_name(name.into()).build())
+            .collect()
+    }
+
+    fn write_bam<W>(writer: &mut Writer<W>, header: &sam::Header) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        writer.write_header(header)?;
+
+        for record in build_records() {
+            writer.write_alignment_record(header, &record)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slice_reader_read_record() -> io::Result<()> {
+        let header = sam::Header::default();
+
+        let mut writer = Writer::from(Vec::new());
+        write_bam(&mut writer, &header)?;
+        let src = writer.into_inner();
+
+        let mut reader = SliceReader::new(&src);
+        assert_eq!(reader.read_header()?, header);
+
+        let names: Vec<_> = reader
+            .records()
+            .map(|result| result.map(|record| record.name().map(|name| name.as_bytes().to_vec())))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(
+            names,
+            [
+                Some(b"r0".to_vec()),
+                Some(b"r1".to_vec()),
+                Some(b"r2".to_vec())
+            ]
+        );
+
+        let mut reader = SliceReader::new(&src[..src.len() - 1]);
+        reader.read_header()?;
+        reader.read_record()?;
+        reader.read_record()?;
+        assert!(matches!(
+            reader.read_record(),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_reader_read_record() -> io::Result<()> {
+        let header = sam::Header::default();
+
+        let mut writer = Writer::new(Vec::new());
+        write_bam(&mut writer, &header)?;
+        writer.try_finish()?;
+        let src = writer.into_inner().into_inner();
+
+        let mut reader = BlockReader::new(bgzf::Reader::new(&src[..]));
+        assert_eq!(reader.read_header()?, header);
+
+        let mut names = Vec::new();
+
+        while let Some(record) = reader.read_record()? {
+            let expected = record.to_owned_record();
+            assert_eq!(format!("{record:?}"), format!("{expected:?}"));
+            names.push(record.name().map(|name| name.as_bytes().to_vec()));
+        }
+
+        assert_eq!(
+            names,
+            [
+                Some(b"r0".to_vec()),
+                Some(b"r1".to_vec()),
+                Some(b"r2".to_vec())
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -22,14 +22,64 @@ pub use self::{
 };
 
 /// A BAM record.
-#[derive(Clone, Default, Eq, PartialEq)]
-pub struct Record(Fields);
+///
+/// By default, a record owns its buffer. A record can also borrow its buffer, e.g.,
+/// `Record<&[u8]>`, to view encoded record data in place without copying it.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Record<B = Vec<u8>>(Fields<B>);
 
 impl Record {
     pub(crate) fn fields_mut(&mut self) -> &mut Fields {
         &mut self.0
     }
+}
+
+impl<'a> Record<&'a [u8]> {
+    /// Creates a BAM record that borrows the given encoded record data.
+    ///
+    /// The source is the record data following the block size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    ///
+    /// let src = [
+    ///     0xff, 0xff, 0xff, 0xff, // ref_id = -1
+    ///     0xff, 0xff, 0xff, 0xff, // pos = -1
+    ///     0x03, // l_read_name = 3
+    ///     0xff, // mapq = 255
+    ///     0x48, 0x12, // bin = 4680
+    ///     0x00, 0x00, // n_cigar_op = 0
+    ///     0x04, 0x00, // flag = 4
+    ///     0x00, 0x00, 0x00, 0x00, // l_seq = 0
+    ///     0xff, 0xff, 0xff, 0xff, // next_ref_id = -1
+    ///     0xff, 0xff, 0xff, 0xff, // next_pos = -1
+    ///     0x00, 0x00, 0x00, 0x00, // tlen = 0
+    ///     b'r', b'0', 0x00, // read_name = "r0\x00"
+    /// ];
+    ///
+    /// let record = bam::Record::try_from_slice(&src)?;
+    /// assert!(record.name().is_some_and(|name| name.as_bytes() == b"r0"));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn try_from_slice(src: &'a [u8]) -> io::Result<Self> {
+        Fields::try_new(src).map(Self)
+    }
 
+    /// Copies the borrowed record data into an owned record.
+    pub fn to_owned_record(&self) -> Record {
+        Record(Fields {
+            buf: self.0.buf.to_vec(),
+            bounds: self.0.bounds.clone(),
+        })
+    }
+}
+
+impl<B> Record<B>
+where
+    B: AsRef<[u8]>,
+{
     /// Returns the reference sequence ID.
     ///
     /// # Examples
@@ -192,7 +242,16 @@ impl Record {
     }
 }
 
-impl fmt::Debug for Record {
+impl Default for Record {
+    fn default() -> Self {
+        Self(Fields::default())
+    }
+}
+
+impl<B> fmt::Debug for Record<B>
+where
+    B: AsRef<[u8]>,
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Record")
             .field("reference_sequence_id", &self.reference_sequence_id())
@@ -214,7 +273,10 @@ impl fmt::Debug for Record {
     }
 }
 
-impl sam::alignment::Record for Record {
+impl<B> sam::alignment::Record for Record<B>
+where
+    B: AsRef<[u8]>,
+{
     fn name(&self) -> Option<Box<dyn sam::alignment::record::Name + '_>> {
         let name = self.name()?;
         Some(Box::new(name))
@@ -8,21 +8,44 @@ use self::bounds::Bounds;
 use super::{Cigar, Data, Name, QualityScores, Sequence};
 
 #[derive(Clone, Eq, PartialEq)]
-pub(crate) struct Fields {
-    pub(crate) buf: Vec<u8>,
+pub(crate) struct Fields<B = Vec<u8>> {
+    pub(crate) buf: B,
     pub(crate) bounds: Bounds,
 }
 
-impl Fields {
+impl<B> Fields<B>
+where
+    B: AsRef<[u8]>,
+{
+    pub(crate) fn try_new(buf: B) -> io::Result<Self> {
+        let mut fields = Self {
+            buf,
+            bounds: Bounds {
+                name_end: 0,
+                cigar_end: 0,
+                sequence_end: 0,
+                quality_scores_end: 0,
+            },
+        };
+
+        fields.index()?;
+
+        Ok(fields)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self.buf.as_ref()
+    }
+
     pub(super) fn reference_sequence_id(&self) -> Option<i32> {
-        let src = &self.buf[bounds::REFERENCE_SEQUENCE_ID_RANGE];
+        let src = &self.as_bytes()[bounds::REFERENCE_SEQUENCE_ID_RANGE];
         // SAFETY: `src` is 4 bytes.
         get_reference_sequence_id(src.try_into().unwrap())
     }
 
     // N.B. this is 0-based.
     pub(super) fn alignment_start(&self) -> Option<i32> {
-        let src = &self.buf[bounds::ALIGNMENT_START_RANGE];
+        let src = &self.as_bytes()[bounds::ALIGNMENT_START_RANGE];
         // SAFETY: `src` is 4 bytes.
         get_position(src.try_into().unwrap())
     }
@@ -30,31 +53,31 @@ impl Fields {
     pub(super) fn mapping_quality(&self) -> Option<u8> {
         const MISSING: u8 = 255;
 
-        match self.buf[bounds::MAPPING_QUALITY_INDEX] {
+        match self.as_bytes()[bounds::MAPPING_QUALITY_INDEX] {
             MISSING => None,
             n => Some(n),
         }
     }
 
     pub(super) fn flags(&self) -> u16 {
-        let src = &self.buf[bounds::FLAGS_RANGE];
+        let src = &self.as_bytes()[bounds::FLAGS_RANGE];
         // SAFETY: `src` is 2 bytes.
         u16::from_le_bytes(src.try_into().unwrap())
     }
 
     pub(super) fn mate_reference_sequence_id(&self) -> Option<i32> {
-        let src = &self.buf[bounds::MATE_REFERENCE_SEQUENCE_ID_RANGE];
+        let src = &self.as_bytes()[bounds::MATE_REFERENCE_SEQUENCE_ID_RANGE];
         // SAFETY: `src` is 4 bytes.
         get_reference_sequence_id(src.try_into().unwrap())
     }
 
     pub(super) fn mate_alignment_start(&self) -> Option<i32> {
-        let src = &self.buf[bounds::MATE_ALIGNMENT_START_RANGE];
+        let src = &self.as_bytes()[bounds::MATE_ALIGNMENT_START_RANGE];
         get_position(src.try_into().unwrap())
     }
 
     pub(super) fn template_length(&self) -> i32 {
-        let src = &self.buf[bounds::TEMPLATE_LENGTH_RANGE];
+        let src = &self.as_bytes()[bounds::TEMPLATE_LENGTH_RANGE];
         // SAFETY: `src` is 4 bytes.
         i32::from_le_bytes(src.try_into().unwrap())
     }
@@ -62,7 +85,7 @@ impl Fields {
     pub(super) fn name(&self) -> Option<Name<'_>> {
         const MISSING: &[u8] = &[b'*', 0x00];
 
-        match &self.buf[self.bounds.name_range()] {
+        match &self.as_bytes()[self.bounds.name_range()] {
             MISSING => None,
             buf => Some(Name::new(buf)),
         }
@@ -80,7 +103,7 @@ impl Fields {
             ((n & 0x0f) as u8, usize::try_from(n >> 4).unwrap())
         }
 
-        let src = &self.buf[self.bounds.cigar_range()];
+        let src = &self.as_bytes()[self.bounds.cigar_range()];
 
         if src.len() == 2 * mem::size_of::<u32>() {
             let k = self.sequence().len();
@@ -90,7 +113,7 @@ impl Fields {
             let op_2 = decode_op(&src[4..8]);
 
             if op_1 == (SOFT_CLIP, k) && matches!(op_2, (SKIP, _)) {
-                let mut data_src = &self.buf[self.bounds.data_range()];
+                let mut data_src = &self.as_bytes()[self.bounds.data_range()];
 
                 if let Ok(Some(buf)) = get_raw_cigar(&mut data_src) {
                     return Cigar::new(buf);
@@ -102,24 +125,24 @@ impl Fields {
     }
 
     pub(super) fn sequence(&self) -> Sequence<'_> {
-        let src = &self.buf[self.bounds.sequence_range()];
+        let src = &self.as_bytes()[self.bounds.sequence_range()];
         let quality_scores_range = self.bounds.quality_scores_range();
         let base_count = quality_scores_range.end - quality_scores_range.start;
         Sequence::new(src, base_count)
     }
 
     pub(super) fn quality_scores(&self) -> QualityScores<'_> {
-        let src = &self.buf[self.bounds.quality_scores_range()];
+        let src = &self.as_bytes()[self.bounds.quality_scores_range()];
         QualityScores::new(src)
     }
 
     pub(super) fn data(&self) -> Data<'_> {
-        let src = &self.buf[self.bounds.data_range()];
+        let src = &self.as_bytes()[self.bounds.data_range()];
         Data::new(src)
     }
 
     pub(crate) fn index(&mut self) -> io::Result<()> {
-        index(&self.buf[..], &mut self.bounds)
+        index(self.buf.as_ref(), &mut self.bounds)
     }
 }
 
@@ -155,19 +178,7 @@ impl TryFrom<Vec<u8>> for Fields {
     type Error = io::Error;
 
     fn try_from(buf: Vec<u8>) -> Result<Self, Self::Error> {
-        let mut fields = Self {
-            buf,
-            bounds: Bounds {
-                name_end: 0,
-                cigar_end: 0,
-                sequence_end: 0,
-                quality_scores_end: 0,
-            },
-        };
-
-        fields.index()?;
-
-        Ok(fields)
+        Self::try_new(buf)
     }
 }
 
@@ -8,7 +8,10 @@ use std::io::{self, Read};
 
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_csi::{
+    binning_index::verifier::{AlignmentContext, RecordReader},
+    BinningIndex,
+};
 use noodles_vcf::{self as vcf, variant::RecordBuf};
 
 use super::{
@@ -106,3 +109,20 @@ where
         self.inner.query(header, &self.index, region)
     }
 }
+
+impl<R> RecordReader for IndexedReader<R>
+where
+    R: bgzf::io::BufRead + bgzf::io::Seek,
+{
+    fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<()> {
+        self.inner.seek(pos)
+    }
+
+    fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+
+    fn read_alignment_context(&mut self) -> io::Result<Option<AlignmentContext>> {
+        self.inner.read_alignment_context()
+    }
+}
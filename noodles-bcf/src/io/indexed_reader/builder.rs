@@ -5,7 +5,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use noodles_bgzf as bgzf;
+use noodles_bgzf::{self as bgzf, storage::Storage};
 use noodles_csi::{self as csi, BinningIndex};
 
 use super::IndexedReader;
@@ -82,6 +82,35 @@ impl Builder {
 
         Ok(IndexedReader::new(reader, index))
     }
+
+    /// Builds an indexed BCF reader from a ranged-read storage.
+    ///
+    /// The storage is read through a cached storage reader ([`bgzf::storage::Reader`]). Use
+    /// [`bgzf::storage::Reader::prefetch`] with the chunk ranges of a query
+    /// ([`csi::binning_index::chunk_ranges`]) to reduce the number of reads from the storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::io::indexed_reader::Builder;
+    /// use noodles_bgzf::storage::Local;
+    /// use noodles_csi as csi;
+    ///
+    /// let index = csi::Index::default();
+    /// let storage = Local::new(io::Cursor::new(Vec::new()));
+    /// let reader = Builder::default().set_index(index).build_from_storage(storage)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build_from_storage<S>(
+        self,
+        storage: S,
+    ) -> io::Result<IndexedReader<bgzf::Reader<bgzf::storage::Reader<S>>>>
+    where
+        S: Storage,
+    {
+        self.build_from_reader(bgzf::storage::Reader::new(storage))
+    }
 }
 
 fn read_associated_index<P>(src: P) -> io::Result<Box<dyn BinningIndex>>
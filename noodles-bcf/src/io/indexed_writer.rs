@@ -0,0 +1,228 @@
+//! Indexed BCF writer.
+
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{
+    self as csi,
+    binning_index::{
+        index::reference_sequence::{bin::Chunk, index::BinnedIndex},
+        Indexer,
+    },
+};
+use noodles_vcf::{self as vcf, variant::io::Write as _};
+
+use super::Writer;
+use crate::Record;
+
+const MIN_SHIFT: u8 = 14;
+const DEPTH: u8 = 5;
+
+/// An indexed BCF writer.
+///
+/// This builds a coordinate-sorted index (CSI) as records are written, avoiding a second pass
+/// over the output. Records must be written in coordinate-sorted order.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bcf as bcf;
+/// use noodles_core::Position;
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::record::value::{map::Contig, Map},
+///     variant::{io::Write, RecordBuf},
+/// };
+///
+/// let mut writer = bcf::io::IndexedWriter::new(Vec::new());
+///
+/// let header = vcf::Header::builder()
+///     .add_contig("sq0", Map::<Contig>::new())
+///     .build();
+///
+/// writer.write_header(&header)?;
+///
+/// let record = RecordBuf::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_variant_start(Position::MIN)
+///     .set_reference_bases("A")
+///     .build();
+///
+/// writer.write_variant_record(&header, &record)?;
+///
+/// let index = writer.finish(&header)?;
+/// assert_eq!(index.reference_sequences().len(), 1);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::Writer<W>>,
+    indexer: Indexer<BinnedIndex>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexed BCF writer with a default compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let writer = bcf::io::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(inner))
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let writer = bcf::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &bgzf::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Writes a VCF header.
+    pub fn write_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a record.
+    pub fn write_record(&mut self, header: &vcf::Header, record: &Record) -> io::Result<()> {
+        self.write_variant_record(header, record)
+    }
+
+    /// Finishes the output stream and returns the built index.
+    ///
+    /// The index has an entry for each contig in the given header.
+    pub fn finish(&mut self, header: &vcf::Header) -> io::Result<csi::Index> {
+        self.inner.try_finish()?;
+
+        let indexer = mem::replace(&mut self.indexer, Indexer::new(MIN_SHIFT, DEPTH));
+        Ok(indexer.build(header.contigs().len()))
+    }
+}
+
+impl<W> From<Writer<bgzf::Writer<W>>> for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: Writer<bgzf::Writer<W>>) -> Self {
+        Self {
+            inner,
+            indexer: Indexer::new(MIN_SHIFT, DEPTH),
+        }
+    }
+}
+
+impl<W> vcf::variant::io::Write for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn write_variant_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_variant_record(
+        &mut self,
+        header: &vcf::Header,
+        record: &dyn vcf::variant::Record,
+    ) -> io::Result<()> {
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_variant_record(header, record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        let chunk = Chunk::new(start_position, end_position);
+
+        let reference_sequence_name = record.reference_sequence_name(header)?;
+
+        let reference_sequence_id = self
+            .inner
+            .string_maps()
+            .contigs()
+            .get_index_of(reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("missing contig in header: {reference_sequence_name}"),
+                )
+            })?;
+
+        let start = record
+            .variant_start()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing position"))?;
+
+        let end = record.variant_end(header)?;
+
+        self.indexer
+            .add_record(Some((reference_sequence_id, start, end, true)), chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_core::Position;
+    use noodles_csi::binning_index::ReferenceSequence as _;
+    use vcf::{
+        header::record::value::{map::Contig, Map},
+        variant::RecordBuf,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_contig("sq0", Map::<Contig>::new())
+            .add_contig("sq1", Map::<Contig>::new())
+            .build();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (name, start) in [("sq0", 8), ("sq0", 13), ("sq1", 5)] {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_name(name)
+                .set_variant_start(Position::try_from(start)?)
+                .set_reference_bases("A")
+                .build();
+
+            writer.write_variant_record(&header, &record)?;
+        }
+
+        let index = writer.finish(&header)?;
+
+        assert_eq!(index.reference_sequences().len(), 2);
+
+        let metadata = index.reference_sequences()[0]
+            .metadata()
+            .expect("missing metadata");
+        assert_eq!(metadata.mapped_record_count(), 2);
+
+        let src = writer.get_ref().get_ref();
+        let mut reader = crate::io::IndexedReader::new(Cursor::new(src), index);
+        let header = reader.read_header()?;
+
+        let region = "sq0:10-20".parse()?;
+        let records: Vec<_> = reader.query(&header, &region)?.collect::<io::Result<_>>()?;
+        assert_eq!(records.len(), 1);
+
+        Ok(())
+    }
+}
@@ -17,7 +17,10 @@ use std::{
 use byteorder::ReadBytesExt;
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_csi::{
+    binning_index::verifier::{AlignmentContext, RecordReader},
+    BinningIndex,
+};
 use noodles_vcf::{self as vcf, header::string_maps::ContigStringMap, variant::RecordBuf};
 
 use self::{header::read_header, record::read_record, record_buf::read_record_buf};
@@ -287,6 +290,39 @@ impl<R> From<R> for Reader<R> {
     }
 }
 
+impl<R> RecordReader for Reader<R>
+where
+    R: bgzf::io::BufRead + bgzf::io::Seek,
+{
+    fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<()> {
+        self.inner.seek_to_virtual_position(pos)?;
+
+        // A BGZF reader does not move when seeking past the end of the stream.
+        if self.inner.virtual_position() < pos {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "invalid virtual position",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+
+    fn read_alignment_context(&mut self) -> io::Result<Option<AlignmentContext>> {
+        let mut record = Record::default();
+
+        if self.read_record(&mut record)? == 0 {
+            return Ok(None);
+        }
+
+        alignment_context(&record).map(Some)
+    }
+}
+
 impl<R> vcf::variant::io::Read<R> for Reader<R>
 where
     R: BufRead,
@@ -336,6 +372,17 @@ where
     Ok((major_version, minor_version))
 }
 
+fn alignment_context(record: &Record) -> io::Result<AlignmentContext> {
+    let Some(start) = record.variant_start().transpose()? else {
+        return Ok(None);
+    };
+
+    let reference_sequence_id = record.reference_sequence_id()?;
+    let end = record.end()?;
+
+    Ok(Some((reference_sequence_id, start, end, true)))
+}
+
 pub(crate) fn resolve_region(
     contig_string_map: &ContigStringMap,
     region: &Region,
@@ -353,7 +400,18 @@ pub(crate) fn resolve_region(
 
 #[cfg(test)]
 mod tests {
+    use noodles_core::Position;
+    use noodles_csi::{
+        self as csi,
+        binning_index::{verifier::Discrepancy, Verifier},
+    };
+    use vcf::{
+        header::record::value::{map::Contig, Map},
+        variant::io::Write,
+    };
+
     use super::*;
+    use crate::io::IndexedWriter;
 
     #[test]
     fn test_read_magic() {
@@ -383,4 +441,57 @@ mod tests {
         assert_eq!(read_format_version(&mut reader)?, (2, 1));
         Ok(())
     }
+
+    #[test]
+    fn test_verify_with_csi() -> Result<(), Box<dyn std::error::Error>> {
+        fn write_records(
+            header: &vcf::Header,
+            starts: &[usize],
+        ) -> Result<(Vec<u8>, csi::Index), Box<dyn std::error::Error>> {
+            let mut writer = IndexedWriter::new(Vec::new());
+            writer.write_header(header)?;
+
+            for &start in starts {
+                let record = RecordBuf::builder()
+                    .set_reference_sequence_name("sq0")
+                    .set_variant_start(Position::try_from(start)?)
+                    .set_reference_bases("A")
+                    .build();
+
+                writer.write_variant_record(header, &record)?;
+            }
+
+            let index = writer.finish(header)?;
+            let data = writer.get_ref().get_ref().clone();
+
+            Ok((data, index))
+        }
+
+        let header = vcf::Header::builder()
+            .add_contig("sq0", Map::<Contig>::new())
+            .build();
+
+        let (data, index) = write_records(&header, &[8, 13, 600_000])?;
+
+        let mut reader = Reader::new(io::Cursor::new(&data));
+        reader.read_header()?;
+        assert!(Verifier::new(&index).verify(&mut reader)?.is_empty());
+
+        // The index of a file with the same record sizes but a different last position.
+        let (_, stale_index) = write_records(&header, &[8, 13, 300_000])?;
+
+        let mut reader = Reader::new(io::Cursor::new(&data));
+        reader.read_header()?;
+        let discrepancies = Verifier::new(&stale_index).verify(&mut reader)?;
+
+        assert!(matches!(
+            discrepancies[..],
+            [Discrepancy::RecordOutsideBin {
+                reference_sequence_id: 0,
+                ..
+            }]
+        ));
+
+        Ok(())
+    }
 }
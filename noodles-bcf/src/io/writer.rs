@@ -123,6 +123,10 @@ where
     pub fn write_record(&mut self, header: &vcf::Header, record: &Record) -> io::Result<()> {
         write_record(&mut self.inner, header, &self.string_maps, record)
     }
+
+    pub(crate) fn string_maps(&self) -> &StringMaps {
+        &self.string_maps
+    }
 }
 
 impl<W> Writer<bgzf::Writer<W>>
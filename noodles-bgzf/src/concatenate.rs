@@ -0,0 +1,116 @@
+use std::io::{self, Read, Write};
+
+use super::{
+    reader::frame::{is_valid_header, read_frame_into},
+    writer::BGZF_EOF,
+};
+
+/// Concatenates BGZF streams.
+///
+/// Blocks are copied verbatim, i.e., without being decompressed. EOF markers are stripped from
+/// the sources, and a single EOF marker is written at the end of the output.
+///
+/// This returns the number of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Read, Write};
+/// use noodles_bgzf as bgzf;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let a = writer.finish()?;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"-bgzf")?;
+/// let b = writer.finish()?;
+///
+/// let mut dst = Vec::new();
+/// bgzf::concatenate([&a[..], &b[..]], &mut dst)?;
+///
+/// let mut reader = bgzf::Reader::new(&dst[..]);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf)?;
+/// assert_eq!(buf, b"noodles-bgzf");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn concatenate<I, R, W>(readers: I, mut writer: W) -> io::Result<u64>
+where
+    I: IntoIterator<Item = R>,
+    R: Read,
+    W: Write,
+{
+    let mut buf = Vec::new();
+    let mut len = 0;
+
+    for mut reader in readers {
+        while read_frame_into(&mut reader, &mut buf)?.is_some() {
+            if !is_valid_header(&buf[..]) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid BGZF header",
+                ));
+            }
+
+            if buf == BGZF_EOF {
+                continue;
+            }
+
+            writer.write_all(&buf)?;
+            len += buf.len() as u64;
+        }
+    }
+
+    writer.write_all(BGZF_EOF)?;
+    len += BGZF_EOF.len() as u64;
+
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Reader, Writer};
+
+    #[test]
+    fn test_concatenate() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"-")?;
+        let a = writer.finish()?;
+
+        let b = BGZF_EOF.to_vec();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"bgzf")?;
+        let c = writer.finish()?;
+
+        let mut dst = Vec::new();
+        let len = concatenate([&a[..], &b[..], &c[..]], &mut dst)?;
+        assert_eq!(len, dst.len() as u64);
+
+        let eof_len = BGZF_EOF.len();
+        let mut expected = a[..a.len() - eof_len].to_vec();
+        expected.extend_from_slice(&c);
+        assert_eq!(dst, expected);
+
+        let mut buf = Vec::new();
+        Reader::new(&dst[..]).read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodles-bgzf");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_concatenate_with_invalid_header() {
+        let mut src = BGZF_EOF.to_vec();
+        src[0] = 0x00;
+
+        assert!(matches!(
+            concatenate([&src[..]], io::sink()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
@@ -8,15 +8,16 @@
 pub mod r#async;
 
 mod reader;
+mod writer;
 
-pub use self::reader::Reader;
+pub use self::{reader::Reader, writer::Writer};
 
 #[cfg(feature = "async")]
-pub use self::r#async::Reader as AsyncReader;
+pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
 
 use std::{
     fs::File,
-    io::{self, BufReader},
+    io::{self, BufReader, BufWriter, Write},
     path::Path,
 };
 
@@ -43,3 +44,26 @@ where
     let mut reader = File::open(src).map(BufReader::new).map(Reader::new)?;
     reader.read_index()
 }
+
+/// Writes a gzip index to a file.
+///
+/// This is a convenience function and is equivalent to creating the given path and writing the
+/// index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = vec![(0, 0)];
+/// gzi::write("out.gz.gzi", &index)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn write<P>(dst: P, index: &Index) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let mut writer = File::create(dst).map(BufWriter::new).map(Writer::new)?;
+    writer.write_index(index)?;
+    writer.into_inner().flush()
+}
@@ -1,14 +1,15 @@
 //! Async gzip index.
 
 mod reader;
+mod writer;
 
-pub use self::reader::Reader;
+pub use self::{reader::Reader, writer::Writer};
 
 use std::path::Path;
 
 use tokio::{
     fs::File,
-    io::{self, BufReader},
+    io::{self, BufReader, BufWriter},
 };
 
 use super::Index;
@@ -37,3 +38,36 @@ where
     let mut reader = File::open(src).await.map(BufReader::new).map(Reader::new)?;
     reader.read_index().await
 }
+
+/// Writes a gzip index to a file.
+///
+/// This is a convenience function and is equivalent to creating the given path and writing the
+/// index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// #
+/// # #[tokio::main]
+/// # async fn main() -> io::Result<()> {
+/// use noodles_bgzf::gzi;
+/// let index = vec![(0, 0)];
+/// gzi::r#async::write("out.gz.gzi", &index).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn write<P>(dst: P, index: &Index) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let mut writer = File::create(dst)
+        .await
+        .map(BufWriter::new)
+        .map(Writer::new)?;
+
+    writer.write_index(index).await?;
+    writer.shutdown().await?;
+
+    Ok(())
+}
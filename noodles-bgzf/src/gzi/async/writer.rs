@@ -0,0 +1,130 @@
+use tokio::io::{self, AsyncWrite, AsyncWriteExt};
+
+use crate::gzi::{writer::entries, Index};
+
+/// An async gzip index (GZI) writer.
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Creates an async gzip index (GZI) writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::AsyncWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::AsyncWriter::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Shuts down the output stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bgzf::gzi;
+    /// let mut writer = gzi::AsyncWriter::new(Vec::new());
+    /// writer.shutdown().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+
+    /// Writes a gzip index.
+    ///
+    /// The implicit first entry `(0, 0)` is not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bgzf::gzi;
+    ///
+    /// let mut writer = gzi::AsyncWriter::new(Vec::new());
+    /// writer.write_index(&vec![(0, 0)]).await?;
+    ///
+    /// assert_eq!(writer.get_ref(), &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_index(&mut self, index: &Index) -> io::Result<()> {
+        let entries = entries(index);
+
+        let len = u64::try_from(entries.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        self.inner.write_u64_le(len).await?;
+
+        for &(compressed, uncompressed) in entries {
+            self.inner.write_u64_le(compressed).await?;
+            self.inner.write_u64_le(uncompressed).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_index() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer
+            .write_index(&vec![(0, 0), (4668, 21294), (23810, 86529)])
+            .await?;
+
+        let expected = [
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // len = 2
+            0x3c, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 4668
+            0x2e, 0x53, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 21294
+            0x02, 0x5d, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 23810
+            0x01, 0x52, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 86529
+        ];
+
+        assert_eq!(writer.get_ref(), &expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::Index;
+
+/// A gzip index (GZI) writer.
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a gzip index (GZI) writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::Writer::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes a gzip index.
+    ///
+    /// The implicit first entry `(0, 0)` is not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::gzi;
+    ///
+    /// let mut writer = gzi::Writer::new(Vec::new());
+    /// writer.write_index(&vec![(0, 0)])?;
+    ///
+    /// assert_eq!(writer.get_ref(), &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_index(&mut self, index: &Index) -> io::Result<()> {
+        let entries = entries(index);
+
+        let len = u64::try_from(entries.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        self.inner.write_u64::<LittleEndian>(len)?;
+
+        for &(compressed, uncompressed) in entries {
+            self.inner.write_u64::<LittleEndian>(compressed)?;
+            self.inner.write_u64::<LittleEndian>(uncompressed)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(super) fn entries(index: &Index) -> &[(u64, u64)] {
+    const START: (u64, u64) = (0, 0);
+    index.strip_prefix(&[START]).unwrap_or(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_index() -> io::Result<()> {
+        let expected = [
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // len = 2
+            0x3c, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 4668
+            0x2e, 0x53, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 21294
+            0x02, 0x5d, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 23810
+            0x01, 0x52, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 86529
+        ];
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_index(&vec![(0, 0), (4668, 21294), (23810, 86529)])?;
+        assert_eq!(writer.get_ref(), &expected);
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_index(&vec![(4668, 21294), (23810, 86529)])?;
+        assert_eq!(writer.get_ref(), &expected);
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_index(&Vec::new())?;
+        assert_eq!(writer.get_ref(), &[0x00; 8]);
+
+        Ok(())
+    }
+}
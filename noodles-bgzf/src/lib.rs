@@ -38,6 +38,7 @@
 pub mod r#async;
 
 mod block;
+mod concatenate;
 pub(crate) mod deflate;
 mod gz;
 pub mod gzi;
@@ -46,13 +47,16 @@ pub mod io;
 mod multithreaded_reader;
 pub mod multithreaded_writer;
 pub mod reader;
+mod recompress;
+pub mod storage;
+pub mod verifier;
 pub mod virtual_position;
 pub mod writer;
 
 pub use self::{
-    indexed_reader::IndexedReader, multithreaded_reader::MultithreadedReader,
-    multithreaded_writer::MultithreadedWriter, reader::Reader, virtual_position::VirtualPosition,
-    writer::Writer,
+    concatenate::concatenate, indexed_reader::IndexedReader,
+    multithreaded_reader::MultithreadedReader, multithreaded_writer::MultithreadedWriter,
+    reader::Reader, recompress::recompress, virtual_position::VirtualPosition, writer::Writer,
 };
 
 #[cfg(feature = "async")]
@@ -13,7 +13,10 @@ use bytes::{Bytes, BytesMut};
 use crossbeam_channel::{Receiver, Sender};
 
 pub use self::builder::Builder;
-use super::writer::{CompressionLevelImpl, MAX_BUF_SIZE};
+use super::{
+    gzi,
+    writer::{CompressionLevelImpl, MAX_BUF_SIZE},
+};
 
 type FrameParts = (Vec<u8>, u32, usize);
 type BufferedTx = Sender<io::Result<FrameParts>>;
@@ -25,7 +28,7 @@ type WriteRx = Receiver<BufferedRx>;
 
 enum State<W> {
     Running {
-        writer_handle: JoinHandle<io::Result<W>>,
+        writer_handle: JoinHandle<io::Result<(W, Option<gzi::Index>)>>,
         deflater_handles: Vec<JoinHandle<()>>,
         write_tx: WriteTx,
         deflate_tx: DeflateTx,
@@ -42,6 +45,7 @@ where
 {
     state: State<W>,
     buf: BytesMut,
+    gzi_index: Option<gzi::Index>,
 }
 
 impl<W> MultithreadedWriter<W>
@@ -110,12 +114,41 @@ where
 
                 drop(write_tx);
 
-                writer_handle.join().unwrap()
+                let (writer, gzi_index) = writer_handle.join().unwrap()?;
+                self.gzi_index = gzi_index;
+
+                Ok(writer)
             }
             State::Done => panic!("invalid state"),
         }
     }
 
+    /// Returns the gzip index (GZI) built while writing.
+    ///
+    /// This is only available if enabled using [`Builder::set_build_gzi_index`] and after the
+    /// writer is finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::multithreaded_writer::Builder;
+    ///
+    /// let mut writer = Builder::default()
+    ///     .set_build_gzi_index(true)
+    ///     .build_from_writer(io::sink());
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// assert!(writer.gzi_index().is_none());
+    ///
+    /// writer.finish()?;
+    /// assert_eq!(writer.gzi_index().map(|index| index.len()), Some(2));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn gzi_index(&self) -> Option<&gzi::Index> {
+        self.gzi_index.as_ref()
+    }
+
     fn remaining(&self) -> usize {
         MAX_BUF_SIZE - self.buf.len()
     }
@@ -181,23 +214,37 @@ where
     }
 }
 
-fn spawn_writer<W>(mut writer: W, write_rx: WriteRx) -> JoinHandle<io::Result<W>>
+fn spawn_writer<W>(
+    mut writer: W,
+    write_rx: WriteRx,
+    build_gzi_index: bool,
+) -> JoinHandle<io::Result<(W, Option<gzi::Index>)>>
 where
     W: Write + Send + 'static,
 {
-    use super::writer::{write_frame, BGZF_EOF};
+    use super::writer::{add_gzi_index_entry, write_frame, BGZF_EOF};
 
     thread::spawn(move || {
+        let mut position = 0;
+        let mut gzi_index = build_gzi_index.then(|| vec![(0, 0)]);
+
         while let Ok(buffered_rx) = write_rx.recv() {
             if let Ok(result) = buffered_rx.recv() {
                 let (compressed_data, crc32, uncompressed_len) = result?;
-                write_frame(&mut writer, &compressed_data, crc32, uncompressed_len)?;
+                let block_size =
+                    write_frame(&mut writer, &compressed_data, crc32, uncompressed_len)?;
+
+                position += block_size as u64;
+
+                if let Some(index) = gzi_index.as_mut() {
+                    add_gzi_index_entry(index, position, uncompressed_len);
+                }
             }
         }
 
         writer.write_all(BGZF_EOF)?;
 
-        Ok(writer)
+        Ok((writer, gzi_index))
     })
 }
 
@@ -231,3 +278,31 @@ fn compress(src: &[u8], compression_level: CompressionLevelImpl) -> io::Result<F
     let crc32 = deflate::encode(src, compression_level, &mut dst)?;
     Ok((dst, crc32, src.len()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gzi_index() -> io::Result<()> {
+        let data = vec![b'n'; MAX_BUF_SIZE + 8];
+
+        let mut writer = crate::writer::Builder::default()
+            .set_build_gzi_index(true)
+            .build_with_writer(Vec::new());
+        writer.write_all(&data)?;
+        writer.try_finish()?;
+        let expected = writer.gzi_index().cloned();
+
+        let mut writer = Builder::default()
+            .set_worker_count(NonZeroUsize::try_from(2).unwrap())
+            .set_build_gzi_index(true)
+            .build_from_writer(Vec::new());
+        writer.write_all(&data)?;
+        writer.finish()?;
+
+        assert_eq!(writer.gzi_index().cloned(), expected);
+
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@ use crate::writer::CompressionLevel;
 pub struct Builder {
     compression_level: CompressionLevel,
     worker_count: NonZeroUsize,
+    build_gzi_index: bool,
 }
 
 impl Builder {
@@ -39,6 +40,23 @@ impl Builder {
         self
     }
 
+    /// Sets whether to build a gzip index (GZI) while writing.
+    ///
+    /// When enabled, the compressed and uncompressed offsets of each written block are recorded.
+    /// The index is available using [`MultithreadedWriter::gzi_index`] after the writer is
+    /// finished. By default, this is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::multithreaded_writer::Builder;
+    /// let builder = Builder::default().set_build_gzi_index(true);
+    /// ```
+    pub fn set_build_gzi_index(mut self, build_gzi_index: bool) -> Self {
+        self.build_gzi_index = build_gzi_index;
+        self
+    }
+
     /// Builds a multithreaded BGZF writer from a writer.
     ///
     /// # Examples
@@ -59,7 +77,7 @@ impl Builder {
         let (write_tx, write_rx) = crossbeam_channel::bounded(worker_count);
         let (deflate_tx, deflate_rx) = crossbeam_channel::bounded(worker_count);
 
-        let writer_handle = spawn_writer(writer, write_rx);
+        let writer_handle = spawn_writer(writer, write_rx, self.build_gzi_index);
         let deflater_handles =
             spawn_deflaters(self.compression_level, self.worker_count, deflate_rx);
 
@@ -71,6 +89,7 @@ impl Builder {
                 deflate_tx,
             },
             buf: BytesMut::new(),
+            gzi_index: None,
         }
     }
 }
@@ -80,6 +99,7 @@ impl Default for Builder {
         Self {
             compression_level: CompressionLevel::default(),
             worker_count: NonZeroUsize::MIN,
+            build_gzi_index: false,
         }
     }
 }
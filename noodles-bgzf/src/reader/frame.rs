@@ -5,7 +5,7 @@ use flate2::Crc;
 
 use crate::{gz, Block, BGZF_HEADER_SIZE};
 
-const MIN_FRAME_SIZE: usize = BGZF_HEADER_SIZE + gz::TRAILER_SIZE;
+pub(crate) const MIN_FRAME_SIZE: usize = BGZF_HEADER_SIZE + gz::TRAILER_SIZE;
 
 pub(crate) fn read_frame_into<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<Option<()>>
 where
@@ -66,7 +66,7 @@ fn parse_header(src: &[u8]) -> io::Result<()> {
     }
 }
 
-fn is_valid_header<B>(mut src: B) -> bool
+pub(crate) fn is_valid_header<B>(mut src: B) -> bool
 where
     B: Buf,
 {
@@ -102,7 +102,7 @@ where
         && subfield_len == BGZF_SLEN
 }
 
-fn parse_trailer<B>(mut src: B) -> io::Result<(u32, usize)>
+pub(crate) fn parse_trailer<B>(mut src: B) -> io::Result<(u32, usize)>
 where
     B: Buf,
 {
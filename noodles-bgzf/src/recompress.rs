@@ -0,0 +1,225 @@
+use std::{
+    io::{self, Read, Write},
+    num::NonZeroUsize,
+    thread,
+};
+
+use crossbeam_channel::{Receiver, Sender};
+
+use super::{
+    deflate,
+    reader::frame::{parse_block, read_frame_into},
+    writer::{write_frame, CompressionLevel, CompressionLevelImpl, BGZF_EOF, MAX_BUF_SIZE},
+    Block,
+};
+
+type FrameParts = (Vec<u8>, u32, usize);
+type BufferedTx = Sender<io::Result<Vec<FrameParts>>>;
+type BufferedRx = Receiver<io::Result<Vec<FrameParts>>>;
+
+/// Recompresses a BGZF stream.
+///
+/// Blocks are decompressed and compressed again at the given compression level using a pool of
+/// `worker_count` threads. The uncompressed data is not otherwise interpreted.
+///
+/// Each input block is written as its own output block, except that a block whose uncompressed
+/// data exceeds the maximum uncompressed size of a written block is split into multiple blocks,
+/// and empty blocks, including EOF markers, are dropped. A single EOF marker is written at the
+/// end of the output only if the entire input is read successfully.
+///
+/// This returns the number of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{io::{self, Read, Write}, num::NonZeroUsize};
+/// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let src = writer.finish()?;
+///
+/// let mut dst = Vec::new();
+/// bgzf::recompress(&src[..], &mut dst, CompressionLevel::BEST, NonZeroUsize::MIN)?;
+///
+/// let mut reader = bgzf::Reader::new(&dst[..]);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf)?;
+/// assert_eq!(buf, b"noodles");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn recompress<R, W>(
+    mut reader: R,
+    mut writer: W,
+    compression_level: CompressionLevel,
+    worker_count: NonZeroUsize,
+) -> io::Result<u64>
+where
+    R: Read,
+    W: Write + Send,
+{
+    let compression_level = CompressionLevelImpl::from(compression_level);
+
+    let mut len = thread::scope(|scope| {
+        let (deflate_tx, deflate_rx) =
+            crossbeam_channel::bounded::<(Vec<u8>, BufferedTx)>(worker_count.get());
+        let (write_tx, write_rx) = crossbeam_channel::bounded::<BufferedRx>(worker_count.get());
+
+        for _ in 0..worker_count.get() {
+            let deflate_rx = deflate_rx.clone();
+
+            scope.spawn(move || {
+                let mut block = Block::default();
+
+                while let Ok((src, buffered_tx)) = deflate_rx.recv() {
+                    let result = recompress_frame(&src, &mut block, compression_level);
+                    buffered_tx.send(result).ok();
+                }
+            });
+        }
+
+        drop(deflate_rx);
+
+        let dst = &mut writer;
+        let writer_handle = scope.spawn(move || -> io::Result<u64> {
+            let mut len = 0;
+
+            while let Ok(buffered_rx) = write_rx.recv() {
+                let frames = buffered_rx
+                    .recv()
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))??;
+
+                for (compressed_data, crc32, uncompressed_len) in frames {
+                    let block_size = write_frame(dst, &compressed_data, crc32, uncompressed_len)?;
+                    len += block_size as u64;
+                }
+            }
+
+            Ok(len)
+        });
+
+        let read_result = (|| {
+            loop {
+                let mut buf = Vec::new();
+
+                if read_frame_into(&mut reader, &mut buf)?.is_none() {
+                    break;
+                }
+
+                let (buffered_tx, buffered_rx) = crossbeam_channel::bounded(1);
+
+                // The writer only hangs up on an error, which is returned when it is joined.
+                if write_tx.send(buffered_rx).is_err() {
+                    break;
+                }
+
+                deflate_tx.send((buf, buffered_tx)).ok();
+            }
+
+            Ok(())
+        })();
+
+        drop(write_tx);
+        drop(deflate_tx);
+
+        let write_result = writer_handle.join().unwrap();
+
+        read_result.and(write_result)
+    })?;
+
+    writer.write_all(BGZF_EOF)?;
+    len += BGZF_EOF.len() as u64;
+
+    Ok(len)
+}
+
+fn recompress_frame(
+    src: &[u8],
+    block: &mut Block,
+    compression_level: CompressionLevelImpl,
+) -> io::Result<Vec<FrameParts>> {
+    parse_block(src, block)?;
+
+    // A block may decompress to more than `MAX_BUF_SIZE` bytes, which is not guaranteed to fit in
+    // a single block when compressed again.
+    block
+        .data()
+        .as_ref()
+        .chunks(MAX_BUF_SIZE)
+        .map(|buf| {
+            let mut dst = Vec::new();
+            let crc32 = deflate::encode(buf, compression_level, &mut dst)?;
+            Ok((dst, crc32, buf.len()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Reader, Writer};
+
+    #[test]
+    fn test_recompress() -> io::Result<()> {
+        let data: Vec<_> = (0..=255).cycle().take(MAX_BUF_SIZE * 3 + 8).collect();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&data)?;
+        let src = writer.finish()?;
+
+        let mut dst = Vec::new();
+        let len = recompress(
+            &src[..],
+            &mut dst,
+            CompressionLevel::NONE,
+            NonZeroUsize::try_from(2).unwrap(),
+        )?;
+        assert_eq!(len, dst.len() as u64);
+        assert!(dst.ends_with(BGZF_EOF));
+
+        let mut buf = Vec::new();
+        Reader::new(&dst[..]).read_to_end(&mut buf)?;
+        assert_eq!(buf, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recompress_with_invalid_block() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        let mut src = writer.finish()?;
+
+        // compressed data
+        src[25] ^= 0xff;
+
+        assert!(matches!(
+            recompress(&src[..], io::sink(), CompressionLevel::BEST, NonZeroUsize::MIN),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recompress_with_truncated_source() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let mut src = writer.finish()?;
+
+        src.truncate(src.len() - BGZF_EOF.len() - 4);
+
+        let mut dst = Vec::new();
+
+        assert!(matches!(
+            recompress(&src[..], &mut dst, CompressionLevel::BEST, NonZeroUsize::MIN),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        assert!(!dst.ends_with(BGZF_EOF));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+//! Ranged-read storage.
+//!
+//! A [`Storage`] reads byte ranges from a source, e.g., a local file or an object in remote
+//! storage. [`Reader`] adapts a storage to [`std::io::Read`] and [`std::io::Seek`] with a block
+//! cache, which allows it to be used as the inner reader of a BGZF reader and, in turn, format
+//! indexed readers.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io::{self, Read, Write};
+//! use noodles_bgzf::{self as bgzf, storage::{self, Local}};
+//!
+//! let mut writer = bgzf::Writer::new(Vec::new());
+//! writer.write_all(b"noodles")?;
+//! let data = writer.finish()?;
+//!
+//! let storage = Local::new(io::Cursor::new(data));
+//! let mut reader = bgzf::Reader::new(storage::Reader::new(storage));
+//!
+//! reader.seek(bgzf::VirtualPosition::from(3))?;
+//!
+//! let mut buf = Vec::new();
+//! reader.read_to_end(&mut buf)?;
+//! assert_eq!(buf, b"dles");
+//! # Ok::<_, io::Error>(())
+//! ```
+
+#[cfg(feature = "http")]
+mod http;
+mod local;
+pub mod reader;
+
+#[cfg(feature = "http")]
+pub use self::http::Http;
+pub use self::{local::Local, reader::Reader};
+
+use std::{io, ops::Range};
+
+/// A ranged-read storage.
+pub trait Storage {
+    /// Returns the size of the source in bytes.
+    fn len(&mut self) -> io::Result<u64>;
+
+    /// Returns whether the source is empty.
+    fn is_empty(&mut self) -> io::Result<bool> {
+        self.len().map(|n| n == 0)
+    }
+
+    /// Reads bytes starting at the given offset.
+    ///
+    /// This fills as much of the buffer as possible and returns the number of bytes read, which
+    /// is only less than the buffer length when the end of the source is reached.
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<usize>;
+}
+
+impl<S> Storage for &mut S
+where
+    S: Storage + ?Sized,
+{
+    fn len(&mut self) -> io::Result<u64> {
+        (**self).len()
+    }
+
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        (**self).read_at(offset, buf)
+    }
+}
+
+impl<S> Storage for Box<S>
+where
+    S: Storage + ?Sized,
+{
+    fn len(&mut self) -> io::Result<u64> {
+        (**self).len()
+    }
+
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        (**self).read_at(offset, buf)
+    }
+}
+
+/// Merges byte ranges that overlap or are separated by at most `max_gap` bytes.
+///
+/// The input ranges do not need to be sorted. The merged ranges are sorted by start.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bgzf::storage;
+/// let ranges = [0..8, 13..21, 144..233];
+/// assert_eq!(storage::coalesce_ranges(&ranges, 8), [0..21, 144..233]);
+/// ```
+pub fn coalesce_ranges(ranges: &[Range<u64>], max_gap: u64) -> Vec<Range<u64>> {
+    let mut ranges: Vec<_> = ranges.iter().filter(|r| !r.is_empty()).cloned().collect();
+    ranges.sort_unstable_by_key(|r| r.start);
+
+    let mut merged_ranges: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+
+    for range in ranges {
+        if let Some(last_range) = merged_ranges.last_mut() {
+            if range.start <= last_range.end.saturating_add(max_gap) {
+                last_range.end = last_range.end.max(range.end);
+                continue;
+            }
+        }
+
+        merged_ranges.push(range);
+    }
+
+    merged_ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_coalesce_ranges() {
+        assert!(coalesce_ranges(&[], 0).is_empty());
+        assert_eq!(coalesce_ranges(&[5..8, 0..5], 0), [0..8]);
+        assert_eq!(coalesce_ranges(&[0..5, 6..8], 0), [0..5, 6..8]);
+        assert_eq!(coalesce_ranges(&[0..5, 6..8], 1), [0..8]);
+        assert_eq!(coalesce_ranges(&[0..13, 2..5, 8..8], 0), [0..13]);
+    }
+}
@@ -0,0 +1,266 @@
+use std::io::{self, Read};
+
+use reqwest::{
+    blocking::{Client, Response},
+    header::{CONTENT_RANGE, RANGE},
+    StatusCode, Url,
+};
+
+use super::Storage;
+
+/// An HTTP storage.
+///
+/// This reads ranges from a remote resource using HTTP range requests.
+pub struct Http {
+    client: Client,
+    url: Url,
+}
+
+impl Http {
+    /// Creates an HTTP storage with a default client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::storage::Http;
+    /// let url = "https://example.com/sample.bam".parse()?;
+    /// let storage = Http::new(url);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(url: Url) -> Self {
+        Self::with_client(Client::new(), url)
+    }
+
+    /// Creates an HTTP storage with a client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::storage::Http;
+    /// let client = reqwest::blocking::Client::new();
+    /// let url = "https://example.com/sample.bam".parse()?;
+    /// let storage = Http::with_client(client, url);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_client(client: Client, url: Url) -> Self {
+        Self { client, url }
+    }
+
+    /// Returns the URL of the resource.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::storage::Http;
+    /// let url = "https://example.com/sample.bam".parse()?;
+    /// let storage = Http::new(url);
+    /// assert_eq!(storage.url().as_str(), "https://example.com/sample.bam");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    fn get_range(&self, start: u64, end: u64) -> io::Result<Response> {
+        self.client
+            .get(self.url.clone())
+            .header(RANGE, format!("bytes={start}-{end}"))
+            .send()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+impl Storage for Http {
+    fn len(&mut self) -> io::Result<u64> {
+        let response = self.get_range(0, 0)?;
+
+        match response.status() {
+            StatusCode::PARTIAL_CONTENT | StatusCode::RANGE_NOT_SATISFIABLE => {
+                let content_range = response
+                    .headers()
+                    .get(CONTENT_RANGE)
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidData, "missing Content-Range")
+                    })?
+                    .to_str()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                parse_content_range_length(content_range)
+            }
+            StatusCode::OK => response.content_length().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length")
+            }),
+            status => Err(invalid_status_error(status)),
+        }
+    }
+
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let end = offset + (buf.len() as u64 - 1);
+        let response = self.get_range(offset, end)?;
+
+        match response.status() {
+            StatusCode::PARTIAL_CONTENT => read_body(response, buf),
+            StatusCode::RANGE_NOT_SATISFIABLE => Ok(0),
+            StatusCode::OK => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "server does not support range requests",
+            )),
+            status => Err(invalid_status_error(status)),
+        }
+    }
+}
+
+fn read_body(mut response: Response, mut buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+
+    while !buf.is_empty() {
+        match response.read(buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                buf = &mut buf[n..];
+                len += n;
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(len)
+}
+
+// `Content-Range: bytes <start>-<end>/<length>` or `Content-Range: bytes */<length>`
+fn parse_content_range_length(s: &str) -> io::Result<u64> {
+    s.strip_prefix("bytes ")
+        .and_then(|t| t.split_once('/'))
+        .and_then(|(_, length)| length.parse().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid Content-Range"))
+}
+
+fn invalid_status_error(status: StatusCode) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        format!("unexpected HTTP status: {status}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    // A minimal HTTP/1.1 server that serves `data` and supports single range requests.
+    fn serve(data: &'static [u8], supports_ranges: bool) -> io::Result<Url> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+
+                let mut range = None;
+
+                let mut reader = BufReader::new(&mut stream);
+                let mut line = String::new();
+
+                while reader.read_line(&mut line).is_ok() {
+                    let header = line.trim_end();
+
+                    if header.is_empty() {
+                        break;
+                    } else if let Some(s) =
+                        header.to_ascii_lowercase().strip_prefix("range: bytes=")
+                    {
+                        let (start, end) = s.split_once('-').unwrap();
+                        range = Some((start.parse::<u64>().unwrap(), end.parse::<u64>().unwrap()));
+                    }
+
+                    line.clear();
+                }
+
+                let len = data.len() as u64;
+
+                let (head, body) = match range {
+                    Some((start, _)) if supports_ranges && start >= len => (
+                        format!("HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{len}\r\nContent-Length: 0\r\n"),
+                        &[][..],
+                    ),
+                    Some((start, end)) if supports_ranges => {
+                        let end = end.min(len - 1);
+                        let body = &data[start as usize..=end as usize];
+                        (
+                            format!(
+                                "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {start}-{end}/{len}\r\nContent-Length: {}\r\n",
+                                body.len()
+                            ),
+                            body,
+                        )
+                    }
+                    _ => (
+                        format!("HTTP/1.1 200 OK\r\nContent-Length: {len}\r\n"),
+                        data,
+                    ),
+                };
+
+                let _ = write!(stream, "{head}Connection: close\r\n\r\n");
+                let _ = stream.write_all(body);
+            }
+        });
+
+        format!("http://{addr}/sample.bin")
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+
+    #[test]
+    fn test_len() -> io::Result<()> {
+        let mut storage = Http::new(serve(b"noodles", true)?);
+        assert_eq!(storage.len()?, 7);
+
+        let mut storage = Http::new(serve(b"noodles", false)?);
+        assert_eq!(storage.len()?, 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_at() -> io::Result<()> {
+        let mut storage = Http::new(serve(b"noodles", true)?);
+
+        let mut buf = [0; 4];
+        assert_eq!(storage.read_at(2, &mut buf)?, 4);
+        assert_eq!(&buf, b"odle");
+
+        assert_eq!(storage.read_at(5, &mut buf)?, 2);
+        assert_eq!(&buf[..2], b"es");
+
+        assert_eq!(storage.read_at(13, &mut buf)?, 0);
+
+        let mut storage = Http::new(serve(b"noodles", false)?);
+        assert!(matches!(
+            storage.read_at(0, &mut buf),
+            Err(e) if e.kind() == io::ErrorKind::Unsupported
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_content_range_length() -> io::Result<()> {
+        assert_eq!(parse_content_range_length("bytes 0-0/8")?, 8);
+        assert_eq!(parse_content_range_length("bytes */0")?, 0);
+        assert!(parse_content_range_length("bytes 0-0").is_err());
+        assert!(parse_content_range_length("0-0/8").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use super::Storage;
+
+/// A local storage.
+///
+/// This reads ranges from a seekable reader, e.g., a local file.
+pub struct Local<R> {
+    inner: R,
+}
+
+impl Local<File> {
+    /// Opens a local file as storage.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bgzf::storage::Local;
+    /// let storage = Local::open("sample.bam")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn open<P>(src: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        File::open(src).map(Self::new)
+    }
+}
+
+impl<R> Local<R> {
+    /// Creates a local storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bgzf::storage::Local;
+    /// let storage = Local::new(io::empty());
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bgzf::storage::Local;
+    /// let storage = Local::new(io::empty());
+    /// let inner = storage.get_ref();
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bgzf::storage::Local;
+    /// let storage = Local::new(io::empty());
+    /// let inner = storage.into_inner();
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Storage for Local<R>
+where
+    R: Read + Seek,
+{
+    fn len(&mut self) -> io::Result<u64> {
+        self.inner.seek(SeekFrom::End(0))
+    }
+
+    fn read_at(&mut self, offset: u64, mut buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.seek(SeekFrom::Start(offset))?;
+
+        let mut len = 0;
+
+        while !buf.is_empty() {
+            match self.inner.read(buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    buf = &mut buf[n..];
+                    len += n;
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_read_at() -> io::Result<()> {
+        let mut storage = Local::new(Cursor::new(b"noodles"));
+
+        assert_eq!(storage.len()?, 7);
+
+        let mut buf = [0; 4];
+        assert_eq!(storage.read_at(2, &mut buf)?, 4);
+        assert_eq!(&buf, b"odle");
+
+        assert_eq!(storage.read_at(5, &mut buf)?, 2);
+        assert_eq!(&buf[..2], b"es");
+
+        assert_eq!(storage.read_at(13, &mut buf)?, 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,391 @@
+//! Storage reader.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    ops::Range,
+};
+
+use indexmap::IndexMap;
+
+use super::Storage;
+
+/// A storage reader.
+///
+/// This reads a storage as a byte stream. Reads are made in fixed-size pages, which are kept in a
+/// least recently used (LRU) cache.
+pub struct Reader<S> {
+    storage: S,
+    position: u64,
+    len: Option<u64>,
+    cache: Cache,
+}
+
+impl<S> Reader<S>
+where
+    S: Storage,
+{
+    /// Creates a storage reader with a default cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bgzf::storage::{self, Local};
+    /// let reader = storage::Reader::new(Local::new(io::empty()));
+    /// ```
+    pub fn new(storage: S) -> Self {
+        Builder::default().build_from_storage(storage)
+    }
+
+    /// Returns a reference to the underlying storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bgzf::storage::{self, Local};
+    /// let reader = storage::Reader::new(Local::new(io::empty()));
+    /// let storage = reader.get_ref();
+    /// ```
+    pub fn get_ref(&self) -> &S {
+        &self.storage
+    }
+
+    /// Returns a mutable reference to the underlying storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bgzf::storage::{self, Local};
+    /// let mut reader = storage::Reader::new(Local::new(io::empty()));
+    /// let storage = reader.get_mut();
+    /// ```
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Returns the underlying storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bgzf::storage::{self, Local};
+    /// let reader = storage::Reader::new(Local::new(io::empty()));
+    /// let storage = reader.into_inner();
+    /// ```
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    /// Reads the given byte ranges into the cache.
+    ///
+    /// Each range is read from the storage with as few reads as possible, skipping pages that are
+    /// already cached. Ranges are typically first merged using
+    /// [`coalesce_ranges`](super::coalesce_ranges).
+    ///
+    /// Pages may be evicted if the ranges are larger than the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use noodles_bgzf::storage::{self, Local};
+    ///
+    /// let mut reader = storage::Reader::new(Local::new(io::Cursor::new(b"noodles")));
+    /// reader.prefetch(&[0..7])?;
+    ///
+    /// let mut buf = Vec::new();
+    /// reader.read_to_end(&mut buf)?;
+    /// assert_eq!(buf, b"noodles");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn prefetch(&mut self, ranges: &[Range<u64>]) -> io::Result<()> {
+        let page_size = self.cache.page_size as u64;
+
+        for range in ranges.iter().filter(|r| !r.is_empty()) {
+            let start_page = range.start / page_size;
+            let end_page = (range.end - 1) / page_size + 1;
+
+            let mut page = start_page;
+
+            while page < end_page {
+                if self.cache.contains(page) {
+                    page += 1;
+                    continue;
+                }
+
+                let run_start = page;
+
+                while page < end_page && !self.cache.contains(page) {
+                    page += 1;
+                }
+
+                self.read_pages(run_start..page)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_pages(&mut self, pages: Range<u64>) -> io::Result<()> {
+        let page_size = self.cache.page_size;
+
+        let page_count = usize::try_from(pages.end - pages.start)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let mut buf = vec![0; page_count * page_size];
+        let len = self
+            .storage
+            .read_at(pages.start * page_size as u64, &mut buf)?;
+        buf.truncate(len);
+
+        // Pages at or past the end of the storage are cached with partial or no data.
+        let mut chunks = buf.chunks(page_size);
+
+        for page in pages {
+            let data = chunks.next().unwrap_or_default();
+            self.cache.insert(page, data.to_vec());
+        }
+
+        Ok(())
+    }
+
+    fn len(&mut self) -> io::Result<u64> {
+        if let Some(len) = self.len {
+            return Ok(len);
+        }
+
+        let len = self.storage.len()?;
+        self.len = Some(len);
+
+        Ok(len)
+    }
+}
+
+impl<S> Read for Reader<S>
+where
+    S: Storage,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let page_size = self.cache.page_size as u64;
+        let page = self.position / page_size;
+
+        if !self.cache.contains(page) {
+            self.read_pages(page..page + 1)?;
+        }
+
+        let data = self.cache.get(page).unwrap_or_default();
+
+        let offset = (self.position % page_size) as usize;
+
+        let src = data.get(offset..).unwrap_or_default();
+        let amt = src.len().min(buf.len());
+        buf[..amt].copy_from_slice(&src[..amt]);
+
+        self.position += amt as u64;
+
+        Ok(amt)
+    }
+}
+
+impl<S> Seek for Reader<S>
+where
+    S: Storage,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => {
+                self.position = n;
+                return Ok(n);
+            }
+            SeekFrom::End(n) => (self.len()?, n),
+            SeekFrom::Current(n) => (self.position, n),
+        };
+
+        self.position = base.checked_add_signed(offset).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        Ok(self.position)
+    }
+}
+
+// Pages are ordered from least to most recently used.
+struct Cache {
+    page_size: usize,
+    page_count: usize,
+    pages: IndexMap<u64, Vec<u8>>,
+}
+
+impl Cache {
+    fn new(page_size: usize, page_count: usize) -> Self {
+        Self {
+            page_size,
+            page_count,
+            pages: IndexMap::new(),
+        }
+    }
+
+    fn contains(&self, page: u64) -> bool {
+        self.pages.contains_key(&page)
+    }
+
+    fn get(&mut self, page: u64) -> Option<&[u8]> {
+        let i = self.pages.get_index_of(&page)?;
+
+        let last_index = self.pages.len() - 1;
+        self.pages.move_index(i, last_index);
+
+        self.pages
+            .get_index(last_index)
+            .map(|(_, data)| data.as_slice())
+    }
+
+    fn insert(&mut self, page: u64, data: Vec<u8>) {
+        let (i, _) = self.pages.insert_full(page, data);
+
+        let last_index = self.pages.len() - 1;
+        self.pages.move_index(i, last_index);
+
+        while self.pages.len() > self.page_count {
+            self.pages.shift_remove_index(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, num::NonZeroUsize};
+
+    use super::*;
+    use crate::storage::Local;
+
+    struct CountingStorage<S> {
+        inner: S,
+        reads: Vec<Range<u64>>,
+    }
+
+    impl<S> Storage for CountingStorage<S>
+    where
+        S: Storage,
+    {
+        fn len(&mut self) -> io::Result<u64> {
+            self.inner.len()
+        }
+
+        fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+            self.reads.push(offset..offset + buf.len() as u64);
+            self.inner.read_at(offset, buf)
+        }
+    }
+
+    fn build_reader(data: &'static [u8]) -> Reader<CountingStorage<Local<Cursor<&'static [u8]>>>> {
+        let storage = CountingStorage {
+            inner: Local::new(Cursor::new(data)),
+            reads: Vec::new(),
+        };
+
+        Builder::default()
+            .set_page_size(NonZeroUsize::try_from(4).unwrap())
+            .set_page_count(NonZeroUsize::try_from(2).unwrap())
+            .build_from_storage(storage)
+    }
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        let mut reader = build_reader(b"noodles-bgzf");
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodles-bgzf");
+
+        assert_eq!(reader.get_ref().reads, [0..4, 4..8, 8..12, 12..16]);
+
+        assert_eq!(reader.read(&mut [0; 1])?, 0);
+        assert_eq!(reader.get_ref().reads, [0..4, 4..8, 8..12, 12..16]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_cache_hits_and_evictions() -> io::Result<()> {
+        let mut reader = build_reader(b"noodles-bgzf");
+        let mut buf = [0; 2];
+
+        reader.read_exact(&mut buf)?;
+        reader.seek(SeekFrom::Start(4))?;
+        reader.read_exact(&mut buf)?;
+        reader.seek(SeekFrom::Start(2))?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"od");
+        assert_eq!(reader.get_ref().reads, [0..4, 4..8]);
+
+        // Evicts page 1.
+        reader.seek(SeekFrom::Start(8))?;
+        reader.read_exact(&mut buf)?;
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(reader.get_ref().reads, [0..4, 4..8, 8..12]);
+
+        reader.seek(SeekFrom::Start(4))?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"le");
+        assert_eq!(reader.get_ref().reads, [0..4, 4..8, 8..12, 4..8]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_prefetch() -> io::Result<()> {
+        let mut reader = build_reader(b"noodles-bgzf");
+
+        reader.seek(SeekFrom::Start(4))?;
+        reader.read_exact(&mut [0; 1])?;
+
+        reader.prefetch(&[1..11])?;
+        assert_eq!(reader.get_ref().reads, [4..8, 0..4, 8..12]);
+
+        reader.seek(SeekFrom::Start(8))?;
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"bgzf");
+        assert_eq!(reader.get_ref().reads, [4..8, 0..4, 8..12]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_prefetch_with_contiguous_pages() -> io::Result<()> {
+        let mut reader = build_reader(b"noodles-bgzf");
+        reader.prefetch(&[1..7])?;
+        assert_eq!(reader.get_ref().reads, [0..8]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let mut reader = build_reader(b"noodles-bgzf");
+
+        assert_eq!(reader.seek(SeekFrom::End(-4))?, 8);
+        assert_eq!(reader.seek(SeekFrom::Current(-2))?, 6);
+
+        let mut buf = [0; 2];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"s-");
+
+        assert!(reader.seek(SeekFrom::Current(-13)).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use std::num::NonZeroUsize;
+
+use super::{Cache, Reader};
+use crate::storage::Storage;
+
+// The maximum size of a BGZF block.
+const DEFAULT_PAGE_SIZE: usize = 1 << 16;
+
+// 256 * 64 KiB = 16 MiB
+const DEFAULT_PAGE_COUNT: usize = 256;
+
+/// A storage reader builder.
+pub struct Builder {
+    page_size: NonZeroUsize,
+    page_count: NonZeroUsize,
+}
+
+impl Builder {
+    /// Sets the size of a cache page.
+    ///
+    /// This is the minimum size of a read from the storage. By default, this is 64 KiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf::storage::reader::Builder;
+    /// let builder = Builder::default().set_page_size(NonZeroUsize::MIN);
+    /// ```
+    pub fn set_page_size(mut self, page_size: NonZeroUsize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Sets the maximum number of pages in the cache.
+    ///
+    /// By default, this is 256.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf::storage::reader::Builder;
+    /// let builder = Builder::default().set_page_count(NonZeroUsize::MIN);
+    /// ```
+    pub fn set_page_count(mut self, page_count: NonZeroUsize) -> Self {
+        self.page_count = page_count;
+        self
+    }
+
+    /// Builds a storage reader from a storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bgzf::storage::{reader::Builder, Local};
+    /// let reader = Builder::default().build_from_storage(Local::new(io::empty()));
+    /// ```
+    pub fn build_from_storage<S>(self, storage: S) -> Reader<S>
+    where
+        S: Storage,
+    {
+        Reader {
+            storage,
+            position: 0,
+            len: None,
+            cache: Cache::new(self.page_size.get(), self.page_count.get()),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            page_size: NonZeroUsize::new(DEFAULT_PAGE_SIZE).unwrap(),
+            page_count: NonZeroUsize::new(DEFAULT_PAGE_COUNT).unwrap(),
+        }
+    }
+}
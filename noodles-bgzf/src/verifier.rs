@@ -0,0 +1,403 @@
+//! BGZF verifier.
+
+mod report;
+
+pub use self::report::{Corruption, CorruptionKind, Report};
+
+use std::io::{self, Read, Write};
+
+use bytes::Buf;
+use flate2::Crc;
+
+use crate::{
+    deflate,
+    reader::frame::{is_valid_header, parse_trailer, MIN_FRAME_SIZE},
+    writer::BGZF_EOF,
+    BGZF_HEADER_SIZE, BGZF_MAX_ISIZE,
+};
+
+/// A BGZF verifier.
+///
+/// This walks the blocks of a BGZF stream and checks each block's header, block size (`BSIZE`),
+/// uncompressed data checksum (`CRC32`), and uncompressed data size (`ISIZE`). Verification stops
+/// at the first corrupt block.
+pub struct Verifier<R> {
+    inner: R,
+    buf: Vec<u8>,
+    data: Vec<u8>,
+}
+
+impl<R> Verifier<R>
+where
+    R: Read,
+{
+    /// Creates a BGZF verifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::Verifier;
+    /// let verifier = Verifier::new(&[][..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            data: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::Verifier;
+    /// let verifier = Verifier::new(&[][..]);
+    /// assert!(verifier.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::Verifier;
+    /// let mut verifier = Verifier::new(&[][..]);
+    /// assert!(verifier.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::Verifier;
+    /// let verifier = Verifier::new(&[][..]);
+    /// assert!(verifier.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Verifies the BGZF stream.
+    ///
+    /// Corruption in the stream is recorded in the returned report. An error is only returned
+    /// when the underlying reader fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, verifier::Verifier};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut verifier = Verifier::new(&data[..]);
+    /// let report = verifier.verify()?;
+    ///
+    /// assert!(report.is_ok());
+    /// assert_eq!(report.block_count(), 2);
+    /// assert_eq!(report.uncompressed_size(), 7);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn verify(&mut self) -> io::Result<Report> {
+        self.walk(|_| Ok(()))
+    }
+
+    /// Copies all intact blocks to the given writer.
+    ///
+    /// Blocks are copied verbatim up to the first corrupt block, and an EOF marker is appended
+    /// if the copied blocks do not already end with one. The returned report describes the
+    /// input stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, verifier::Verifier};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let mut data = writer.finish()?;
+    /// data.truncate(data.len() - 8);
+    ///
+    /// let mut recovered = Vec::new();
+    /// let report = Verifier::new(&data[..]).recover(&mut recovered)?;
+    /// assert!(report.corruption().is_some());
+    ///
+    /// let report = Verifier::new(&recovered[..]).verify()?;
+    /// assert!(report.is_ok());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn recover<W>(&mut self, mut writer: W) -> io::Result<Report>
+    where
+        W: Write,
+    {
+        let report = self.walk(|frame| writer.write_all(frame))?;
+
+        if !report.has_eof_marker() {
+            writer.write_all(BGZF_EOF)?;
+        }
+
+        Ok(report)
+    }
+
+    fn walk<F>(&mut self, mut f: F) -> io::Result<Report>
+    where
+        F: FnMut(&[u8]) -> io::Result<()>,
+    {
+        let mut report = Report::default();
+        let mut position = 0;
+
+        loop {
+            match self.read_block()? {
+                None => break,
+                Some(Ok(uncompressed_len)) => {
+                    f(&self.buf)?;
+
+                    report.block_count += 1;
+                    report.uncompressed_size += uncompressed_len as u64;
+                    report.has_eof_marker = self.buf == BGZF_EOF;
+
+                    position += self.buf.len() as u64;
+                }
+                Some(Err(kind)) => {
+                    report.has_eof_marker = false;
+                    report.corruption = Some(Corruption::new(position, kind));
+                    break;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn read_block(&mut self) -> io::Result<Option<Result<usize, CorruptionKind>>> {
+        const BSIZE_POSITION: usize = 16;
+
+        self.buf.resize(BGZF_HEADER_SIZE, 0);
+
+        match read_exact_or_eof(&mut self.inner, &mut self.buf)? {
+            0 => return Ok(None),
+            n if n < BGZF_HEADER_SIZE => return Ok(Some(Err(CorruptionKind::UnexpectedEof))),
+            _ => {}
+        }
+
+        if !is_valid_header(&self.buf[..]) {
+            return Ok(Some(Err(CorruptionKind::InvalidHeader)));
+        }
+
+        let bsize = (&self.buf[BSIZE_POSITION..]).get_u16_le();
+        let block_size = usize::from(bsize) + 1;
+
+        if block_size < MIN_FRAME_SIZE {
+            return Ok(Some(Err(CorruptionKind::InvalidBlockSize)));
+        }
+
+        self.buf.resize(block_size, 0);
+
+        let n = read_exact_or_eof(&mut self.inner, &mut self.buf[BGZF_HEADER_SIZE..])?;
+
+        if n < block_size - BGZF_HEADER_SIZE {
+            return Ok(Some(Err(CorruptionKind::UnexpectedEof)));
+        }
+
+        Ok(Some(check_frame(&self.buf, &mut self.data)))
+    }
+}
+
+fn read_exact_or_eof<R>(reader: &mut R, mut buf: &mut [u8]) -> io::Result<usize>
+where
+    R: Read,
+{
+    let mut len = 0;
+
+    while !buf.is_empty() {
+        match reader.read(buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                buf = &mut buf[n..];
+                len += n;
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(len)
+}
+
+fn check_frame(src: &[u8], data: &mut Vec<u8>) -> Result<usize, CorruptionKind> {
+    use crate::gz;
+
+    let n = src.len() - gz::TRAILER_SIZE;
+    let cdata = &src[BGZF_HEADER_SIZE..n];
+    let trailer = &src[n..];
+
+    let (crc32, r#isize) =
+        parse_trailer(trailer).map_err(|_| CorruptionKind::InvalidUncompressedSize)?;
+
+    if r#isize > BGZF_MAX_ISIZE {
+        return Err(CorruptionKind::InvalidUncompressedSize);
+    }
+
+    data.resize(r#isize, 0);
+    deflate::decode(cdata, data).map_err(|_| CorruptionKind::InvalidData)?;
+
+    let mut crc = Crc::new();
+    crc.update(data);
+
+    if crc.sum() == crc32 {
+        Ok(r#isize)
+    } else {
+        Err(CorruptionKind::ChecksumMismatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_data() -> io::Result<(Vec<u8>, u64)> {
+        use crate::Writer;
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let second_block_position = writer.get_ref().len() as u64;
+        writer.write_all(b"bgzf")?;
+
+        writer.finish().map(|data| (data, second_block_position))
+    }
+
+    #[test]
+    fn test_verify() -> io::Result<()> {
+        let (data, _) = build_data()?;
+        let report = Verifier::new(&data[..]).verify()?;
+
+        assert!(report.is_ok());
+        assert_eq!(report.block_count(), 3);
+        assert_eq!(report.uncompressed_size(), 11);
+        assert!(report.has_eof_marker());
+        assert!(report.corruption().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_missing_eof_marker() -> io::Result<()> {
+        let (mut data, _) = build_data()?;
+        data.truncate(data.len() - BGZF_EOF.len());
+
+        let report = Verifier::new(&data[..]).verify()?;
+
+        assert!(!report.is_ok());
+        assert_eq!(report.block_count(), 2);
+        assert!(!report.has_eof_marker());
+        assert!(report.corruption().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_corrupt_blocks() -> io::Result<()> {
+        fn t(data: &[u8], expected: Corruption) -> io::Result<()> {
+            let report = Verifier::new(data).verify()?;
+            assert_eq!(report.block_count(), 1);
+            assert!(!report.has_eof_marker());
+            assert_eq!(report.corruption(), Some(expected));
+            Ok(())
+        }
+
+        let (src, position) = build_data()?;
+        let i = usize::try_from(position).unwrap();
+
+        let mut data = src.clone();
+        data[i] = 0x00;
+        t(
+            &data,
+            Corruption::new(position, CorruptionKind::InvalidHeader),
+        )?;
+
+        let mut data = src.clone();
+        data[i + 16] = 0x00;
+        data[i + 17] = 0x00;
+        t(
+            &data,
+            Corruption::new(position, CorruptionKind::InvalidBlockSize),
+        )?;
+
+        let mut data = src.clone();
+        data.truncate(i + 8);
+        t(
+            &data,
+            Corruption::new(position, CorruptionKind::UnexpectedEof),
+        )?;
+
+        let mut data = src.clone();
+        data.truncate(i + BGZF_HEADER_SIZE + 2);
+        t(
+            &data,
+            Corruption::new(position, CorruptionKind::UnexpectedEof),
+        )?;
+
+        let end = src.len() - BGZF_EOF.len();
+
+        let mut data = src.clone();
+        // CRC32
+        data[end - 8] ^= 0xff;
+        t(
+            &data,
+            Corruption::new(position, CorruptionKind::ChecksumMismatch),
+        )?;
+
+        let mut data = src.clone();
+        // ISIZE
+        data[end - 2] = 0xff;
+        t(
+            &data,
+            Corruption::new(position, CorruptionKind::InvalidUncompressedSize),
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recover() -> io::Result<()> {
+        use crate::Reader;
+
+        let (mut data, position) = build_data()?;
+        data.truncate(usize::try_from(position).unwrap() + 8);
+
+        let mut recovered = Vec::new();
+        let report = Verifier::new(&data[..]).recover(&mut recovered)?;
+        assert_eq!(
+            report.corruption(),
+            Some(Corruption::new(position, CorruptionKind::UnexpectedEof))
+        );
+
+        let report = Verifier::new(&recovered[..]).verify()?;
+        assert!(report.is_ok());
+        assert_eq!(report.block_count(), 2);
+
+        let mut buf = Vec::new();
+        Reader::new(&recovered[..]).read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodles");
+
+        let (data, _) = build_data()?;
+        let mut recovered = Vec::new();
+        Verifier::new(&data[..]).recover(&mut recovered)?;
+        assert_eq!(recovered, data);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt;
+
+/// A BGZF verification report.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    pub(super) block_count: u64,
+    pub(super) uncompressed_size: u64,
+    pub(super) has_eof_marker: bool,
+    pub(super) corruption: Option<Corruption>,
+}
+
+impl Report {
+    /// Returns the number of intact blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::Report;
+    /// let report = Report::default();
+    /// assert_eq!(report.block_count(), 0);
+    /// ```
+    pub fn block_count(&self) -> u64 {
+        self.block_count
+    }
+
+    /// Returns the total uncompressed size of the intact blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::Report;
+    /// let report = Report::default();
+    /// assert_eq!(report.uncompressed_size(), 0);
+    /// ```
+    pub fn uncompressed_size(&self) -> u64 {
+        self.uncompressed_size
+    }
+
+    /// Returns whether the stream ends with an EOF marker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::Report;
+    /// let report = Report::default();
+    /// assert!(!report.has_eof_marker());
+    /// ```
+    pub fn has_eof_marker(&self) -> bool {
+        self.has_eof_marker
+    }
+
+    /// Returns the first corrupt block, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::Report;
+    /// let report = Report::default();
+    /// assert!(report.corruption().is_none());
+    /// ```
+    pub fn corruption(&self) -> Option<Corruption> {
+        self.corruption
+    }
+
+    /// Returns whether all blocks are intact and the stream ends with an EOF marker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::Report;
+    /// let report = Report::default();
+    /// assert!(!report.is_ok());
+    /// ```
+    pub fn is_ok(&self) -> bool {
+        self.corruption.is_none() && self.has_eof_marker
+    }
+}
+
+/// A corrupt block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Corruption {
+    position: u64,
+    kind: CorruptionKind,
+}
+
+impl Corruption {
+    /// Creates a corrupt block description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::{Corruption, CorruptionKind};
+    /// let corruption = Corruption::new(0, CorruptionKind::InvalidHeader);
+    /// ```
+    pub fn new(position: u64, kind: CorruptionKind) -> Self {
+        Self { position, kind }
+    }
+
+    /// Returns the compressed position of the start of the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::{Corruption, CorruptionKind};
+    /// let corruption = Corruption::new(8, CorruptionKind::InvalidHeader);
+    /// assert_eq!(corruption.position(), 8);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the kind of corruption.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::verifier::{Corruption, CorruptionKind};
+    /// let corruption = Corruption::new(8, CorruptionKind::InvalidHeader);
+    /// assert_eq!(corruption.kind(), CorruptionKind::InvalidHeader);
+    /// ```
+    pub fn kind(&self) -> CorruptionKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Corruption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.position)
+    }
+}
+
+/// A kind of block corruption.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CorruptionKind {
+    /// The block header is invalid.
+    InvalidHeader,
+    /// The block size (`BSIZE`) is invalid.
+    InvalidBlockSize,
+    /// The stream ends before the end of the block.
+    UnexpectedEof,
+    /// The compressed data is invalid.
+    InvalidData,
+    /// The uncompressed data checksum (`CRC32`) does not match.
+    ChecksumMismatch,
+    /// The uncompressed data size (`ISIZE`) is invalid.
+    InvalidUncompressedSize,
+}
+
+impl fmt::Display for CorruptionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "invalid block header"),
+            Self::InvalidBlockSize => write!(f, "invalid block size"),
+            Self::UnexpectedEof => write!(f, "unexpected EOF"),
+            Self::InvalidData => write!(f, "invalid compressed data"),
+            Self::ChecksumMismatch => write!(f, "block data checksum mismatch"),
+            Self::InvalidUncompressedSize => write!(f, "invalid uncompressed size"),
+        }
+    }
+}
@@ -9,7 +9,7 @@ pub use self::{builder::Builder, compression_level::CompressionLevel};
 use std::io::{self, Write};
 
 pub(crate) use self::frame::write_frame;
-use super::{gz, VirtualPosition, BGZF_HEADER_SIZE, BGZF_MAX_ISIZE};
+use super::{gz, gzi, VirtualPosition, BGZF_HEADER_SIZE, BGZF_MAX_ISIZE};
 
 // The max DEFLATE overhead for 65536 bytes of data at compression level 0.
 //
@@ -72,6 +72,7 @@ where
     staging_buf: Vec<u8>,
     compression_buf: Vec<u8>,
     compression_level: CompressionLevelImpl,
+    gzi_index: Option<gzi::Index>,
 }
 
 impl<W> Writer<W>
@@ -153,6 +154,32 @@ where
         VirtualPosition::try_from((self.position, uncompressed_position)).unwrap()
     }
 
+    /// Returns the gzip index (GZI) built while writing.
+    ///
+    /// This is only available if enabled using [`Builder::set_build_gzi_index`]. It has an entry
+    /// for the start of each block and is complete after the writer is finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::writer::Builder::default()
+    ///     .set_build_gzi_index(true)
+    ///     .build_with_writer(Vec::new());
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// writer.try_finish()?;
+    ///
+    /// let eof_position = (writer.get_ref().len() - 28) as u64;
+    /// assert_eq!(writer.gzi_index(), Some(&vec![(0, 0), (eof_position, 7)]));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn gzi_index(&self) -> Option<&gzi::Index> {
+        self.gzi_index.as_ref()
+    }
+
     fn flush_block(&mut self) -> io::Result<()> {
         use crate::deflate;
 
@@ -165,6 +192,10 @@ where
 
         self.position += block_size as u64;
 
+        if let Some(index) = self.gzi_index.as_mut() {
+            add_gzi_index_entry(index, self.position, uncompressed_len);
+        }
+
         self.staging_buf.clear();
 
         Ok(())
@@ -263,6 +294,18 @@ where
     }
 }
 
+pub(crate) fn add_gzi_index_entry(
+    index: &mut gzi::Index,
+    compressed_position: u64,
+    uncompressed_len: usize,
+) {
+    let (_, uncompressed_position) = index.last().copied().unwrap_or_default();
+    index.push((
+        compressed_position,
+        uncompressed_position + uncompressed_len as u64,
+    ));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,6 +333,42 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_gzi_index() -> io::Result<()> {
+        use std::io::{Cursor, Read};
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.try_finish()?;
+        assert!(writer.gzi_index().is_none());
+
+        let mut writer = Builder::default()
+            .set_build_gzi_index(true)
+            .build_with_writer(Vec::new());
+
+        let data = vec![b'n'; MAX_BUF_SIZE + 8];
+        writer.write_all(&data)?;
+        writer.flush()?;
+        let position = writer.position();
+        writer.try_finish()?;
+
+        let index = writer.gzi_index().cloned().expect("missing GZI index");
+        assert_eq!(index.len(), 3);
+        assert_eq!(index[1].1, MAX_BUF_SIZE as u64);
+        assert_eq!(index[2], (position, data.len() as u64));
+
+        let mut reader = crate::Reader::new(Cursor::new(writer.get_ref()));
+
+        for &(compressed_position, uncompressed_position) in &index {
+            reader.seek(VirtualPosition::from(compressed_position << 16))?;
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+            assert_eq!(buf.len() as u64, data.len() as u64 - uncompressed_position);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_finish() -> io::Result<()> {
         let mut writer = Writer::new(Vec::new());
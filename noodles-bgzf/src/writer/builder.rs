@@ -6,6 +6,7 @@ use super::{CompressionLevel, Writer, MAX_BUF_SIZE};
 #[derive(Debug, Default)]
 pub struct Builder {
     compression_level: CompressionLevel,
+    build_gzi_index: bool,
 }
 
 impl Builder {
@@ -26,6 +27,22 @@ impl Builder {
         self
     }
 
+    /// Sets whether to build a gzip index (GZI) while writing.
+    ///
+    /// When enabled, the compressed and uncompressed offsets of each written block are recorded.
+    /// The index is available using [`Writer::gzi_index`]. By default, this is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::writer::Builder::default().set_build_gzi_index(true);
+    /// ```
+    pub fn set_build_gzi_index(mut self, build_gzi_index: bool) -> Self {
+        self.build_gzi_index = build_gzi_index;
+        self
+    }
+
     /// Builds a BGZF writer from a writer.
     ///
     /// # Examples
@@ -45,6 +62,7 @@ impl Builder {
             staging_buf: Vec::with_capacity(MAX_BUF_SIZE),
             compression_buf: Vec::new(),
             compression_level: self.compression_level.into(),
+            gzi_index: self.build_gzi_index.then(|| vec![(0, 0)]),
         }
     }
 }
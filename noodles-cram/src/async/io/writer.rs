@@ -178,12 +178,10 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn write_record(
-        &mut self,
-        header: &sam::Header,
-        mut record: Record,
-    ) -> io::Result<()> {
-        use crate::data_container::builder::AddRecordError;
+    pub async fn write_record(&mut self, header: &sam::Header, record: Record) -> io::Result<()> {
+        use crate::{data_container::builder::AddRecordError, io::writer::apply_lossy_transform};
+
+        let mut record = apply_lossy_transform(&self.options.lossy_transform, record);
 
         loop {
             match self.data_container_builder.add_record(record) {
@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use noodles_fasta as fasta;
+use noodles_sam::alignment::lossy;
 use tokio::{
     fs::File,
     io::{self, AsyncWrite},
@@ -55,6 +56,17 @@ impl Builder {
         self
     }
 
+    /// Sets the lossy transform applied to records before they are written.
+    ///
+    /// If the transform discards read names, this is equivalent to setting
+    /// [`Self::preserve_read_names`] to `false`.
+    ///
+    /// By default, no lossy transform is applied.
+    pub fn set_lossy_transform(mut self, transform: lossy::Transform) -> Self {
+        self.options.lossy_transform = transform;
+        self
+    }
+
     /// Builds an async CRAM writer from a path.
     ///
     /// # Examples
@@ -95,6 +107,10 @@ impl Builder {
             self.options.version = Version::new(3, 1);
         }
 
+        if self.options.lossy_transform.discards_read_names() {
+            self.options.preserve_read_names = false;
+        }
+
         Writer {
             inner: writer,
             reference_sequence_repository: self.reference_sequence_repository,
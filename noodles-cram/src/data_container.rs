@@ -1,6 +1,7 @@
 //! CRAM data container and fields.
 
 pub mod block_content_encoder_map;
+pub mod block_sizes;
 pub(crate) mod builder;
 pub mod compression_header;
 mod header;
@@ -8,8 +9,8 @@ mod reference_sequence_context;
 pub(crate) mod slice;
 
 pub use self::{
-    block_content_encoder_map::BlockContentEncoderMap, compression_header::CompressionHeader,
-    slice::Slice,
+    block_content_encoder_map::BlockContentEncoderMap, block_sizes::BlockSizes,
+    compression_header::CompressionHeader, slice::Slice,
 };
 pub(crate) use self::{
     builder::Builder, header::Header, reference_sequence_context::ReferenceSequenceContext,
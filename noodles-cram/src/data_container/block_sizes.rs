@@ -0,0 +1,544 @@
+//! CRAM data container block sizes.
+
+mod block_size;
+
+pub use self::block_size::BlockSize;
+pub use crate::{
+    container::block::CompressionMethod,
+    data_container::compression_header::encoding::Kind as EncodingKind,
+};
+
+use std::collections::BTreeMap;
+
+use super::{
+    compression_header::{
+        data_series_encoding_map::{data_series::STANDARD_DATA_SERIES, DataSeries},
+        encoding::codec::{Byte, ByteArray, Integer},
+        DataSeriesEncodingMap, Encoding,
+    },
+    CompressionHeader, DataContainer,
+};
+use crate::container::block::ContentId;
+
+/// A data container block ID.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum BlockId {
+    /// The core data block.
+    Core,
+    /// An external data block with the given content ID.
+    External(i32),
+}
+
+/// Block sizes summarized over one or more data containers.
+///
+/// This only reads block headers and compression headers. Records are not decoded, and blocks are
+/// not decompressed.
+///
+/// Each block is attributed to the data series and tags whose encodings read from it, along with
+/// the kind of encoding that reads from the block. Data series using non-external encodings
+/// (e.g., Huffman or beta) are attributed to the core data block.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_cram::{self as cram, data_container::BlockSizes};
+///
+/// let mut reader = File::open("sample.cram").map(cram::io::Reader::new)?;
+/// reader.read_header()?;
+///
+/// let mut block_sizes = BlockSizes::default();
+///
+/// while let Some(container) = reader.read_data_container()? {
+///     block_sizes.add_data_container(&container);
+/// }
+///
+/// for (id, size) in block_sizes.iter() {
+///     println!(
+///         "{id:?}\t{}\t{}\t{:?}\t{:?}",
+///         size.compressed_len(),
+///         size.uncompressed_len(),
+///         size.data_series(),
+///         size.tags()
+///     );
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BlockSizes(BTreeMap<BlockId, BlockSize>);
+
+impl BlockSizes {
+    /// Returns the number of distinct blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::data_container::BlockSizes;
+    /// let block_sizes = BlockSizes::default();
+    /// assert_eq!(block_sizes.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are any blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::data_container::BlockSizes;
+    /// let block_sizes = BlockSizes::default();
+    /// assert!(block_sizes.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the size summary of the block with the given ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::data_container::{block_sizes::BlockId, BlockSizes};
+    /// let block_sizes = BlockSizes::default();
+    /// assert!(block_sizes.get(BlockId::Core).is_none());
+    /// ```
+    pub fn get(&self, id: BlockId) -> Option<&BlockSize> {
+        self.0.get(&id)
+    }
+
+    /// Returns an iterator over block IDs and their size summaries, ordered by block ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::data_container::BlockSizes;
+    /// let block_sizes = BlockSizes::default();
+    /// assert!(block_sizes.iter().next().is_none());
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (BlockId, &BlockSize)> {
+        self.0.iter().map(|(id, size)| (*id, size))
+    }
+
+    /// Returns the combined size summary of the blocks that store the given data series.
+    ///
+    /// If the data series is spread across multiple blocks (e.g., a byte array with separate
+    /// length and value encodings), the sizes of all its blocks are summed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::data_container::{
+    ///     compression_header::data_series_encoding_map::DataSeries, BlockSizes,
+    /// };
+    ///
+    /// let block_sizes = BlockSizes::default();
+    /// assert!(block_sizes.get_by_data_series(DataSeries::QualityScores).is_none());
+    /// ```
+    pub fn get_by_data_series(&self, data_series: DataSeries) -> Option<BlockSize> {
+        self.0
+            .values()
+            .filter(|size| size.data_series().iter().any(|(ds, _)| *ds == data_series))
+            .fold(None, |acc: Option<BlockSize>, size| {
+                let mut acc = acc.unwrap_or_default();
+                acc.add_block_size(size);
+                Some(acc)
+            })
+    }
+
+    /// Returns the total compressed size of all blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::data_container::BlockSizes;
+    /// let block_sizes = BlockSizes::default();
+    /// assert_eq!(block_sizes.compressed_len(), 0);
+    /// ```
+    pub fn compressed_len(&self) -> u64 {
+        self.0.values().map(|size| size.compressed_len()).sum()
+    }
+
+    /// Returns the total uncompressed size of all blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::data_container::BlockSizes;
+    /// let block_sizes = BlockSizes::default();
+    /// assert_eq!(block_sizes.uncompressed_len(), 0);
+    /// ```
+    pub fn uncompressed_len(&self) -> u64 {
+        self.0.values().map(|size| size.uncompressed_len()).sum()
+    }
+
+    /// Adds the blocks of the given data container to the summary.
+    pub fn add_data_container(&mut self, data_container: &DataContainer) {
+        let compression_header = data_container.compression_header();
+
+        for slice in data_container.slices() {
+            let block = slice.core_data_block();
+            self.0.entry(BlockId::Core).or_default().add_block(
+                block.compression_method(),
+                block.data().len(),
+                block.uncompressed_len(),
+            );
+
+            for block in slice.external_blocks() {
+                let id = BlockId::External(i32::from(block.content_id()));
+
+                self.0.entry(id).or_default().add_block(
+                    block.compression_method(),
+                    block.data().len(),
+                    block.uncompressed_len(),
+                );
+            }
+        }
+
+        self.add_attributions(compression_header);
+    }
+
+    fn add_attributions(&mut self, compression_header: &CompressionHeader) {
+        let data_series_encoding_map = compression_header.data_series_encoding_map();
+
+        for &data_series in STANDARD_DATA_SERIES {
+            let mut ids = Vec::new();
+            data_series_block_ids(data_series_encoding_map, data_series, &mut ids);
+
+            for (id, kind) in ids {
+                self.0
+                    .entry(id)
+                    .or_default()
+                    .add_data_series(data_series, kind);
+            }
+        }
+
+        let tag_encoding_map = compression_header.tag_encoding_map();
+        let tag_ids_dictionary = compression_header.preservation_map().tag_ids_dictionary();
+
+        for &key in tag_ids_dictionary.iter().flatten() {
+            let content_id = ContentId::from(key);
+
+            if let Some(encoding) = tag_encoding_map.get(&content_id) {
+                let mut ids = Vec::new();
+                byte_array_block_ids(encoding, &mut ids);
+
+                for (id, kind) in ids {
+                    self.0.entry(id).or_default().add_tag(key, kind);
+                }
+            }
+        }
+    }
+}
+
+fn data_series_block_ids(
+    map: &DataSeriesEncodingMap,
+    data_series: DataSeries,
+    ids: &mut Vec<(BlockId, EncodingKind)>,
+) {
+    match data_series {
+        DataSeries::BamBitFlags => integer_block_ids(map.bam_bit_flags_encoding(), ids),
+        DataSeries::CramBitFlags => integer_block_ids(map.cram_bit_flags_encoding(), ids),
+        DataSeries::ReferenceId => {
+            if let Some(encoding) = map.reference_id_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::ReadLengths => integer_block_ids(map.read_lengths_encoding(), ids),
+        DataSeries::InSeqPositions => integer_block_ids(map.in_seq_positions_encoding(), ids),
+        DataSeries::ReadGroups => integer_block_ids(map.read_groups_encoding(), ids),
+        DataSeries::ReadNames => {
+            if let Some(encoding) = map.read_names_encoding() {
+                byte_array_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::NextMateBitFlags => {
+            if let Some(encoding) = map.next_mate_bit_flags_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::NextFragmentReferenceSequenceId => {
+            if let Some(encoding) = map.next_fragment_reference_sequence_id_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::NextMateAlignmentStart => {
+            if let Some(encoding) = map.next_mate_alignment_start_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::TemplateSize => {
+            if let Some(encoding) = map.template_size_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::DistanceToNextFragment => {
+            if let Some(encoding) = map.distance_to_next_fragment_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::TagIds => integer_block_ids(map.tag_ids_encoding(), ids),
+        DataSeries::NumberOfReadFeatures => {
+            if let Some(encoding) = map.number_of_read_features_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::ReadFeaturesCodes => {
+            if let Some(encoding) = map.read_features_codes_encoding() {
+                byte_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::InReadPositions => {
+            if let Some(encoding) = map.in_read_positions_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::DeletionLengths => {
+            if let Some(encoding) = map.deletion_lengths_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::StretchesOfBases => {
+            if let Some(encoding) = map.stretches_of_bases_encoding() {
+                byte_array_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::StretchesOfQualityScores => {
+            if let Some(encoding) = map.stretches_of_quality_scores_encoding() {
+                byte_array_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::BaseSubstitutionCodes => {
+            if let Some(encoding) = map.base_substitution_codes_encoding() {
+                byte_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::Insertion => {
+            if let Some(encoding) = map.insertion_encoding() {
+                byte_array_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::ReferenceSkipLength => {
+            if let Some(encoding) = map.reference_skip_length_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::Padding => {
+            if let Some(encoding) = map.padding_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::HardClip => {
+            if let Some(encoding) = map.hard_clip_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::SoftClip => {
+            if let Some(encoding) = map.soft_clip_encoding() {
+                byte_array_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::MappingQualities => {
+            if let Some(encoding) = map.mapping_qualities_encoding() {
+                integer_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::Bases => {
+            if let Some(encoding) = map.bases_encoding() {
+                byte_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::QualityScores => {
+            if let Some(encoding) = map.quality_scores_encoding() {
+                byte_block_ids(encoding, ids);
+            }
+        }
+        DataSeries::ReservedTc | DataSeries::ReservedTn => {}
+    }
+}
+
+fn push_block_id(ids: &mut Vec<(BlockId, EncodingKind)>, id: BlockId, kind: EncodingKind) {
+    if !ids.contains(&(id, kind)) {
+        ids.push((id, kind));
+    }
+}
+
+fn integer_block_ids(encoding: &Encoding<Integer>, ids: &mut Vec<(BlockId, EncodingKind)>) {
+    let kind = match encoding.get() {
+        Integer::External(content_id) => {
+            let id = BlockId::External(i32::from(*content_id));
+            push_block_id(ids, id, EncodingKind::External);
+            return;
+        }
+        Integer::Golomb(..) => EncodingKind::Golomb,
+        Integer::Huffman(..) => EncodingKind::Huffman,
+        Integer::Beta(..) => EncodingKind::Beta,
+        Integer::Subexp(..) => EncodingKind::Subexp,
+        Integer::GolombRice(..) => EncodingKind::GolombRice,
+        Integer::Gamma(..) => EncodingKind::Gamma,
+    };
+
+    push_block_id(ids, BlockId::Core, kind);
+}
+
+fn byte_block_ids(encoding: &Encoding<Byte>, ids: &mut Vec<(BlockId, EncodingKind)>) {
+    match encoding.get() {
+        Byte::External(content_id) => {
+            let id = BlockId::External(i32::from(*content_id));
+            push_block_id(ids, id, EncodingKind::External);
+        }
+        Byte::Huffman(..) => push_block_id(ids, BlockId::Core, EncodingKind::Huffman),
+    }
+}
+
+fn byte_array_block_ids(encoding: &Encoding<ByteArray>, ids: &mut Vec<(BlockId, EncodingKind)>) {
+    match encoding.get() {
+        ByteArray::ByteArrayLen(len_encoding, value_encoding) => {
+            integer_block_ids(len_encoding, ids);
+            byte_block_ids(value_encoding, ids);
+        }
+        ByteArray::ByteArrayStop(_, content_id) => {
+            let id = BlockId::External(i32::from(*content_id));
+            push_block_id(ids, id, EncodingKind::ByteArrayStop);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_array_block_ids() {
+        let mut ids = Vec::new();
+
+        let encoding = Encoding::new(ByteArray::ByteArrayLen(
+            Encoding::new(Integer::Huffman(vec![0], vec![0])),
+            Encoding::new(Byte::External(ContentId::from(5))),
+        ));
+        byte_array_block_ids(&encoding, &mut ids);
+        assert_eq!(
+            ids,
+            [
+                (BlockId::Core, EncodingKind::Huffman),
+                (BlockId::External(5), EncodingKind::External)
+            ]
+        );
+
+        ids.clear();
+
+        let encoding = Encoding::new(ByteArray::ByteArrayStop(0x00, ContentId::from(8)));
+        byte_array_block_ids(&encoding, &mut ids);
+        assert_eq!(ids, [(BlockId::External(8), EncodingKind::ByteArrayStop)]);
+    }
+
+    #[test]
+    fn test_get_by_data_series() {
+        let mut block_sizes = BlockSizes::default();
+
+        let size = block_sizes.0.entry(BlockId::Core).or_default();
+        size.add_block(CompressionMethod::None, 2, 2);
+        size.add_data_series(DataSeries::ReadNames, EncodingKind::Huffman);
+
+        let size = block_sizes.0.entry(BlockId::External(5)).or_default();
+        size.add_block(CompressionMethod::Gzip, 3, 8);
+        size.add_data_series(DataSeries::ReadNames, EncodingKind::External);
+
+        let size = block_sizes.0.entry(BlockId::External(8)).or_default();
+        size.add_block(CompressionMethod::Gzip, 5, 13);
+        size.add_data_series(DataSeries::QualityScores, EncodingKind::External);
+
+        let size = block_sizes
+            .get_by_data_series(DataSeries::ReadNames)
+            .expect("missing read names blocks");
+        assert_eq!(size.block_count(), 2);
+        assert_eq!(size.compressed_len(), 5);
+        assert_eq!(size.uncompressed_len(), 10);
+        assert_eq!(
+            size.compression_methods(),
+            [CompressionMethod::None, CompressionMethod::Gzip]
+        );
+
+        assert!(block_sizes
+            .get_by_data_series(DataSeries::MappingQualities)
+            .is_none());
+    }
+
+    #[test]
+    fn test_add_data_container() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_sam::{
+            self as sam,
+            alignment::{
+                record::{data::field::Tag, Flags},
+                record_buf::{data::field::Value, QualityScores, Sequence},
+                RecordBuf,
+            },
+        };
+
+        use crate::{
+            data_container::compression_header::preservation_map::tag_ids_dictionary::Key,
+            io::{Reader, Writer},
+        };
+
+        let header = sam::Header::default();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&header)?;
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::UNMAPPED)
+            .set_sequence(Sequence::from(b"ACGT"))
+            .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50]))
+            .set_data(
+                [(Tag::ALIGNMENT_HIT_COUNT, Value::UInt8(1))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build();
+
+        let record = crate::Record::try_from_alignment_record(&header, &record)?;
+        writer.write_record(&header, record)?;
+        writer.try_finish(&header)?;
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        reader.read_header()?;
+
+        let mut block_sizes = BlockSizes::default();
+        let mut expected_compressed_len = 0;
+
+        while let Some(container) = reader.read_data_container()? {
+            for slice in container.slices() {
+                expected_compressed_len += slice.core_data_block().data().len() as u64;
+
+                for block in slice.external_blocks() {
+                    expected_compressed_len += block.data().len() as u64;
+                }
+            }
+
+            block_sizes.add_data_container(&container);
+        }
+
+        assert!(block_sizes.get(BlockId::Core).is_some());
+        assert_eq!(block_sizes.compressed_len(), expected_compressed_len);
+
+        let size = block_sizes
+            .get_by_data_series(DataSeries::QualityScores)
+            .expect("missing quality scores block");
+        assert_eq!(size.block_count(), 1);
+        assert_eq!(size.uncompressed_len(), 4);
+
+        let key = Key::new(
+            Tag::ALIGNMENT_HIT_COUNT,
+            sam::alignment::record::data::field::Type::UInt8,
+        );
+        assert!(block_sizes
+            .iter()
+            .any(|(_, size)| size.tags().contains(&(key, EncodingKind::External))));
+
+        assert!(size
+            .data_series()
+            .contains(&(DataSeries::QualityScores, EncodingKind::External)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use super::{CompressionMethod, EncodingKind};
+use crate::data_container::compression_header::{
+    data_series_encoding_map::DataSeries, preservation_map::tag_ids_dictionary::Key,
+};
+
+/// A size summary of blocks with the same ID.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BlockSize {
+    block_count: u64,
+    compressed_len: u64,
+    uncompressed_len: u64,
+    compression_methods: Vec<CompressionMethod>,
+    data_series: Vec<(DataSeries, EncodingKind)>,
+    tags: Vec<(Key, EncodingKind)>,
+}
+
+impl BlockSize {
+    /// Returns the number of blocks.
+    pub fn block_count(&self) -> u64 {
+        self.block_count
+    }
+
+    /// Returns the total compressed size of the blocks.
+    pub fn compressed_len(&self) -> u64 {
+        self.compressed_len
+    }
+
+    /// Returns the total uncompressed size of the blocks.
+    pub fn uncompressed_len(&self) -> u64 {
+        self.uncompressed_len
+    }
+
+    /// Returns the compression ratio.
+    ///
+    /// This is the uncompressed size divided by the compressed size. If the compressed size is 0,
+    /// this returns `None`.
+    pub fn ratio(&self) -> Option<f64> {
+        if self.compressed_len == 0 {
+            None
+        } else {
+            Some(self.uncompressed_len as f64 / self.compressed_len as f64)
+        }
+    }
+
+    /// Returns the compression methods used by the blocks, in the order first seen.
+    pub fn compression_methods(&self) -> &[CompressionMethod] {
+        &self.compression_methods
+    }
+
+    /// Returns the data series stored in the blocks and the kinds of the encodings that read them.
+    ///
+    /// A byte array data series may be listed in multiple blocks, e.g., with a Huffman-encoded
+    /// length in the core data block and external values.
+    pub fn data_series(&self) -> &[(DataSeries, EncodingKind)] {
+        &self.data_series
+    }
+
+    /// Returns the tags stored in the blocks and the kinds of the encodings that read them.
+    pub fn tags(&self) -> &[(Key, EncodingKind)] {
+        &self.tags
+    }
+
+    pub(super) fn add_block(
+        &mut self,
+        compression_method: CompressionMethod,
+        compressed_len: usize,
+        uncompressed_len: usize,
+    ) {
+        self.block_count += 1;
+        self.compressed_len += compressed_len as u64;
+        self.uncompressed_len += uncompressed_len as u64;
+
+        if !self.compression_methods.contains(&compression_method) {
+            self.compression_methods.push(compression_method);
+        }
+    }
+
+    pub(super) fn add_block_size(&mut self, other: &Self) {
+        self.block_count += other.block_count;
+        self.compressed_len += other.compressed_len;
+        self.uncompressed_len += other.uncompressed_len;
+
+        for &compression_method in &other.compression_methods {
+            if !self.compression_methods.contains(&compression_method) {
+                self.compression_methods.push(compression_method);
+            }
+        }
+
+        for &(data_series, kind) in &other.data_series {
+            self.add_data_series(data_series, kind);
+        }
+
+        for &(key, kind) in &other.tags {
+            self.add_tag(key, kind);
+        }
+    }
+
+    pub(super) fn add_data_series(&mut self, data_series: DataSeries, kind: EncodingKind) {
+        if !self.data_series.contains(&(data_series, kind)) {
+            self.data_series.push((data_series, kind));
+        }
+    }
+
+    pub(super) fn add_tag(&mut self, key: Key, kind: EncodingKind) {
+        if !self.tags.contains(&(key, kind)) {
+            self.tags.push((key, kind));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio() {
+        let mut size = BlockSize::default();
+        assert!(size.ratio().is_none());
+
+        size.add_block(CompressionMethod::Gzip, 4, 8);
+        size.add_block(CompressionMethod::Gzip, 6, 12);
+        assert_eq!(size.ratio(), Some(2.0));
+        assert_eq!(size.compression_methods(), [CompressionMethod::Gzip]);
+    }
+}
@@ -1,13 +1,24 @@
+/// An encoding kind.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Kind {
+    /// No encoding (`NULL`).
     Null,
+    /// Values are read from an external block (`EXTERNAL`).
     External,
+    /// Golomb coding (`GOLOMB`).
     Golomb,
+    /// Huffman coding (`HUFFMAN`).
     Huffman,
+    /// A byte array with an encoded length (`BYTE_ARRAY_LEN`).
     ByteArrayLen,
+    /// A byte array terminated by a stop byte (`BYTE_ARRAY_STOP`).
     ByteArrayStop,
+    /// Beta coding (`BETA`).
     Beta,
+    /// Subexponential coding (`SUBEXP`).
     Subexp,
+    /// Golomb-Rice coding (`GOLOMB_RICE`).
     GolombRice,
+    /// Elias gamma coding (`GAMMA`).
     Gamma,
 }
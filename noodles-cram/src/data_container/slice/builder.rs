@@ -283,7 +283,23 @@ fn set_detached(record: &mut Record) {
 }
 
 // _Sequence Alignment/Map Format Specification_ (2021-06-03) § 1.3.2 "Reference MD5 calculation"
-pub(crate) fn calculate_normalized_sequence_digest(sequence: &[u8]) -> [u8; 16] {
+/// Calculates the MD5 digest of a normalized sequence.
+///
+/// Only the characters in the inclusive range `!` to `~` are hashed, and lowercase characters are
+/// converted to uppercase. This is the reference sequence MD5 checksum (`M5`) in SAM headers and
+/// CRAM slice headers.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::calculate_normalized_sequence_digest;
+///
+/// assert_eq!(
+///     calculate_normalized_sequence_digest(b"ACgt\nAC GT"),
+///     calculate_normalized_sequence_digest(b"ACGTACGT"),
+/// );
+/// ```
+pub fn calculate_normalized_sequence_digest(sequence: &[u8]) -> [u8; 16] {
     let mut hasher = Md5::new();
 
     for &b in sequence {
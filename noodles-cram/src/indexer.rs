@@ -4,8 +4,9 @@ use noodles_core::Position;
 
 use super::{
     crai,
-    data_container::{slice, CompressionHeader, Slice},
+    data_container::{self, slice, CompressionHeader, Slice},
     io::Reader,
+    DataContainer,
 };
 
 /// Indexes a CRAM file.
@@ -31,34 +32,50 @@ where
     while let Some((container_header, data_container)) =
         reader.read_data_container_with_container_header()?
     {
-        let container_len = container_header.len();
+        push_container_index_records(
+            &mut index,
+            &container_header,
+            &data_container,
+            container_position,
+        )?;
 
-        let landmarks = container_header.landmarks();
-        let slice_count = landmarks.len();
+        container_position = reader.position()?;
+    }
 
-        for (i, slice) in data_container.slices().iter().enumerate() {
-            let landmark = landmarks[i];
+    Ok(index)
+}
 
-            let slice_length = if i < slice_count - 1 {
-                landmarks[i + 1] - landmark
-            } else {
-                container_len - landmark
-            };
-
-            push_index_records(
-                &mut index,
-                data_container.compression_header(),
-                slice,
-                container_position,
-                landmark as u64,
-                slice_length as u64,
-            )?;
-        }
+pub(crate) fn push_container_index_records(
+    index: &mut crai::Index,
+    container_header: &data_container::Header,
+    data_container: &DataContainer,
+    container_position: u64,
+) -> io::Result<()> {
+    let container_len = container_header.len();
 
-        container_position = reader.position()?;
+    let landmarks = container_header.landmarks();
+    let slice_count = landmarks.len();
+
+    for (i, slice) in data_container.slices().iter().enumerate() {
+        let landmark = landmarks[i];
+
+        let slice_length = if i < slice_count - 1 {
+            landmarks[i + 1] - landmark
+        } else {
+            container_len - landmark
+        };
+
+        push_index_records(
+            index,
+            data_container.compression_header(),
+            slice,
+            container_position,
+            landmark as u64,
+            slice_length as u64,
+        )?;
     }
 
-    Ok(index)
+    Ok(())
 }
 
 fn push_index_records(
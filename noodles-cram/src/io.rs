@@ -3,8 +3,11 @@
 mod bit_reader;
 mod bit_writer;
 pub mod indexed_reader;
+pub mod indexed_writer;
 pub mod reader;
 pub mod writer;
 
 pub(crate) use self::{bit_reader::BitReader, bit_writer::BitWriter};
-pub use self::{indexed_reader::IndexedReader, reader::Reader, writer::Writer};
+pub use self::{
+    indexed_reader::IndexedReader, indexed_writer::IndexedWriter, reader::Reader, writer::Writer,
+};
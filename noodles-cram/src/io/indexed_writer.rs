@@ -0,0 +1,320 @@
+//! Indexed CRAM writer.
+
+use std::io::{self, Write};
+
+use bytes::BytesMut;
+use noodles_sam as sam;
+
+use super::{
+    reader::{data_container::read_data_container_with_container_header, header_container},
+    writer, Writer,
+};
+use crate::{crai, indexer::push_container_index_records, Record};
+
+// § 6 "File definition" (2022-04-12): magic number (4) + format version (2) + file ID (20)
+const FILE_DEFINITION_LEN: usize = 4 + 2 + 20;
+
+/// An indexed CRAM writer.
+///
+/// This builds a CRAM index (CRAI) as data containers are written, avoiding a second pass over
+/// the output. Records must be written in coordinate-sorted order.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_cram as cram;
+/// use noodles_sam as sam;
+///
+/// let mut writer = cram::io::IndexedWriter::new(Vec::new());
+///
+/// let header = sam::Header::default();
+/// writer.write_header(&header)?;
+///
+/// let record = cram::Record::default();
+/// writer.write_record(&header, record)?;
+///
+/// let index = writer.finish(&header)?;
+/// assert_eq!(index.len(), 1);
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<Indexer<W>>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexed CRAM writer with default options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::io::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::from_builder(writer::Builder::default(), inner)
+    }
+
+    /// Creates an indexed CRAM writer using the options of the given writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::io::writer::Builder::default().preserve_read_names(false);
+    /// let writer = cram::io::IndexedWriter::from_builder(builder, Vec::new());
+    /// ```
+    pub fn from_builder(builder: writer::Builder, inner: W) -> Self {
+        Self {
+            inner: builder.build_with_writer(Indexer::new(inner)),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner.get_ref().inner
+    }
+
+    /// Writes a SAM header.
+    pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a CRAM record.
+    pub fn write_record(&mut self, header: &sam::Header, record: Record) -> io::Result<()> {
+        self.inner.write_record(header, record)
+    }
+
+    /// Finishes the output stream and returns the built index.
+    ///
+    /// This writes any pending records and the final EOF container.
+    pub fn finish(&mut self, header: &sam::Header) -> io::Result<crai::Index> {
+        self.inner.try_finish(header)?;
+        Ok(self.inner.get_ref().index.clone())
+    }
+}
+
+impl<W> sam::alignment::io::Write for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn sam::alignment::Record,
+    ) -> io::Result<()> {
+        self.inner.write_alignment_record(header, record)
+    }
+
+    fn finish(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.inner.try_finish(header)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum State {
+    FileDefinition,
+    FileHeader,
+    DataContainers,
+}
+
+// Passes written bytes through to the inner writer while reading back each complete container
+// to add its slices to the index.
+#[derive(Debug)]
+struct Indexer<W> {
+    inner: W,
+    buf: Vec<u8>,
+    state: State,
+    position: u64,
+    index: crai::Index,
+}
+
+impl<W> Indexer<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            state: State::FileDefinition,
+            position: 0,
+            index: crai::Index::new(),
+        }
+    }
+
+    fn index_complete_containers(&mut self) -> io::Result<()> {
+        loop {
+            let len = match self.state {
+                State::FileDefinition => {
+                    if self.buf.len() < FILE_DEFINITION_LEN {
+                        return Ok(());
+                    }
+
+                    FILE_DEFINITION_LEN
+                }
+                State::FileHeader | State::DataContainers => {
+                    let Some(len) = container_len(&self.buf)? else {
+                        return Ok(());
+                    };
+
+                    len
+                }
+            };
+
+            if self.state == State::DataContainers {
+                let mut src = &self.buf[..len];
+                let mut buf = BytesMut::new();
+
+                if let Some((header, data_container)) =
+                    read_data_container_with_container_header(&mut src, &mut buf)?
+                {
+                    push_container_index_records(
+                        &mut self.index,
+                        &header,
+                        &data_container,
+                        self.position,
+                    )?;
+                }
+            }
+
+            self.buf.drain(..len);
+            self.position += len as u64;
+
+            self.state = match self.state {
+                State::FileDefinition => State::FileHeader,
+                State::FileHeader | State::DataContainers => State::DataContainers,
+            };
+        }
+    }
+}
+
+impl<W> Write for Indexer<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.buf.extend_from_slice(&buf[..n]);
+        self.index_complete_containers()?;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// Returns the length of the container at the start of `src`, including its header, or `None` if
+// the container is incomplete.
+fn container_len(src: &[u8]) -> io::Result<Option<usize>> {
+    let mut reader = src;
+
+    let body_len = match header_container::header::read_header(&mut reader) {
+        Ok(len) => len,
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let header_len = src.len() - reader.len();
+    let len = header_len + body_len;
+
+    if src.len() < len {
+        Ok(None)
+    } else {
+        Ok(Some(len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, num::NonZeroUsize};
+
+    use noodles_core::Position;
+    use noodles_fasta::{
+        self as fasta,
+        record::{Definition, Sequence},
+    };
+    use sam::{
+        alignment::{
+            io::Write as _,
+            record::{cigar::op::Kind, cigar::Op, Flags},
+            record_buf::{QualityScores, Sequence as RecordBufSequence},
+            RecordBuf,
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        const SQ0_LN: NonZeroUsize = match NonZeroUsize::new(8) {
+            Some(length) => length,
+            None => unreachable!(),
+        };
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(SQ0_LN))
+            .build();
+
+        let builder =
+            writer::Builder::default().set_reference_sequence_repository(repository.clone());
+        let mut writer = IndexedWriter::from_builder(builder, Vec::new());
+        writer.write_header(&header)?;
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(2)?)
+            .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+            .set_sequence(RecordBufSequence::from(b"TCAC".to_vec()))
+            .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50]))
+            .build();
+
+        writer.write_alignment_record(&header, &record)?;
+
+        let index = writer.finish(&header)?;
+
+        assert_eq!(index.len(), 1);
+
+        let index_record = &index[0];
+        assert_eq!(index_record.reference_sequence_id(), Some(0));
+        assert_eq!(index_record.alignment_start(), Position::new(2));
+        assert_eq!(index_record.alignment_span(), 4);
+
+        let src = writer.get_ref();
+        let mut reader = crate::io::indexed_reader::Builder::default()
+            .set_reference_sequence_repository(repository)
+            .set_index(index)
+            .build_from_reader(Cursor::new(src))?;
+        reader.read_header()?;
+
+        let region = "sq0:3-4".parse()?;
+        let records: Vec<_> = reader.query(&header, &region)?.collect::<io::Result<_>>()?;
+        assert_eq!(records.len(), 1);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-mod header;
+pub(crate) mod header;
 
 use std::io::{self, BufRead, BufReader, Read};
 
@@ -17,7 +17,7 @@ use std::{
 };
 
 use noodles_fasta as fasta;
-use noodles_sam::{self as sam, header::ReferenceSequences};
+use noodles_sam::{self as sam, alignment::lossy, header::ReferenceSequences};
 
 use crate::{file_definition::Version, DataContainer, FileDefinition, Record, MAGIC_NUMBER};
 
@@ -214,9 +214,11 @@ where
     /// writer.try_finish(&header)?;
     /// # Ok::<(), io::Error>(())
     /// ```
-    pub fn write_record(&mut self, header: &sam::Header, mut record: Record) -> io::Result<()> {
+    pub fn write_record(&mut self, header: &sam::Header, record: Record) -> io::Result<()> {
         use crate::data_container::builder::AddRecordError;
 
+        let mut record = apply_lossy_transform(&self.options.lossy_transform, record);
+
         loop {
             match self.data_container_builder.add_record(record) {
                 Ok(_) => {
@@ -329,6 +331,63 @@ pub(crate) fn add_missing_reference_sequence_checksums(
     Ok(())
 }
 
+pub(crate) fn apply_lossy_transform(transform: &lossy::Transform, mut record: Record) -> Record {
+    use sam::alignment::record_buf::QualityScores;
+
+    use crate::record::{Feature, Flags};
+
+    // Read names are discarded by the preservation map rather than here so that detached mates
+    // keep their names.
+    if transform.quality_score_bins().is_none() && !transform.discards_secondary_quality_scores() {
+        return record;
+    }
+
+    let flags = record.bam_flags();
+
+    transform.apply_to_quality_scores(flags, &mut record.quality_scores);
+
+    if record.quality_scores.is_empty() {
+        record
+            .cram_bit_flags
+            .remove(Flags::QUALITY_SCORES_STORED_AS_ARRAY);
+    }
+
+    let transform_score = |score: u8| -> Option<u8> {
+        let mut quality_scores = QualityScores::from(vec![score]);
+        transform.apply_to_quality_scores(flags, &mut quality_scores);
+        quality_scores.as_ref().first().copied()
+    };
+
+    // Mapped records that do not store quality scores as an array keep them in features.
+    for feature in record.features.iter_mut() {
+        if let Feature::ReadBase(position, base, score) = *feature {
+            *feature = match transform_score(score) {
+                Some(score) => Feature::ReadBase(position, base, score),
+                None => Feature::Bases(position, vec![base]),
+            };
+        }
+    }
+
+    record.features.retain_mut(|feature| match feature {
+        Feature::Scores(_, scores) => {
+            let mut quality_scores = QualityScores::from(std::mem::take(scores));
+            transform.apply_to_quality_scores(flags, &mut quality_scores);
+            *scores = quality_scores.into();
+            !scores.is_empty()
+        }
+        Feature::QualityScore(_, score) => match transform_score(*score) {
+            Some(s) => {
+                *score = s;
+                true
+            }
+            None => false,
+        },
+        _ => true,
+    });
+
+    record
+}
+
 #[cfg(test)]
 mod tests {
     use bstr::BString;
@@ -398,4 +457,93 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_apply_lossy_transform() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_core::Position;
+        use sam::alignment::{
+            lossy::QualityScoreBins,
+            record::{
+                cigar::{op::Kind, Op},
+                Flags,
+            },
+            record_buf::{Cigar, QualityScores, Sequence},
+            RecordBuf,
+        };
+
+        use crate::record::Feature;
+
+        let header = sam::Header::default();
+
+        let record = Record::try_from_alignment_record(
+            &header,
+            &RecordBuf::builder()
+                .set_flags(Flags::UNMAPPED)
+                .set_sequence(Sequence::from(b"ACGT"))
+                .set_quality_scores(QualityScores::from(vec![8, 21, 38, 45]))
+                .build(),
+        )?;
+
+        let transform = lossy::Transform::default();
+        let actual = apply_lossy_transform(&transform, record.clone());
+        assert_eq!(actual, record);
+
+        let transform = lossy::Transform::builder()
+            .set_quality_score_bins(QualityScoreBins::illumina_8_level())
+            .discard_secondary_quality_scores(true)
+            .build();
+
+        let actual = apply_lossy_transform(&transform, record);
+        assert_eq!(
+            actual.quality_scores(),
+            &QualityScores::from(vec![6, 22, 37, 40])
+        );
+
+        let record = Record::try_from_alignment_record(
+            &header,
+            &RecordBuf::builder()
+                .set_flags(Flags::empty())
+                .set_cigar(Cigar::from(vec![
+                    Op::new(Kind::SoftClip, 1),
+                    Op::new(Kind::Match, 1),
+                    Op::new(Kind::Insertion, 2),
+                ]))
+                .set_sequence(Sequence::from(b"ACGT"))
+                .set_quality_scores(QualityScores::from(vec![8, 21, 38, 45]))
+                .build(),
+        )?;
+
+        let actual = apply_lossy_transform(&transform, record.clone());
+        assert_eq!(
+            actual.quality_scores(),
+            &QualityScores::from(vec![6, 22, 37, 40])
+        );
+
+        assert_eq!(
+            &actual.features()[..],
+            [
+                Feature::SoftClip(Position::try_from(1)?, vec![b'A']),
+                Feature::QualityScore(Position::try_from(1)?, 6),
+                Feature::ReadBase(Position::try_from(2)?, b'C', 22),
+                Feature::Insertion(Position::try_from(3)?, vec![b'G', b'T']),
+                Feature::Scores(Position::try_from(3)?, vec![37, 40]),
+            ]
+        );
+
+        let mut record = record;
+        record.bam_bit_flags = Flags::SECONDARY;
+
+        let actual = apply_lossy_transform(&transform, record);
+        assert!(actual.quality_scores().is_empty());
+        assert_eq!(
+            &actual.features()[..],
+            [
+                Feature::SoftClip(Position::try_from(1)?, vec![b'A']),
+                Feature::Bases(Position::try_from(2)?, vec![b'C']),
+                Feature::Insertion(Position::try_from(3)?, vec![b'G', b'T']),
+            ]
+        );
+
+        Ok(())
+    }
 }
@@ -5,6 +5,7 @@ use std::{
 };
 
 use noodles_fasta as fasta;
+use noodles_sam::alignment::lossy;
 
 use super::{Options, Writer};
 use crate::{
@@ -91,6 +92,32 @@ impl Builder {
         self
     }
 
+    /// Sets the lossy transform applied to records before they are written.
+    ///
+    /// Quality scores are binned or discarded as configured by the transform. If the transform
+    /// discards read names, this is equivalent to setting [`Self::preserve_read_names`] to
+    /// `false`, and read names are regenerated from record IDs on read.
+    ///
+    /// By default, no lossy transform is applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::Builder;
+    /// use noodles_sam::alignment::lossy::{self, QualityScoreBins};
+    ///
+    /// let transform = lossy::Transform::builder()
+    ///     .set_quality_score_bins(QualityScoreBins::illumina_8_level())
+    ///     .discard_secondary_quality_scores(true)
+    ///     .build();
+    ///
+    /// let builder = Builder::default().set_lossy_transform(transform);
+    /// ```
+    pub fn set_lossy_transform(mut self, transform: lossy::Transform) -> Self {
+        self.options.lossy_transform = transform;
+        self
+    }
+
     /// Builds a CRAM writer from a path.
     ///
     /// # Examples
@@ -123,6 +150,10 @@ impl Builder {
             self.options.version = Version::new(3, 1);
         }
 
+        if self.options.lossy_transform.discards_read_names() {
+            self.options.preserve_read_names = false;
+        }
+
         Writer {
             inner: writer,
             reference_sequence_repository: self.reference_sequence_repository,
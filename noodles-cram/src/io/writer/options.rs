@@ -1,3 +1,5 @@
+use noodles_sam::alignment::lossy;
+
 use crate::{data_container::BlockContentEncoderMap, file_definition::Version};
 
 #[derive(Clone, Debug)]
@@ -6,6 +8,7 @@ pub struct Options {
     pub encode_alignment_start_positions_as_deltas: bool,
     pub version: Version,
     pub block_content_encoder_map: BlockContentEncoderMap,
+    pub lossy_transform: lossy::Transform,
 }
 
 impl Default for Options {
@@ -15,6 +18,7 @@ impl Default for Options {
             encode_alignment_start_positions_as_deltas: true,
             version: Version::default(),
             block_content_encoder_map: BlockContentEncoderMap::default(),
+            lossy_transform: lossy::Transform::default(),
         }
     }
 }
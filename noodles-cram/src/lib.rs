@@ -17,7 +17,10 @@ mod num;
 pub mod record;
 
 pub use self::{
-    data_container::DataContainer, file_definition::FileDefinition, indexer::index, record::Record,
+    data_container::{slice::builder::calculate_normalized_sequence_digest, DataContainer},
+    file_definition::FileDefinition,
+    indexer::index,
+    record::Record,
 };
 
 #[cfg(feature = "async")]
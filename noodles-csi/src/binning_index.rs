@@ -3,14 +3,17 @@
 pub mod index;
 mod indexer;
 mod reference_sequence;
+pub mod verifier;
 
-use std::io;
+use std::{io, ops::Range};
 
 use noodles_bgzf as bgzf;
 use noodles_core::region::Interval;
 
 use self::index::{reference_sequence::bin::Chunk, Header};
-pub use self::{index::Index, indexer::Indexer, reference_sequence::ReferenceSequence};
+pub use self::{
+    index::Index, indexer::Indexer, reference_sequence::ReferenceSequence, verifier::Verifier,
+};
 
 /// A binning index.
 pub trait BinningIndex {
@@ -164,6 +167,62 @@ pub fn optimize_chunks(chunks: &[Chunk], min_offset: bgzf::VirtualPosition) -> V
     merged_chunks
 }
 
+/// Returns the compressed byte ranges that contain a list of chunks.
+///
+/// Each range spans from the start of the block of the chunk start to the end of the block of the
+/// chunk end or, if the chunk ends at the start of a block, to the start of that block. Ranges
+/// that overlap or are separated by at most `max_gap` bytes are merged, which reduces the number
+/// of reads from a ranged-read storage (see [`bgzf::storage`]).
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bgzf as bgzf;
+/// use noodles_csi::binning_index::{chunk_ranges, index::reference_sequence::bin::Chunk};
+///
+/// let chunks = [
+///     Chunk::new(
+///         bgzf::VirtualPosition::try_from((8, 0))?,
+///         bgzf::VirtualPosition::try_from((13, 21))?,
+///     ),
+///     Chunk::new(
+///         bgzf::VirtualPosition::try_from((34, 55))?,
+///         bgzf::VirtualPosition::try_from((89, 144))?,
+///     ),
+///     Chunk::new(
+///         bgzf::VirtualPosition::try_from((233_000, 0))?,
+///         bgzf::VirtualPosition::try_from((377_000, 0))?,
+///     ),
+/// ];
+///
+/// assert_eq!(
+///     chunk_ranges(&chunks, 0),
+///     [8..65625, 233_000..377_000]
+/// );
+/// # Ok::<_, bgzf::virtual_position::TryFromU64U16TupleError>(())
+/// ```
+pub fn chunk_ranges(chunks: &[Chunk], max_gap: u64) -> Vec<Range<u64>> {
+    // The maximum size of a BGZF block, i.e., `BSIZE` (u16) + 1.
+    const MAX_BLOCK_SIZE: u64 = 1 << 16;
+
+    let ranges: Vec<_> = chunks
+        .iter()
+        .map(|chunk| {
+            let start = chunk.start().compressed();
+
+            let end = if chunk.end().uncompressed() == 0 {
+                chunk.end().compressed()
+            } else {
+                chunk.end().compressed() + MAX_BLOCK_SIZE
+            };
+
+            start..end
+        })
+        .collect();
+
+    bgzf::storage::coalesce_ranges(&ranges, max_gap)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -245,4 +304,31 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_chunk_ranges() -> Result<(), bgzf::virtual_position::TryFromU64U16TupleError> {
+        let chunks = [
+            Chunk::new(
+                bgzf::VirtualPosition::try_from((200_000, 0))?,
+                bgzf::VirtualPosition::try_from((210_000, 8))?,
+            ),
+            Chunk::new(
+                bgzf::VirtualPosition::try_from((0, 13))?,
+                bgzf::VirtualPosition::try_from((100, 21))?,
+            ),
+            Chunk::new(
+                bgzf::VirtualPosition::try_from((70_000, 34))?,
+                bgzf::VirtualPosition::try_from((80_000, 0))?,
+            ),
+        ];
+
+        assert_eq!(
+            chunk_ranges(&chunks, 0),
+            [0..65636, 70_000..80_000, 200_000..275_536]
+        );
+
+        assert_eq!(chunk_ranges(&chunks, 4364), [0..80_000, 200_000..275_536]);
+
+        Ok(())
+    }
 }
@@ -2,9 +2,12 @@
 
 mod builder;
 pub mod header;
+mod merge;
 pub mod reference_sequence;
 
-pub use self::{builder::Builder, header::Header, reference_sequence::ReferenceSequence};
+pub use self::{
+    builder::Builder, header::Header, merge::merge, reference_sequence::ReferenceSequence,
+};
 
 use std::io;
 
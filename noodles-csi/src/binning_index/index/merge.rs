@@ -0,0 +1,354 @@
+use std::io;
+
+use indexmap::IndexMap;
+use noodles_bgzf as bgzf;
+
+use super::{
+    reference_sequence::{
+        self,
+        index::{BinnedIndex, LinearIndex},
+    },
+    Header, Index, ReferenceSequence,
+};
+use crate::BinningIndex;
+
+/// Merges binning indices of concatenated files.
+///
+/// Each source is an index paired with the compressed offset of the start of its file in the
+/// concatenation. Virtual positions in each index are shifted by its offset, and the bins, linear
+/// indices, and metadata of each reference sequence are combined. Unplaced, unmapped record counts
+/// are summed.
+///
+/// All indices must have the same min shift and depth. If the indices have tabix headers, they
+/// must be the same, except for the reference sequence names, which are merged in order of
+/// appearance. Otherwise, reference sequences are matched by position.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_csi::{self as csi, binning_index::index, BinningIndex};
+///
+/// let a = csi::Index::builder().set_unplaced_unmapped_record_count(8).build();
+/// let b = csi::Index::builder().set_unplaced_unmapped_record_count(13).build();
+///
+/// let index = index::merge(&[(&a, 0), (&b, 144)])?;
+/// assert_eq!(index.unplaced_unmapped_record_count(), Some(21));
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn merge<I>(sources: &[(&Index<I>, u64)]) -> io::Result<Index<I>>
+where
+    I: reference_sequence::Index + Default,
+{
+    let mut sources = sources.to_vec();
+    sources.sort_by_key(|(_, offset)| *offset);
+
+    let Some((first_index, _)) = sources.first() else {
+        return Ok(Index::default());
+    };
+
+    let min_shift = first_index.min_shift();
+    let depth = first_index.depth();
+
+    let mut header = first_index.header().cloned();
+    let mut reference_sequences: Vec<ReferenceSequence<I>> = Vec::new();
+    let mut unplaced_unmapped_record_count = None;
+
+    for (index, offset) in sources {
+        if index.min_shift() != min_shift || index.depth() != depth {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "min shift and depth mismatch",
+            ));
+        }
+
+        let ids = match (header.as_mut(), index.header()) {
+            (Some(dst), Some(src)) => merge_headers(dst, src, index.reference_sequences().len())?,
+            (None, None) => (0..index.reference_sequences().len()).collect(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "header mismatch",
+                ))
+            }
+        };
+
+        for (id, reference_sequence) in ids.into_iter().zip(index.reference_sequences()) {
+            if id >= reference_sequences.len() {
+                reference_sequences.resize_with(id + 1, || {
+                    ReferenceSequence::new(IndexMap::new(), I::default(), None)
+                });
+            }
+
+            reference_sequences[id].merge(reference_sequence, offset)?;
+        }
+
+        if let Some(n) = index.unplaced_unmapped_record_count() {
+            unplaced_unmapped_record_count = Some(unplaced_unmapped_record_count.unwrap_or(0) + n);
+        }
+    }
+
+    let mut builder = Index::builder()
+        .set_min_shift(min_shift)
+        .set_depth(depth)
+        .set_reference_sequences(reference_sequences);
+
+    if let Some(header) = header {
+        builder = builder.set_header(header);
+    }
+
+    if let Some(n) = unplaced_unmapped_record_count {
+        builder = builder.set_unplaced_unmapped_record_count(n);
+    }
+
+    Ok(builder.build())
+}
+
+// Adds the reference sequence names of `src` to `dst` and returns the new ID of each source
+// reference sequence.
+fn merge_headers(
+    dst: &mut Header,
+    src: &Header,
+    reference_sequence_count: usize,
+) -> io::Result<Vec<usize>> {
+    let is_compatible = dst.format() == src.format()
+        && dst.reference_sequence_name_index() == src.reference_sequence_name_index()
+        && dst.start_position_index() == src.start_position_index()
+        && dst.end_position_index() == src.end_position_index()
+        && dst.line_comment_prefix() == src.line_comment_prefix()
+        && dst.line_skip_count() == src.line_skip_count();
+
+    if !is_compatible {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "header mismatch",
+        ));
+    }
+
+    if src.reference_sequence_names().len() != reference_sequence_count {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "reference sequence names-reference sequences length mismatch",
+        ));
+    }
+
+    let names = dst.reference_sequence_names_mut();
+
+    Ok(src
+        .reference_sequence_names()
+        .iter()
+        .map(|name| names.insert_full(name.clone()).0)
+        .collect())
+}
+
+pub(crate) fn merge_binned_indices(
+    dst: &mut BinnedIndex,
+    src: &BinnedIndex,
+    offset: u64,
+) -> io::Result<()> {
+    for (&bin_id, &position) in src {
+        let position = shift(position, offset)?;
+
+        dst.entry(bin_id)
+            .and_modify(|loffset| *loffset = (*loffset).min(position))
+            .or_insert(position);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn merge_linear_indices(
+    dst: &mut LinearIndex,
+    src: &LinearIndex,
+    offset: u64,
+) -> io::Result<()> {
+    for (i, &position) in src.iter().enumerate() {
+        let position = shift(position, offset)?;
+
+        match dst.get_mut(i) {
+            Some(p) => *p = (*p).min(position),
+            None => dst.push(position),
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn shift(
+    position: bgzf::VirtualPosition,
+    offset: u64,
+) -> io::Result<bgzf::VirtualPosition> {
+    position
+        .compressed()
+        .checked_add(offset)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid virtual position offset",
+            )
+        })
+        .and_then(|compressed| {
+            bgzf::VirtualPosition::try_from((compressed, position.uncompressed()))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::binning_index::{
+        index::{header::Format, reference_sequence::bin::Chunk},
+        Indexer,
+    };
+
+    type Record = (Option<(usize, usize, usize, bool)>, (u64, u16), (u64, u16));
+
+    fn build_index(
+        header: Option<Header>,
+        records: &[Record],
+        offset: u64,
+    ) -> Result<Index<LinearIndex>, Box<dyn std::error::Error>> {
+        let mut indexer = Indexer::default();
+
+        if let Some(header) = header {
+            indexer = indexer.set_header(header);
+        }
+
+        for &(context, (start_c, start_u), (end_c, end_u)) in records {
+            let context = context
+                .map(
+                    |(id, start, end, is_mapped)| -> Result<_, Box<dyn std::error::Error>> {
+                        Ok((
+                            id,
+                            Position::try_from(start)?,
+                            Position::try_from(end)?,
+                            is_mapped,
+                        ))
+                    },
+                )
+                .transpose()?;
+
+            let chunk = Chunk::new(
+                bgzf::VirtualPosition::try_from((start_c + offset, start_u))?,
+                bgzf::VirtualPosition::try_from((end_c + offset, end_u))?,
+            );
+
+            indexer.add_record(context, chunk)?;
+        }
+
+        let reference_sequence_count = records
+            .iter()
+            .filter_map(|(context, _, _)| context.map(|(id, ..)| id + 1))
+            .max()
+            .unwrap_or(0);
+
+        Ok(indexer.build(reference_sequence_count))
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), Box<dyn std::error::Error>> {
+        const OFFSET: u64 = 1 << 20;
+
+        let a = [
+            (Some((0, 8, 13, true)), (0, 0), (0, 55)),
+            (Some((0, 20000, 20100, true)), (0, 55), (89, 0)),
+            (Some((1, 34, 55, false)), (89, 0), (89, 34)),
+        ];
+
+        let b = [
+            (Some((0, 50000, 50100, true)), (0, 0), (0, 21)),
+            (Some((1, 8, 13, true)), (0, 21), (0, 89)),
+            (None, (144, 0), (144, 8)),
+        ];
+
+        let index_a = build_index(None, &a, 0)?;
+        let index_b = build_index(None, &b, 0)?;
+        let actual = merge(&[(&index_b, OFFSET), (&index_a, 0)])?;
+
+        let mut records: Vec<_> = a
+            .iter()
+            .copied()
+            .chain(b.iter().map(|&(context, (sc, su), (ec, eu))| {
+                (context, (sc + OFFSET, su), (ec + OFFSET, eu))
+            }))
+            .collect();
+
+        // The indexer requires records to be sorted by reference sequence.
+        records.sort_by_key(|(context, _, _)| context.map(|(id, ..)| id).unwrap_or(usize::MAX));
+
+        let expected = build_index(None, &records, 0)?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_headers() -> Result<(), Box<dyn std::error::Error>> {
+        const OFFSET: u64 = 1 << 20;
+
+        fn build_header(names: &[&str]) -> Header {
+            Header::builder()
+                .set_format(Format::Vcf)
+                .set_reference_sequence_names(names.iter().map(|s| String::from(*s)).collect())
+                .build()
+        }
+
+        let index_a = build_index(
+            Some(build_header(&["sq0", "sq1"])),
+            &[(Some((1, 8, 13, true)), (0, 0), (0, 55))],
+            0,
+        )?;
+
+        let index_b = build_index(
+            Some(build_header(&["sq1", "sq2"])),
+            &[
+                (Some((0, 21, 34, true)), (0, 0), (0, 21)),
+                (Some((1, 8, 13, true)), (0, 21), (0, 89)),
+            ],
+            OFFSET,
+        )?;
+
+        let actual = merge(&[(&index_a, 0), (&index_b, 0)])?;
+
+        let expected = build_index(
+            Some(build_header(&["sq0", "sq1", "sq2"])),
+            &[
+                (Some((1, 8, 13, true)), (0, 0), (0, 55)),
+                (Some((1, 21, 34, true)), (OFFSET, 0), (OFFSET, 21)),
+                (Some((2, 8, 13, true)), (OFFSET, 21), (OFFSET, 89)),
+            ],
+            0,
+        )?;
+
+        assert_eq!(actual, expected);
+
+        let index_c = build_index(None, &[], 0)?;
+        assert!(merge(&[(&index_a, 0), (&index_c, OFFSET)]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_min_shift_mismatch() {
+        let a: Index<LinearIndex> = Index::builder().set_min_shift(14).build();
+        let b = Index::builder().set_min_shift(12).build();
+        assert!(matches!(
+            merge(&[(&a, 0), (&b, 8)]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_shift() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(
+            shift(bgzf::VirtualPosition::try_from((8, 13))?, 21)?,
+            bgzf::VirtualPosition::try_from((29, 13))?
+        );
+
+        assert!(shift(bgzf::VirtualPosition::MAX, 1).is_err());
+
+        Ok(())
+    }
+}
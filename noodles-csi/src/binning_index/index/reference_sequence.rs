@@ -228,6 +228,35 @@ where
 
         metadata.update(is_mapped, chunk);
     }
+
+    pub(crate) fn merge(&mut self, other: &Self, offset: u64) -> io::Result<()> {
+        use super::merge::shift;
+
+        for (&id, bin) in other.bins() {
+            let dst_bin = self.bins.entry(id).or_insert(Bin::new(Vec::new()));
+
+            for chunk in bin.chunks() {
+                let start = shift(chunk.start(), offset)?;
+                let end = shift(chunk.end(), offset)?;
+                dst_bin.add_chunk(Chunk::new(start, end));
+            }
+        }
+
+        self.index.merge(&other.index, offset)?;
+
+        if let Some(other_metadata) = other.metadata.as_ref() {
+            let metadata = self.metadata.get_or_insert(Metadata::new(
+                bgzf::VirtualPosition::MAX,
+                bgzf::VirtualPosition::MIN,
+                0,
+                0,
+            ));
+
+            metadata.merge(other_metadata, offset)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<I> binning_index::ReferenceSequence for ReferenceSequence<I>
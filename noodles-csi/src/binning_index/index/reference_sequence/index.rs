@@ -3,6 +3,8 @@
 mod binned_index;
 mod linear_index;
 
+use std::io;
+
 use noodles_bgzf as bgzf;
 use noodles_core::Position;
 
@@ -20,4 +22,16 @@ pub trait Index {
 
     /// Adds a record to the index.
     fn update(&mut self, min_shift: u8, depth: u8, start: Position, end: Position, chunk: Chunk);
+
+    /// Merges another index, shifting its virtual positions by a compressed offset.
+    ///
+    /// The default implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    fn merge(&mut self, other: &Self, offset: u64) -> io::Result<()> {
+        let _ = (other, offset);
+
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "index merging is not supported",
+        ))
+    }
 }
@@ -1,9 +1,14 @@
+use std::io;
+
 use indexmap::IndexMap;
 use noodles_bgzf as bgzf;
 use noodles_core::Position;
 
 use super::Index;
-use crate::binning_index::index::reference_sequence::{bin::Chunk, parent_id, reg2bin};
+use crate::binning_index::index::{
+    merge::merge_binned_indices,
+    reference_sequence::{bin::Chunk, parent_id, reg2bin},
+};
 
 /// A binned index.
 pub type BinnedIndex = IndexMap<usize, bgzf::VirtualPosition>;
@@ -42,6 +47,10 @@ impl Index for BinnedIndex {
             })
             .or_insert(chunk.start());
     }
+
+    fn merge(&mut self, other: &Self, offset: u64) -> io::Result<()> {
+        merge_binned_indices(self, other, offset)
+    }
 }
 
 #[cfg(test)]
@@ -87,4 +96,35 @@ mod tests {
             Some(bgzf::VirtualPosition::from(21))
         );
     }
+
+    #[test]
+    fn test_merge() -> Result<(), Box<dyn std::error::Error>> {
+        let mut index: BinnedIndex = [
+            (0, bgzf::VirtualPosition::from(8)),
+            (2, bgzf::VirtualPosition::from(13)),
+        ]
+        .into_iter()
+        .collect();
+
+        let other: BinnedIndex = [
+            (2, bgzf::VirtualPosition::try_from((21, 0))?),
+            (9, bgzf::VirtualPosition::try_from((34, 0))?),
+        ]
+        .into_iter()
+        .collect();
+
+        index.merge(&other, 55)?;
+
+        let expected: BinnedIndex = [
+            (0, bgzf::VirtualPosition::from(8)),
+            (2, bgzf::VirtualPosition::from(13)),
+            (9, bgzf::VirtualPosition::try_from((89, 0))?),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(index, expected);
+
+        Ok(())
+    }
 }
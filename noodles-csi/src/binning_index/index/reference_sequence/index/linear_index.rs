@@ -1,8 +1,10 @@
+use std::io;
+
 use noodles_bgzf as bgzf;
 use noodles_core::Position;
 
 use super::Index;
-use crate::binning_index::index::reference_sequence::bin::Chunk;
+use crate::binning_index::index::{merge::merge_linear_indices, reference_sequence::bin::Chunk};
 
 /// A linear index.
 pub type LinearIndex = Vec<bgzf::VirtualPosition>;
@@ -29,6 +31,10 @@ impl Index for LinearIndex {
             self.resize(new_len, chunk.start());
         }
     }
+
+    fn merge(&mut self, other: &Self, offset: u64) -> io::Result<()> {
+        merge_linear_indices(self, other, offset)
+    }
 }
 
 #[cfg(test)]
@@ -101,4 +107,31 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_merge() -> Result<(), Box<dyn std::error::Error>> {
+        let mut index = vec![
+            bgzf::VirtualPosition::from(8),
+            bgzf::VirtualPosition::default(),
+        ];
+
+        let other = vec![
+            bgzf::VirtualPosition::try_from((13, 0))?,
+            bgzf::VirtualPosition::try_from((21, 0))?,
+            bgzf::VirtualPosition::try_from((34, 0))?,
+        ];
+
+        index.merge(&other, 55)?;
+
+        assert_eq!(
+            index,
+            [
+                bgzf::VirtualPosition::from(8),
+                bgzf::VirtualPosition::default(),
+                bgzf::VirtualPosition::try_from((89, 0))?,
+            ]
+        );
+
+        Ok(())
+    }
 }
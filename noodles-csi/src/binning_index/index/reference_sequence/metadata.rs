@@ -1,6 +1,9 @@
+use std::io;
+
 use noodles_bgzf as bgzf;
 
 use super::bin::Chunk;
+use crate::binning_index::index::merge::shift;
 
 /// Index reference sequence metadata.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -135,4 +138,17 @@ impl Metadata {
         self.start_position = self.start_position.min(chunk.start());
         self.end_position = self.end_position.max(chunk.end());
     }
+
+    pub(super) fn merge(&mut self, other: &Self, offset: u64) -> io::Result<()> {
+        self.mapped_record_count += other.mapped_record_count;
+        self.unmapped_record_count += other.unmapped_record_count;
+
+        let start_position = shift(other.start_position, offset)?;
+        self.start_position = self.start_position.min(start_position);
+
+        let end_position = shift(other.end_position, offset)?;
+        self.end_position = self.end_position.max(end_position);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,823 @@
+//! Binning index verifier.
+//!
+//! A [`Verifier`] checks a binning index against the file it indexes, e.g., to detect stale
+//! indices. It reports each [`Discrepancy`] it finds rather than stopping at the first one.
+
+mod discrepancy;
+mod text_record_reader;
+
+pub use self::{discrepancy::Discrepancy, text_record_reader::TextRecordReader};
+
+use std::{fs, io, num::NonZeroUsize, path::Path, time::SystemTime};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+
+use super::{
+    index::{
+        reference_sequence::{self, bin::Chunk},
+        ReferenceSequence,
+    },
+    BinningIndex, Index,
+};
+
+/// An indexed record alignment context.
+///
+/// This is the reference sequence ID, start position, end position, and whether the record is
+/// mapped, or `None` if the record is unplaced. It is the same context given to
+/// [`super::Indexer::add_record`].
+pub type AlignmentContext = Option<(usize, Position, Position, bool)>;
+
+/// A reader of the alignment contexts of records in an indexed file.
+///
+/// This is implemented by [`TextRecordReader`] for tabix-indexed text files. BAM and BCF readers
+/// implement this in `noodles-bam` and `noodles-bcf`, respectively.
+pub trait RecordReader {
+    /// Seeks to the given virtual position.
+    fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<()>;
+
+    /// Returns the current virtual position.
+    fn virtual_position(&self) -> bgzf::VirtualPosition;
+
+    /// Reads the alignment context of the next record.
+    ///
+    /// This returns `None` at the end of the file.
+    fn read_alignment_context(&mut self) -> io::Result<Option<AlignmentContext>>;
+}
+
+impl<R> RecordReader for &mut R
+where
+    R: RecordReader + ?Sized,
+{
+    fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<()> {
+        (**self).seek(pos)
+    }
+
+    fn virtual_position(&self) -> bgzf::VirtualPosition {
+        (**self).virtual_position()
+    }
+
+    fn read_alignment_context(&mut self) -> io::Result<Option<AlignmentContext>> {
+        (**self).read_alignment_context()
+    }
+}
+
+/// A verification mode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Check all bins and the metadata.
+    #[default]
+    Full,
+    /// Check at most the given number of bins per reference sequence, evenly spaced.
+    ///
+    /// Metadata record counts are not checked, as they require reading the entire file.
+    Sampled(NonZeroUsize),
+}
+
+/// A binning index verifier.
+pub struct Verifier<'i, I> {
+    index: &'i Index<I>,
+    mode: Mode,
+}
+
+impl<'i, I> Verifier<'i, I>
+where
+    I: reference_sequence::Index,
+{
+    /// Creates a binning index verifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, binning_index::Verifier};
+    /// let index = csi::Index::default();
+    /// let verifier = Verifier::new(&index);
+    /// ```
+    pub fn new(index: &'i Index<I>) -> Self {
+        Self {
+            index,
+            mode: Mode::default(),
+        }
+    }
+
+    /// Sets the verification mode.
+    ///
+    /// By default, this is [`Mode::Full`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_csi::{self as csi, binning_index::{verifier::Mode, Verifier}};
+    ///
+    /// let index = csi::Index::default();
+    /// let verifier = Verifier::new(&index).set_mode(Mode::Sampled(NonZeroUsize::MIN));
+    /// ```
+    pub fn set_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Verifies the index against the records read by the given reader.
+    ///
+    /// This checks that
+    ///
+    ///   * the records in each bin chunk are on the reference sequence and overlap the bin;
+    ///   * the linear index does not skip any record read from a chunk; and
+    ///   * in [`Mode::Full`], the metadata record counts and the unplaced, unmapped record count
+    ///     match the file.
+    ///
+    /// I/O errors other than invalid or truncated data are returned as errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::{
+    ///     self as csi,
+    ///     binning_index::{index::Header, verifier::TextRecordReader, Verifier},
+    /// };
+    ///
+    /// let index = csi::Index::default();
+    /// let header = Header::default();
+    /// let mut reader = TextRecordReader::new(bgzf::Reader::new(io::Cursor::new(Vec::new())), &header);
+    ///
+    /// let discrepancies = Verifier::new(&index).verify(&mut reader)?;
+    /// assert!(discrepancies.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn verify<R>(&self, reader: &mut R) -> io::Result<Vec<Discrepancy>>
+    where
+        R: RecordReader,
+    {
+        let mut discrepancies = Vec::new();
+
+        for (reference_sequence_id, reference_sequence) in
+            self.index.reference_sequences().iter().enumerate()
+        {
+            self.verify_bins(
+                reader,
+                reference_sequence_id,
+                reference_sequence,
+                &mut discrepancies,
+            )?;
+
+            if self.mode == Mode::Full {
+                verify_metadata(
+                    reader,
+                    reference_sequence_id,
+                    reference_sequence,
+                    &mut discrepancies,
+                )?;
+            }
+        }
+
+        if self.mode == Mode::Full {
+            self.verify_unplaced_unmapped_record_count(reader, &mut discrepancies)?;
+        }
+
+        Ok(discrepancies)
+    }
+
+    fn verify_bins<R>(
+        &self,
+        reader: &mut R,
+        reference_sequence_id: usize,
+        reference_sequence: &ReferenceSequence<I>,
+        discrepancies: &mut Vec<Discrepancy>,
+    ) -> io::Result<()>
+    where
+        R: RecordReader,
+    {
+        let bins = reference_sequence.bins();
+
+        let step = match self.mode {
+            Mode::Full => 1,
+            Mode::Sampled(n) => ((bins.len() + n.get() - 1) / n.get()).max(1),
+        };
+
+        for (&bin_id, bin) in bins.iter().step_by(step) {
+            for &chunk in bin.chunks() {
+                let result = self.verify_chunk(
+                    reader,
+                    reference_sequence_id,
+                    reference_sequence,
+                    bin_id,
+                    chunk,
+                    discrepancies,
+                );
+
+                match result {
+                    Ok(()) => {}
+                    Err(e) if is_invalid_data(&e) => {
+                        discrepancies.push(Discrepancy::InvalidChunk {
+                            reference_sequence_id,
+                            bin_id,
+                            chunk,
+                        })
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn verify_chunk<R>(
+        &self,
+        reader: &mut R,
+        reference_sequence_id: usize,
+        reference_sequence: &ReferenceSequence<I>,
+        bin_id: usize,
+        chunk: Chunk,
+        discrepancies: &mut Vec<Discrepancy>,
+    ) -> io::Result<()>
+    where
+        R: RecordReader,
+    {
+        let min_shift = self.index.min_shift();
+        let depth = self.index.depth();
+
+        if chunk.start() >= chunk.end() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid chunk"));
+        }
+
+        let (bin_start, bin_end) = bin_interval(bin_id, min_shift, depth);
+
+        reader.seek(chunk.start())?;
+
+        loop {
+            let record_position = reader.virtual_position();
+
+            if record_position >= chunk.end() {
+                break;
+            }
+
+            let context = reader.read_alignment_context()?.ok_or_else(|| {
+                io::Error::new(io::ErrorKind::UnexpectedEof, "unexpected end of chunk")
+            })?;
+
+            let (start, end) = match context {
+                Some((id, start, end, _)) if id == reference_sequence_id => (start, end),
+                _ => {
+                    discrepancies.push(Discrepancy::RecordOutsideBin {
+                        reference_sequence_id,
+                        bin_id,
+                        record_position,
+                    });
+
+                    continue;
+                }
+            };
+
+            if usize::from(start) > bin_end || usize::from(end) < bin_start {
+                discrepancies.push(Discrepancy::RecordOutsideBin {
+                    reference_sequence_id,
+                    bin_id,
+                    record_position,
+                });
+            }
+
+            // A query starting anywhere in the record must not skip it. The minimum offset is
+            // constant within each 2^min_shift window, so only the start of each window the
+            // record covers needs to be checked.
+            let positions = window_start_positions(start, end, min_shift);
+
+            for position in positions {
+                let min_offset = reference_sequence.min_offset(min_shift, depth, position);
+
+                if min_offset > record_position {
+                    discrepancies.push(Discrepancy::InvalidLinearOffset {
+                        reference_sequence_id,
+                        position,
+                        min_offset,
+                        record_position,
+                    });
+
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn verify_unplaced_unmapped_record_count<R>(
+        &self,
+        reader: &mut R,
+        discrepancies: &mut Vec<Discrepancy>,
+    ) -> io::Result<()>
+    where
+        R: RecordReader,
+    {
+        let Some(expected) = self.index.unplaced_unmapped_record_count() else {
+            return Ok(());
+        };
+
+        let pos = self
+            .index
+            .last_first_record_start_position()
+            .unwrap_or_default();
+
+        reader.seek(pos)?;
+
+        let mut actual = 0;
+
+        while let Some(context) = reader.read_alignment_context()? {
+            if context.is_none() {
+                actual += 1;
+            }
+        }
+
+        if actual != expected {
+            discrepancies
+                .push(Discrepancy::UnplacedUnmappedRecordCountMismatch { expected, actual });
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks whether an index was last modified before its associated file.
+///
+/// This returns [`Discrepancy::StaleIndex`] if the index is older than the file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_csi::binning_index::verifier;
+///
+/// if let Some(discrepancy) = verifier::verify_modification_times("sample.vcf.gz", "sample.vcf.gz.tbi")? {
+///     eprintln!("{discrepancy}");
+/// }
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn verify_modification_times<P, Q>(data_src: P, index_src: Q) -> io::Result<Option<Discrepancy>>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let data_modified = fs::metadata(data_src)?.modified()?;
+    let index_modified = fs::metadata(index_src)?.modified()?;
+    Ok(compare_modification_times(data_modified, index_modified))
+}
+
+fn compare_modification_times(
+    data_modified: SystemTime,
+    index_modified: SystemTime,
+) -> Option<Discrepancy> {
+    (index_modified < data_modified).then_some(Discrepancy::StaleIndex {
+        data_modified,
+        index_modified,
+    })
+}
+
+fn verify_metadata<I, R>(
+    reader: &mut R,
+    reference_sequence_id: usize,
+    reference_sequence: &ReferenceSequence<I>,
+    discrepancies: &mut Vec<Discrepancy>,
+) -> io::Result<()>
+where
+    I: reference_sequence::Index,
+    R: RecordReader,
+{
+    use super::ReferenceSequence as _;
+
+    let Some(metadata) = reference_sequence.metadata() else {
+        return Ok(());
+    };
+
+    let (mapped_record_count, unmapped_record_count) = match count_records(
+        reader,
+        reference_sequence_id,
+        metadata.start_position(),
+        metadata.end_position(),
+    ) {
+        Ok(counts) => counts,
+        Err(e) if is_invalid_data(&e) => (0, 0),
+        Err(e) => return Err(e),
+    };
+
+    if mapped_record_count != metadata.mapped_record_count() {
+        discrepancies.push(Discrepancy::MappedRecordCountMismatch {
+            reference_sequence_id,
+            expected: metadata.mapped_record_count(),
+            actual: mapped_record_count,
+        });
+    }
+
+    if unmapped_record_count != metadata.unmapped_record_count() {
+        discrepancies.push(Discrepancy::UnmappedRecordCountMismatch {
+            reference_sequence_id,
+            expected: metadata.unmapped_record_count(),
+            actual: unmapped_record_count,
+        });
+    }
+
+    Ok(())
+}
+
+fn count_records<R>(
+    reader: &mut R,
+    reference_sequence_id: usize,
+    start: bgzf::VirtualPosition,
+    end: bgzf::VirtualPosition,
+) -> io::Result<(u64, u64)>
+where
+    R: RecordReader,
+{
+    let mut mapped_record_count = 0;
+    let mut unmapped_record_count = 0;
+
+    reader.seek(start)?;
+
+    while reader.virtual_position() < end {
+        let Some(context) = reader.read_alignment_context()? else {
+            break;
+        };
+
+        match context {
+            Some((id, _, _, true)) if id == reference_sequence_id => mapped_record_count += 1,
+            Some((id, _, _, false)) if id == reference_sequence_id => unmapped_record_count += 1,
+            _ => {}
+        }
+    }
+
+    Ok((mapped_record_count, unmapped_record_count))
+}
+
+fn is_invalid_data(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof
+    )
+}
+
+// Returns the 1-based, closed interval of a bin.
+fn bin_interval(bin_id: usize, min_shift: u8, depth: u8) -> (usize, usize) {
+    let mut level = 0;
+    let mut first_id = 0;
+
+    while level < depth {
+        let next_first_id = first_id + (1 << (3 * u32::from(level)));
+
+        if bin_id < next_first_id {
+            break;
+        }
+
+        first_id = next_first_id;
+        level += 1;
+    }
+
+    let shift = u32::from(min_shift) + 3 * u32::from(depth - level);
+    let offset = bin_id - first_id;
+
+    let start = (offset << shift) + 1;
+    let end = (offset + 1) << shift;
+
+    (start, end)
+}
+
+// Returns the start position and the start position of each following 2^min_shift window up to
+// the end position.
+fn window_start_positions(
+    start: Position,
+    end: Position,
+    min_shift: u8,
+) -> impl Iterator<Item = Position> {
+    let first_window = (usize::from(start) - 1) >> min_shift;
+    let last_window = (usize::from(end) - 1) >> min_shift;
+
+    let window_starts =
+        (first_window + 1..=last_window).filter_map(move |i| Position::new((i << min_shift) + 1));
+
+    std::iter::once(start).chain(window_starts)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::binning_index::{
+        index::{
+            header::{format::CoordinateSystem, Format},
+            reference_sequence::{index::LinearIndex, Bin, Metadata},
+            Header,
+        },
+        Indexer, ReferenceSequence as _,
+    };
+
+    const LINES: &[(&str, usize, usize)] = &[
+        ("sq0", 8, 13),
+        ("sq0", 21, 34),
+        ("sq0", 20000, 20100),
+        ("sq1", 55, 89),
+    ];
+
+    fn build_header() -> Header {
+        Header::builder()
+            .set_format(Format::Generic(CoordinateSystem::Gff))
+            .set_start_position_index(1)
+            .set_end_position_index(Some(2))
+            .set_reference_sequence_names(
+                [String::from("sq0"), String::from("sq1")]
+                    .into_iter()
+                    .collect(),
+            )
+            .build()
+    }
+
+    fn build_data_and_index(
+        header: &Header,
+    ) -> Result<(Vec<u8>, Index<LinearIndex>), Box<dyn std::error::Error>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        let mut indexer = Indexer::default().set_header(header.clone());
+
+        for &(name, start, end) in LINES {
+            let chunk_start = writer.virtual_position();
+            writeln!(writer, "{name}\t{start}\t{end}")?;
+            // Each record is written in its own block to exercise seeking between blocks.
+            writer.flush()?;
+            let chunk_end = writer.virtual_position();
+
+            let id = header
+                .reference_sequence_names()
+                .get_index_of(name)
+                .expect("missing reference sequence name");
+
+            indexer.add_record(
+                Some((
+                    id,
+                    Position::try_from(start)?,
+                    Position::try_from(end)?,
+                    true,
+                )),
+                Chunk::new(chunk_start, chunk_end),
+            )?;
+        }
+
+        let data = writer.finish()?;
+        let index = indexer.build(header.reference_sequence_names().len());
+
+        Ok((data, index))
+    }
+
+    fn verify(
+        index: &Index<LinearIndex>,
+        header: &Header,
+        data: &[u8],
+        mode: Mode,
+    ) -> io::Result<Vec<Discrepancy>> {
+        let mut reader = TextRecordReader::new(bgzf::Reader::new(io::Cursor::new(data)), header);
+        Verifier::new(index).set_mode(mode).verify(&mut reader)
+    }
+
+    fn rebuild_reference_sequence<F>(
+        index: &Index<LinearIndex>,
+        reference_sequence_id: usize,
+        f: F,
+    ) -> Index<LinearIndex>
+    where
+        F: FnOnce(&ReferenceSequence<LinearIndex>) -> ReferenceSequence<LinearIndex>,
+    {
+        let mut reference_sequences = index.reference_sequences().to_vec();
+        reference_sequences[reference_sequence_id] =
+            f(&index.reference_sequences()[reference_sequence_id]);
+
+        let mut builder = Index::builder()
+            .set_min_shift(index.min_shift())
+            .set_depth(index.depth())
+            .set_reference_sequences(reference_sequences);
+
+        if let Some(header) = index.header() {
+            builder = builder.set_header(header.clone());
+        }
+
+        if let Some(n) = index.unplaced_unmapped_record_count() {
+            builder = builder.set_unplaced_unmapped_record_count(n);
+        }
+
+        builder.build()
+    }
+
+    #[test]
+    fn test_verify() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let (data, index) = build_data_and_index(&header)?;
+
+        assert!(verify(&index, &header, &data, Mode::Full)?.is_empty());
+        assert!(verify(&index, &header, &data, Mode::Sampled(NonZeroUsize::MIN))?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_stale_bins() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let (data, index) = build_data_and_index(&header)?;
+
+        // Moves all the chunks of sq0 to the bin of the first record of sq1.
+        let sq1_chunk = index.reference_sequences()[1]
+            .bins()
+            .values()
+            .flat_map(|bin| bin.chunks())
+            .copied()
+            .next()
+            .expect("missing chunk");
+
+        let index = rebuild_reference_sequence(&index, 0, |reference_sequence| {
+            let bins = reference_sequence
+                .bins()
+                .keys()
+                .map(|&id| (id, Bin::new(vec![sq1_chunk])))
+                .collect();
+
+            ReferenceSequence::new(bins, reference_sequence.index().clone(), None)
+        });
+
+        let discrepancies = verify(&index, &header, &data, Mode::Full)?;
+        let bin_ids: Vec<_> = index.reference_sequences()[0]
+            .bins()
+            .keys()
+            .copied()
+            .collect();
+
+        let expected: Vec<_> = bin_ids
+            .into_iter()
+            .map(|bin_id| Discrepancy::RecordOutsideBin {
+                reference_sequence_id: 0,
+                bin_id,
+                record_position: sq1_chunk.start(),
+            })
+            .collect();
+
+        assert_eq!(discrepancies, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_invalid_chunk() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let (data, index) = build_data_and_index(&header)?;
+
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::try_from((data.len() as u64, 0))?,
+            bgzf::VirtualPosition::try_from((data.len() as u64 + 1, 0))?,
+        );
+
+        let index = rebuild_reference_sequence(&index, 1, |reference_sequence| {
+            ReferenceSequence::new(
+                [(4681, Bin::new(vec![chunk]))].into_iter().collect(),
+                reference_sequence.index().clone(),
+                None,
+            )
+        });
+
+        assert_eq!(
+            verify(&index, &header, &data, Mode::Full)?,
+            [Discrepancy::InvalidChunk {
+                reference_sequence_id: 1,
+                bin_id: 4681,
+                chunk,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_invalid_linear_index() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let (data, index) = build_data_and_index(&header)?;
+
+        let record_position = index.reference_sequences()[0]
+            .bins()
+            .values()
+            .flat_map(|bin| bin.chunks())
+            .map(|chunk| chunk.start())
+            .max()
+            .expect("missing chunk");
+
+        let index = rebuild_reference_sequence(&index, 0, |reference_sequence| {
+            let mut linear_index = reference_sequence.index().clone();
+            linear_index[1] = bgzf::VirtualPosition::MAX;
+
+            ReferenceSequence::new(
+                reference_sequence.bins().clone(),
+                linear_index,
+                reference_sequence.metadata().cloned(),
+            )
+        });
+
+        assert_eq!(
+            verify(&index, &header, &data, Mode::Full)?,
+            [Discrepancy::InvalidLinearOffset {
+                reference_sequence_id: 0,
+                position: Position::try_from(20000)?,
+                min_offset: bgzf::VirtualPosition::MAX,
+                record_position,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_metadata_count_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let (data, index) = build_data_and_index(&header)?;
+
+        let index = rebuild_reference_sequence(&index, 0, |reference_sequence| {
+            let metadata = reference_sequence.metadata().expect("missing metadata");
+
+            ReferenceSequence::new(
+                reference_sequence.bins().clone(),
+                reference_sequence.index().clone(),
+                Some(Metadata::new(
+                    metadata.start_position(),
+                    metadata.end_position(),
+                    2,
+                    1,
+                )),
+            )
+        });
+
+        assert_eq!(
+            verify(&index, &header, &data, Mode::Full)?,
+            [
+                Discrepancy::MappedRecordCountMismatch {
+                    reference_sequence_id: 0,
+                    expected: 2,
+                    actual: 3,
+                },
+                Discrepancy::UnmappedRecordCountMismatch {
+                    reference_sequence_id: 0,
+                    expected: 1,
+                    actual: 0,
+                },
+            ]
+        );
+
+        assert!(verify(&index, &header, &data, Mode::Sampled(NonZeroUsize::MIN))?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bin_interval() {
+        const MIN_SHIFT: u8 = 14;
+        const DEPTH: u8 = 5;
+
+        assert_eq!(bin_interval(0, MIN_SHIFT, DEPTH), (1, 1 << 29));
+        assert_eq!(bin_interval(1, MIN_SHIFT, DEPTH), (1, 1 << 26));
+        assert_eq!(bin_interval(2, MIN_SHIFT, DEPTH), ((1 << 26) + 1, 1 << 27));
+        assert_eq!(bin_interval(4681, MIN_SHIFT, DEPTH), (1, 1 << 14));
+        assert_eq!(
+            bin_interval(4682, MIN_SHIFT, DEPTH),
+            ((1 << 14) + 1, 1 << 15)
+        );
+    }
+
+    #[test]
+    fn test_window_start_positions() -> Result<(), noodles_core::position::TryFromIntError> {
+        let positions: Vec<_> =
+            window_start_positions(Position::try_from(8)?, Position::try_from(13)?, 4).collect();
+        assert_eq!(positions, [Position::try_from(8)?]);
+
+        let positions: Vec<_> =
+            window_start_positions(Position::try_from(8)?, Position::try_from(40)?, 4).collect();
+        assert_eq!(
+            positions,
+            [
+                Position::try_from(8)?,
+                Position::try_from(17)?,
+                Position::try_from(33)?,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare_modification_times() {
+        use std::time::Duration;
+
+        let t0 = SystemTime::UNIX_EPOCH;
+        let t1 = t0 + Duration::from_secs(1);
+
+        assert!(compare_modification_times(t0, t1).is_none());
+        assert!(compare_modification_times(t0, t0).is_none());
+        assert_eq!(
+            compare_modification_times(t1, t0),
+            Some(Discrepancy::StaleIndex {
+                data_modified: t1,
+                index_modified: t0,
+            })
+        );
+    }
+}
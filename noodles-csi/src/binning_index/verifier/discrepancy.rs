@@ -0,0 +1,127 @@
+use std::{fmt, time::SystemTime};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+
+use crate::binning_index::index::reference_sequence::bin::Chunk;
+
+/// A discrepancy between a binning index and its associated file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Discrepancy {
+    /// A bin chunk is empty, reversed, or does not point to a readable record boundary.
+    InvalidChunk {
+        /// The reference sequence ID.
+        reference_sequence_id: usize,
+        /// The bin ID.
+        bin_id: usize,
+        /// The chunk.
+        chunk: Chunk,
+    },
+    /// A record in a bin chunk does not overlap the bin.
+    RecordOutsideBin {
+        /// The reference sequence ID.
+        reference_sequence_id: usize,
+        /// The bin ID.
+        bin_id: usize,
+        /// The position of the record.
+        record_position: bgzf::VirtualPosition,
+    },
+    /// The linear index offset of a position is after a record that covers it.
+    InvalidLinearOffset {
+        /// The reference sequence ID.
+        reference_sequence_id: usize,
+        /// The queried start position.
+        position: Position,
+        /// The minimum offset given by the index.
+        min_offset: bgzf::VirtualPosition,
+        /// The position of the record.
+        record_position: bgzf::VirtualPosition,
+    },
+    /// The mapped record count in the metadata does not match the file.
+    MappedRecordCountMismatch {
+        /// The reference sequence ID.
+        reference_sequence_id: usize,
+        /// The count in the index.
+        expected: u64,
+        /// The count in the file.
+        actual: u64,
+    },
+    /// The unmapped record count in the metadata does not match the file.
+    UnmappedRecordCountMismatch {
+        /// The reference sequence ID.
+        reference_sequence_id: usize,
+        /// The count in the index.
+        expected: u64,
+        /// The count in the file.
+        actual: u64,
+    },
+    /// The unplaced, unmapped record count does not match the file.
+    UnplacedUnmappedRecordCountMismatch {
+        /// The count in the index.
+        expected: u64,
+        /// The count in the file.
+        actual: u64,
+    },
+    /// The index was last modified before the file.
+    StaleIndex {
+        /// The modification time of the file.
+        data_modified: SystemTime,
+        /// The modification time of the index.
+        index_modified: SystemTime,
+    },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidChunk {
+                reference_sequence_id,
+                bin_id,
+                chunk,
+            } => write!(
+                f,
+                "reference sequence {reference_sequence_id}, bin {bin_id}: invalid chunk {:?}-{:?}",
+                chunk.start(),
+                chunk.end()
+            ),
+            Self::RecordOutsideBin {
+                reference_sequence_id,
+                bin_id,
+                record_position,
+            } => write!(
+                f,
+                "reference sequence {reference_sequence_id}, bin {bin_id}: record at {record_position:?} does not overlap bin"
+            ),
+            Self::InvalidLinearOffset {
+                reference_sequence_id,
+                position,
+                min_offset,
+                record_position,
+            } => write!(
+                f,
+                "reference sequence {reference_sequence_id}: min offset {min_offset:?} at {position} is after record at {record_position:?}"
+            ),
+            Self::MappedRecordCountMismatch {
+                reference_sequence_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "reference sequence {reference_sequence_id}: mapped record count mismatch: expected {expected}, got {actual}"
+            ),
+            Self::UnmappedRecordCountMismatch {
+                reference_sequence_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "reference sequence {reference_sequence_id}: unmapped record count mismatch: expected {expected}, got {actual}"
+            ),
+            Self::UnplacedUnmappedRecordCountMismatch { expected, actual } => write!(
+                f,
+                "unplaced, unmapped record count mismatch: expected {expected}, got {actual}"
+            ),
+            Self::StaleIndex { .. } => write!(f, "index is older than the file"),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::io;
+
+use noodles_bgzf as bgzf;
+
+use super::{AlignmentContext, RecordReader};
+use crate::{
+    binning_index::index::Header,
+    io::{IndexedRecord, IndexedRecords},
+};
+
+/// A record reader for tabix-indexed text files.
+///
+/// Records are parsed using the columns described by the tabix header. Reference sequence names
+/// are resolved using the header reference sequence names, and all records are considered mapped.
+///
+/// When the header has no end position column, the end position of a record is its start
+/// position. This may be shorter than the length used by the indexer, e.g., for VCF records, but
+/// never longer.
+pub struct TextRecordReader<'h, R> {
+    inner: R,
+    header: &'h Header,
+}
+
+impl<'h, R> TextRecordReader<'h, R> {
+    /// Creates a text record reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::binning_index::{index::Header, verifier::TextRecordReader};
+    ///
+    /// let header = Header::default();
+    /// let reader = TextRecordReader::new(bgzf::Reader::new(io::empty()), &header);
+    /// ```
+    pub fn new(inner: R, header: &'h Header) -> Self {
+        Self { inner, header }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<'h, R> RecordReader for TextRecordReader<'h, R>
+where
+    R: bgzf::io::BufRead + bgzf::io::Seek,
+{
+    fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<()> {
+        self.inner.seek_to_virtual_position(pos)?;
+
+        // A BGZF reader does not move when seeking past the end of the stream.
+        if self.inner.virtual_position() < pos {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "invalid virtual position",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+
+    fn read_alignment_context(&mut self) -> io::Result<Option<AlignmentContext>> {
+        let Some(record) = IndexedRecords::new(&mut self.inner, self.header)
+            .next()
+            .transpose()?
+        else {
+            return Ok(None);
+        };
+
+        let Some(reference_sequence_id) = self
+            .header
+            .reference_sequence_names()
+            .get_index_of(record.indexed_reference_sequence_name())
+        else {
+            return Ok(Some(None));
+        };
+
+        let start = record.indexed_start_position();
+
+        let end = if self.header.end_position_index().is_some() {
+            record.indexed_end_position()
+        } else {
+            start
+        };
+
+        Ok(Some(Some((reference_sequence_id, start, end, true))))
+    }
+}
@@ -0,0 +1,22 @@
+//! Validates and prints all records in a PAF file.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader},
+};
+
+use noodles_paf as paf;
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = File::open(src).map(BufReader::new).map(paf::Reader::new)?;
+
+    for result in reader.records() {
+        let record = result?;
+        println!("{record}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+#![warn(missing_docs)]
+
+//! **noodles-paf** handles the reading and writing of the Pairwise mApping Format (PAF).
+
+mod reader;
+pub mod record;
+mod writer;
+
+pub use self::{reader::Reader, record::Record, writer::Writer};
@@ -0,0 +1,175 @@
+use std::{
+    io::{self, BufRead},
+    iter,
+};
+
+use super::Record;
+
+/// A PAF reader.
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: BufRead,
+{
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let data = [];
+    /// let reader = paf::Reader::new(&data[..]);
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let data = [];
+    /// let mut reader = paf::Reader::new(&data[..]);
+    /// assert!(reader.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let data = [];
+    /// let reader = paf::Reader::new(&data[..]);
+    /// assert!(reader.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Creates a PAF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let data = [];
+    /// let reader = paf::Reader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Reads a raw PAF line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_paf as paf;
+    ///
+    /// let data = b"q0\t8\t0\t8\t+\tt0\t13\t2\t10\t8\t8\t60\n";
+    /// let mut reader = paf::Reader::new(&data[..]);
+    ///
+    /// let mut buf = String::new();
+    /// reader.read_line(&mut buf)?;
+    ///
+    /// assert_eq!(buf, "q0\t8\t0\t8\t+\tt0\t13\t2\t10\t8\t8\t60");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        read_line(&mut self.inner, buf)
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_paf as paf;
+    ///
+    /// let data = b"q0\t8\t0\t8\t+\tt0\t13\t2\t10\t8\t8\t60\ttp:A:P\n";
+    /// let mut reader = paf::Reader::new(&data[..]);
+    ///
+    /// let mut records = reader.records();
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(record.map(|r| r.query_sequence_length()), Some(8));
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn records(&mut self) -> impl Iterator<Item = io::Result<Record>> + '_ {
+        let mut buf = String::new();
+
+        iter::from_fn(move || {
+            buf.clear();
+
+            match self.read_line(&mut buf) {
+                Ok(0) => None,
+                Ok(_) => Some(
+                    buf.parse()
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                ),
+                Err(e) => Some(Err(e)),
+            }
+        })
+    }
+}
+
+fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
+where
+    R: BufRead,
+{
+    const LINE_FEED: char = '\n';
+    const CARRIAGE_RETURN: char = '\r';
+
+    match reader.read_line(buf) {
+        Ok(0) => Ok(0),
+        Ok(n) => {
+            if buf.ends_with(LINE_FEED) {
+                buf.pop();
+
+                if buf.ends_with(CARRIAGE_RETURN) {
+                    buf.pop();
+                }
+            }
+
+            Ok(n)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_line() -> io::Result<()> {
+        fn t(buf: &mut String, mut reader: &[u8], expected: &str) -> io::Result<()> {
+            buf.clear();
+            read_line(&mut reader, buf)?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        let mut buf = String::new();
+
+        t(&mut buf, b"noodles\n", "noodles")?;
+        t(&mut buf, b"noodles\r\n", "noodles")?;
+        t(&mut buf, b"noodles", "noodles")?;
+
+        Ok(())
+    }
+}
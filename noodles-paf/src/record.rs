@@ -0,0 +1,617 @@
+//! PAF record and fields.
+
+pub mod builder;
+mod convert;
+pub mod strand;
+
+pub use self::{builder::Builder, strand::Strand};
+
+use std::{error, fmt, num, str::FromStr};
+
+use noodles_core::Position;
+use noodles_sam::{
+    alignment::{
+        record::{data::field::Tag, MappingQuality},
+        record_buf::{data::field::Value, Data},
+    },
+    io::writer::record::write_field,
+};
+
+const FIELD_DELIMITER: char = '\t';
+const MISSING_MAPPING_QUALITY: u8 = 255;
+
+/// A PAF record.
+///
+/// Start and end positions are 1-based and inclusive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    query_sequence_name: String,
+    query_sequence_length: usize,
+    query_start: Position,
+    query_end: Position,
+    strand: Strand,
+    target_sequence_name: String,
+    target_sequence_length: usize,
+    target_start: Position,
+    target_end: Position,
+    matches: usize,
+    alignment_block_length: usize,
+    mapping_quality: Option<MappingQuality>,
+    data: Data,
+}
+
+impl Record {
+    /// Returns a builder to create a record from each of its fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let builder = paf::Record::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the query sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert!(record.query_sequence_name().is_empty());
+    /// ```
+    pub fn query_sequence_name(&self) -> &str {
+        &self.query_sequence_name
+    }
+
+    /// Returns the query sequence length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert_eq!(record.query_sequence_length(), 0);
+    /// ```
+    pub fn query_sequence_length(&self) -> usize {
+        self.query_sequence_length
+    }
+
+    /// Returns the query start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert_eq!(record.query_start(), Position::MIN);
+    /// ```
+    pub fn query_start(&self) -> Position {
+        self.query_start
+    }
+
+    /// Returns the query end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert_eq!(record.query_end(), Position::MIN);
+    /// ```
+    pub fn query_end(&self) -> Position {
+        self.query_end
+    }
+
+    /// Returns the strand of the query relative to the target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf::{self as paf, record::Strand};
+    /// let record = paf::Record::default();
+    /// assert_eq!(record.strand(), Strand::Forward);
+    /// ```
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the target sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert!(record.target_sequence_name().is_empty());
+    /// ```
+    pub fn target_sequence_name(&self) -> &str {
+        &self.target_sequence_name
+    }
+
+    /// Returns the target sequence length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert_eq!(record.target_sequence_length(), 0);
+    /// ```
+    pub fn target_sequence_length(&self) -> usize {
+        self.target_sequence_length
+    }
+
+    /// Returns the target start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert_eq!(record.target_start(), Position::MIN);
+    /// ```
+    pub fn target_start(&self) -> Position {
+        self.target_start
+    }
+
+    /// Returns the target end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert_eq!(record.target_end(), Position::MIN);
+    /// ```
+    pub fn target_end(&self) -> Position {
+        self.target_end
+    }
+
+    /// Returns the number of residue matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert_eq!(record.matches(), 0);
+    /// ```
+    pub fn matches(&self) -> usize {
+        self.matches
+    }
+
+    /// Returns the alignment block length.
+    ///
+    /// This is the number of bases, including gaps, in the alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert_eq!(record.alignment_block_length(), 0);
+    /// ```
+    pub fn alignment_block_length(&self) -> usize {
+        self.alignment_block_length
+    }
+
+    /// Returns the mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert!(record.mapping_quality().is_none());
+    /// ```
+    pub fn mapping_quality(&self) -> Option<MappingQuality> {
+        self.mapping_quality
+    }
+
+    /// Returns the optional fields.
+    ///
+    /// These are SAM-style typed tag-value pairs, e.g., `tp:A:P`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::default();
+    /// assert!(record.data().is_empty());
+    /// ```
+    pub fn data(&self) -> &Data {
+        &self.data
+    }
+
+    /// Returns a mutable reference to the optional fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// use noodles_sam::alignment::{
+    ///     record::data::field::Tag,
+    ///     record_buf::data::field::Value,
+    /// };
+    ///
+    /// let mut record = paf::Record::default();
+    /// record.data_mut().insert(Tag::ALIGNMENT_HIT_COUNT, Value::from(1));
+    /// assert_eq!(record.data().len(), 1);
+    /// ```
+    pub fn data_mut(&mut self) -> &mut Data {
+        &mut self.data
+    }
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        Builder::default().build_unchecked()
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t",
+            self.query_sequence_name(),
+            self.query_sequence_length(),
+            usize::from(self.query_start()) - 1,
+            self.query_end(),
+            self.strand(),
+            self.target_sequence_name(),
+            self.target_sequence_length(),
+            usize::from(self.target_start()) - 1,
+            self.target_end(),
+            self.matches(),
+            self.alignment_block_length(),
+        )?;
+
+        match self.mapping_quality() {
+            Some(mapping_quality) => write!(f, "{}", u8::from(mapping_quality))?,
+            None => write!(f, "{MISSING_MAPPING_QUALITY}")?,
+        }
+
+        let mut buf = Vec::new();
+
+        for (tag, value) in self.data().iter() {
+            buf.clear();
+            write_field(&mut buf, tag, &value.into()).map_err(|_| fmt::Error)?;
+            let s = std::str::from_utf8(&buf).map_err(|_| fmt::Error)?;
+            write!(f, "{FIELD_DELIMITER}{s}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when a raw PAF record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The query sequence name is missing.
+    MissingQuerySequenceName,
+    /// The query sequence length is missing.
+    MissingQuerySequenceLength,
+    /// The query sequence length is invalid.
+    InvalidQuerySequenceLength(num::ParseIntError),
+    /// The query start is missing.
+    MissingQueryStart,
+    /// The query start is invalid.
+    InvalidQueryStart,
+    /// The query end is missing.
+    MissingQueryEnd,
+    /// The query end is invalid.
+    InvalidQueryEnd,
+    /// The strand is missing.
+    MissingStrand,
+    /// The strand is invalid.
+    InvalidStrand(strand::ParseError),
+    /// The target sequence name is missing.
+    MissingTargetSequenceName,
+    /// The target sequence length is missing.
+    MissingTargetSequenceLength,
+    /// The target sequence length is invalid.
+    InvalidTargetSequenceLength(num::ParseIntError),
+    /// The target start is missing.
+    MissingTargetStart,
+    /// The target start is invalid.
+    InvalidTargetStart,
+    /// The target end is missing.
+    MissingTargetEnd,
+    /// The target end is invalid.
+    InvalidTargetEnd,
+    /// The number of residue matches is missing.
+    MissingMatches,
+    /// The number of residue matches is invalid.
+    InvalidMatches(num::ParseIntError),
+    /// The alignment block length is missing.
+    MissingAlignmentBlockLength,
+    /// The alignment block length is invalid.
+    InvalidAlignmentBlockLength(num::ParseIntError),
+    /// The mapping quality is missing.
+    MissingMappingQuality,
+    /// The mapping quality is invalid.
+    InvalidMappingQuality(num::ParseIntError),
+    /// An optional field is invalid.
+    InvalidField,
+    /// An optional field tag is duplicated.
+    DuplicateTag(Tag),
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidQuerySequenceLength(e)
+            | Self::InvalidTargetSequenceLength(e)
+            | Self::InvalidMatches(e)
+            | Self::InvalidAlignmentBlockLength(e)
+            | Self::InvalidMappingQuality(e) => Some(e),
+            Self::InvalidStrand(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::MissingQuerySequenceName => f.write_str("missing query sequence name"),
+            Self::MissingQuerySequenceLength => f.write_str("missing query sequence length"),
+            Self::InvalidQuerySequenceLength(_) => f.write_str("invalid query sequence length"),
+            Self::MissingQueryStart => f.write_str("missing query start"),
+            Self::InvalidQueryStart => f.write_str("invalid query start"),
+            Self::MissingQueryEnd => f.write_str("missing query end"),
+            Self::InvalidQueryEnd => f.write_str("invalid query end"),
+            Self::MissingStrand => f.write_str("missing strand"),
+            Self::InvalidStrand(_) => f.write_str("invalid strand"),
+            Self::MissingTargetSequenceName => f.write_str("missing target sequence name"),
+            Self::MissingTargetSequenceLength => f.write_str("missing target sequence length"),
+            Self::InvalidTargetSequenceLength(_) => f.write_str("invalid target sequence length"),
+            Self::MissingTargetStart => f.write_str("missing target start"),
+            Self::InvalidTargetStart => f.write_str("invalid target start"),
+            Self::MissingTargetEnd => f.write_str("missing target end"),
+            Self::InvalidTargetEnd => f.write_str("invalid target end"),
+            Self::MissingMatches => f.write_str("missing matches"),
+            Self::InvalidMatches(_) => f.write_str("invalid matches"),
+            Self::MissingAlignmentBlockLength => f.write_str("missing alignment block length"),
+            Self::InvalidAlignmentBlockLength(_) => f.write_str("invalid alignment block length"),
+            Self::MissingMappingQuality => f.write_str("missing mapping quality"),
+            Self::InvalidMappingQuality(_) => f.write_str("invalid mapping quality"),
+            Self::InvalidField => f.write_str("invalid field"),
+            Self::DuplicateTag(tag) => write!(f, "duplicate tag: {tag:?}"),
+        }
+    }
+}
+
+impl FromStr for Record {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut fields = s.split(FIELD_DELIMITER);
+
+        let query_sequence_name = fields
+            .next()
+            .map(String::from)
+            .ok_or(ParseError::MissingQuerySequenceName)?;
+
+        let query_sequence_length = fields
+            .next()
+            .ok_or(ParseError::MissingQuerySequenceLength)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidQuerySequenceLength))?;
+
+        let query_start = fields
+            .next()
+            .ok_or(ParseError::MissingQueryStart)
+            .and_then(|s| parse_start(s).ok_or(ParseError::InvalidQueryStart))?;
+
+        let query_end = fields
+            .next()
+            .ok_or(ParseError::MissingQueryEnd)
+            .and_then(|s| {
+                parse_end(s)
+                    .filter(|end| *end >= query_start)
+                    .ok_or(ParseError::InvalidQueryEnd)
+            })?;
+
+        let strand = fields
+            .next()
+            .ok_or(ParseError::MissingStrand)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidStrand))?;
+
+        let target_sequence_name = fields
+            .next()
+            .map(String::from)
+            .ok_or(ParseError::MissingTargetSequenceName)?;
+
+        let target_sequence_length = fields
+            .next()
+            .ok_or(ParseError::MissingTargetSequenceLength)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidTargetSequenceLength))?;
+
+        let target_start = fields
+            .next()
+            .ok_or(ParseError::MissingTargetStart)
+            .and_then(|s| parse_start(s).ok_or(ParseError::InvalidTargetStart))?;
+
+        let target_end = fields
+            .next()
+            .ok_or(ParseError::MissingTargetEnd)
+            .and_then(|s| {
+                parse_end(s)
+                    .filter(|end| *end >= target_start)
+                    .ok_or(ParseError::InvalidTargetEnd)
+            })?;
+
+        let matches = fields
+            .next()
+            .ok_or(ParseError::MissingMatches)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidMatches))?;
+
+        let alignment_block_length = fields
+            .next()
+            .ok_or(ParseError::MissingAlignmentBlockLength)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidAlignmentBlockLength))?;
+
+        let mapping_quality = fields
+            .next()
+            .ok_or(ParseError::MissingMappingQuality)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidMappingQuality))
+            .map(MappingQuality::new)?;
+
+        let data = parse_data(fields)?;
+
+        Ok(Self {
+            query_sequence_name,
+            query_sequence_length,
+            query_start,
+            query_end,
+            strand,
+            target_sequence_name,
+            target_sequence_length,
+            target_start,
+            target_end,
+            matches,
+            alignment_block_length,
+            mapping_quality,
+            data,
+        })
+    }
+}
+
+// Converts a 0-based start position to a 1-based position.
+fn parse_start(s: &str) -> Option<Position> {
+    s.parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_add(1))
+        .and_then(Position::new)
+}
+
+fn parse_end(s: &str) -> Option<Position> {
+    s.parse().ok().and_then(Position::new)
+}
+
+fn parse_data<'a, I>(fields: I) -> Result<Data, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    use noodles_sam::record::data::field::parse_field;
+
+    let mut data = Data::default();
+
+    for field in fields {
+        let mut src = field.as_bytes();
+
+        let (tag, value) = parse_field(&mut src).map_err(|_| ParseError::InvalidField)?;
+
+        if !src.is_empty() {
+            return Err(ParseError::InvalidField);
+        }
+
+        let value = Value::try_from(value).map_err(|_| ParseError::InvalidField)?;
+
+        if data.insert(tag, value).is_some() {
+            return Err(ParseError::DuplicateTag(tag));
+        }
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE: &str = "q0\t8\t1\t7\t-\tt0\t13\t3\t9\t5\t6\t60\ttp:A:P\tNM:i:1\tcg:Z:6M";
+
+    fn build_record() -> Result<Record, Box<dyn std::error::Error>> {
+        Ok(Record::builder()
+            .set_query_sequence_name("q0")
+            .set_query_sequence_length(8)
+            .set_query_start(Position::try_from(2)?)
+            .set_query_end(Position::try_from(7)?)
+            .set_strand(Strand::Reverse)
+            .set_target_sequence_name("t0")
+            .set_target_sequence_length(13)
+            .set_target_start(Position::try_from(4)?)
+            .set_target_end(Position::try_from(9)?)
+            .set_matches(5)
+            .set_alignment_block_length(6)
+            .set_mapping_quality(MappingQuality::new(60).ok_or("invalid mapping quality")?)
+            .set_data(
+                [
+                    (Tag::new(b't', b'p'), Value::Character(b'P')),
+                    (Tag::EDIT_DISTANCE, Value::Int32(1)),
+                    (Tag::new(b'c', b'g'), Value::from("6M")),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .build()?)
+    }
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let record = build_record()?;
+        assert_eq!(record.to_string(), LINE);
+
+        let record = Record::default();
+        assert_eq!(record.to_string(), "\t0\t0\t1\t+\t\t0\t0\t1\t0\t0\t255");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(LINE.parse::<Record>()?, build_record()?);
+
+        let record: Record = "q0\t8\t0\t8\t+\tt0\t13\t0\t8\t8\t8\t255".parse()?;
+        assert!(record.mapping_quality().is_none());
+        assert!(record.data().is_empty());
+
+        assert_eq!("".parse::<Record>(), Err(ParseError::Empty));
+        assert_eq!(
+            "q0\t8\t0".parse::<Record>(),
+            Err(ParseError::MissingQueryEnd)
+        );
+        assert_eq!(
+            "q0\t8\t0\t0\t+\tt0\t13\t0\t8\t8\t8\t255".parse::<Record>(),
+            Err(ParseError::InvalidQueryEnd)
+        );
+        assert_eq!(
+            "q0\t8\t5\t2\t+\tt0\t13\t0\t8\t8\t8\t255".parse::<Record>(),
+            Err(ParseError::InvalidQueryEnd)
+        );
+        assert_eq!(
+            "q0\t8\t0\t8\t+\tt0\t13\t8\t8\t8\t8\t255".parse::<Record>(),
+            Err(ParseError::InvalidTargetEnd)
+        );
+        assert_eq!(
+            "q0\t8\t0\t8\t.\tt0\t13\t0\t8\t8\t8\t255".parse::<Record>(),
+            Err(ParseError::InvalidStrand(strand::ParseError::Invalid))
+        );
+        assert_eq!(
+            "q0\t8\t0\t8\t+\tt0\t13\t0\t8\t8\t8\t255\tNM:i".parse::<Record>(),
+            Err(ParseError::InvalidField)
+        );
+        assert_eq!(
+            "q0\t8\t0\t8\t+\tt0\t13\t0\t8\t8\t8\t255\tNM:i:0\tNM:i:1".parse::<Record>(),
+            Err(ParseError::DuplicateTag(Tag::EDIT_DISTANCE))
+        );
+
+        Ok(())
+    }
+}
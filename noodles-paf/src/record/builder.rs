@@ -0,0 +1,359 @@
+//! PAF record builder.
+
+use std::{error, fmt};
+
+use noodles_core::Position;
+use noodles_sam::alignment::{record::MappingQuality, record_buf::Data};
+
+use super::{Record, Strand};
+
+/// An error returned when a PAF record fails to build.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BuildError {
+    /// The query start is greater than the query end.
+    InvalidQueryInterval,
+    /// The target start is greater than the target end.
+    InvalidTargetInterval,
+}
+
+impl error::Error for BuildError {}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidQueryInterval => f.write_str("invalid query interval"),
+            Self::InvalidTargetInterval => f.write_str("invalid target interval"),
+        }
+    }
+}
+
+/// A PAF record builder.
+#[derive(Debug)]
+pub struct Builder {
+    query_sequence_name: String,
+    query_sequence_length: usize,
+    query_start: Position,
+    query_end: Position,
+    strand: Strand,
+    target_sequence_name: String,
+    target_sequence_length: usize,
+    target_start: Position,
+    target_end: Position,
+    matches: usize,
+    alignment_block_length: usize,
+    mapping_quality: Option<MappingQuality>,
+    data: Data,
+}
+
+impl Builder {
+    /// Sets the query sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::builder().set_query_sequence_name("q0").build()?;
+    /// assert_eq!(record.query_sequence_name(), "q0");
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn set_query_sequence_name<N>(mut self, query_sequence_name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.query_sequence_name = query_sequence_name.into();
+        self
+    }
+
+    /// Sets the query sequence length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::builder().set_query_sequence_length(8).build()?;
+    /// assert_eq!(record.query_sequence_length(), 8);
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn set_query_sequence_length(mut self, query_sequence_length: usize) -> Self {
+        self.query_sequence_length = query_sequence_length;
+        self
+    }
+
+    /// Sets the query start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_paf as paf;
+    ///
+    /// let record = paf::Record::builder()
+    ///     .set_query_start(Position::try_from(2)?)
+    ///     .set_query_end(Position::try_from(5)?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.query_start(), Position::try_from(2)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_query_start(mut self, query_start: Position) -> Self {
+        self.query_start = query_start;
+        self
+    }
+
+    /// Sets the query end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::builder().set_query_end(Position::try_from(5)?).build()?;
+    /// assert_eq!(record.query_end(), Position::try_from(5)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_query_end(mut self, query_end: Position) -> Self {
+        self.query_end = query_end;
+        self
+    }
+
+    /// Sets the strand of the query relative to the target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf::{self as paf, record::Strand};
+    /// let record = paf::Record::builder().set_strand(Strand::Reverse).build()?;
+    /// assert_eq!(record.strand(), Strand::Reverse);
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn set_strand(mut self, strand: Strand) -> Self {
+        self.strand = strand;
+        self
+    }
+
+    /// Sets the target sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::builder().set_target_sequence_name("t0").build()?;
+    /// assert_eq!(record.target_sequence_name(), "t0");
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn set_target_sequence_name<N>(mut self, target_sequence_name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.target_sequence_name = target_sequence_name.into();
+        self
+    }
+
+    /// Sets the target sequence length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::builder().set_target_sequence_length(13).build()?;
+    /// assert_eq!(record.target_sequence_length(), 13);
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn set_target_sequence_length(mut self, target_sequence_length: usize) -> Self {
+        self.target_sequence_length = target_sequence_length;
+        self
+    }
+
+    /// Sets the target start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_paf as paf;
+    ///
+    /// let record = paf::Record::builder()
+    ///     .set_target_start(Position::try_from(8)?)
+    ///     .set_target_end(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.target_start(), Position::try_from(8)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_target_start(mut self, target_start: Position) -> Self {
+        self.target_start = target_start;
+        self
+    }
+
+    /// Sets the target end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::builder().set_target_end(Position::try_from(13)?).build()?;
+    /// assert_eq!(record.target_end(), Position::try_from(13)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_target_end(mut self, target_end: Position) -> Self {
+        self.target_end = target_end;
+        self
+    }
+
+    /// Sets the number of residue matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::builder().set_matches(5).build()?;
+    /// assert_eq!(record.matches(), 5);
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn set_matches(mut self, matches: usize) -> Self {
+        self.matches = matches;
+        self
+    }
+
+    /// Sets the alignment block length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::builder().set_alignment_block_length(6).build()?;
+    /// assert_eq!(record.alignment_block_length(), 6);
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn set_alignment_block_length(mut self, alignment_block_length: usize) -> Self {
+        self.alignment_block_length = alignment_block_length;
+        self
+    }
+
+    /// Sets the mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// use noodles_sam::alignment::record::MappingQuality;
+    /// let record = paf::Record::builder().set_mapping_quality(MappingQuality::MIN).build()?;
+    /// assert_eq!(record.mapping_quality(), Some(MappingQuality::MIN));
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn set_mapping_quality(mut self, mapping_quality: MappingQuality) -> Self {
+        self.mapping_quality = Some(mapping_quality);
+        self
+    }
+
+    /// Sets the optional fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// use noodles_sam::alignment::{
+    ///     record::data::field::Tag,
+    ///     record_buf::{data::field::Value, Data},
+    /// };
+    ///
+    /// let data: Data = [(Tag::ALIGNMENT_HIT_COUNT, Value::from(1))]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let record = paf::Record::builder().set_data(data.clone()).build()?;
+    ///
+    /// assert_eq!(record.data(), &data);
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn set_data(mut self, data: Data) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Builds a PAF record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let record = paf::Record::builder().build()?;
+    /// # Ok::<_, noodles_paf::record::builder::BuildError>(())
+    /// ```
+    pub fn build(self) -> Result<Record, BuildError> {
+        if self.query_start > self.query_end {
+            return Err(BuildError::InvalidQueryInterval);
+        }
+
+        if self.target_start > self.target_end {
+            return Err(BuildError::InvalidTargetInterval);
+        }
+
+        Ok(self.build_unchecked())
+    }
+
+    pub(super) fn build_unchecked(self) -> Record {
+        Record {
+            query_sequence_name: self.query_sequence_name,
+            query_sequence_length: self.query_sequence_length,
+            query_start: self.query_start,
+            query_end: self.query_end,
+            strand: self.strand,
+            target_sequence_name: self.target_sequence_name,
+            target_sequence_length: self.target_sequence_length,
+            target_start: self.target_start,
+            target_end: self.target_end,
+            matches: self.matches,
+            alignment_block_length: self.alignment_block_length,
+            mapping_quality: self.mapping_quality,
+            data: self.data,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            query_sequence_name: String::new(),
+            query_sequence_length: 0,
+            query_start: Position::MIN,
+            query_end: Position::MIN,
+            strand: Strand::Forward,
+            target_sequence_name: String::new(),
+            target_sequence_length: 0,
+            target_start: Position::MIN,
+            target_end: Position::MIN,
+            matches: 0,
+            alignment_block_length: 0,
+            mapping_quality: None,
+            data: Data::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        assert!(Builder::default().build().is_ok());
+
+        assert_eq!(
+            Builder::default()
+                .set_query_start(Position::try_from(2)?)
+                .build(),
+            Err(BuildError::InvalidQueryInterval)
+        );
+
+        assert_eq!(
+            Builder::default()
+                .set_target_start(Position::try_from(2)?)
+                .build(),
+            Err(BuildError::InvalidTargetInterval)
+        );
+
+        Ok(())
+    }
+}
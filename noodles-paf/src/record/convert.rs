@@ -0,0 +1,516 @@
+use std::{io, str};
+
+use noodles_core::Position;
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        record::{
+            cigar::{op::Kind, Op},
+            data::field::Tag,
+            Flags,
+        },
+        record_buf::{data::field::Value as ValueBuf, Cigar, Data},
+        RecordBuf,
+    },
+    io::writer::record::write_cigar,
+    record::parse_cigar,
+};
+
+use super::{Record, Strand};
+
+// The `cg` field, the alignment CIGAR excluding clipping.
+const CIGAR: Tag = Tag::new(b'c', b'g');
+
+const MISSING_NAME: &str = "*";
+
+impl Record {
+    /// Converts an alignment record to a PAF record.
+    ///
+    /// The query is the read, and the target is the reference sequence. The alignment CIGAR,
+    /// without clipping, is stored in the `cg:Z` field.
+    ///
+    /// The number of residue matches is taken from sequence match operations (`=`), if the CIGAR
+    /// has any; otherwise, it is derived from the edit distance (`NM`), if present; otherwise, it
+    /// is the number of alignment match operations (`M`).
+    ///
+    /// This returns an error if the record is unmapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_core::Position;
+    /// use noodles_paf::{self as paf, record::Strand};
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::{
+    ///         record::{
+    ///             cigar::{op::Kind, Op},
+    ///             Flags,
+    ///         },
+    ///         RecordBuf,
+    ///     },
+    ///     header::record::value::{map::ReferenceSequence, Map},
+    /// };
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence(
+    ///         "sq0",
+    ///         Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+    ///     )
+    ///     .build();
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_name(b"r0".into())
+    ///     .set_flags(Flags::empty())
+    ///     .set_reference_sequence_id(0)
+    ///     .set_alignment_start(Position::try_from(3)?)
+    ///     .set_cigar([Op::new(Kind::SoftClip, 2), Op::new(Kind::Match, 4)].into_iter().collect())
+    ///     .build();
+    ///
+    /// let paf_record = paf::Record::try_from_alignment_record(&header, &record)?;
+    ///
+    /// assert_eq!(
+    ///     paf_record.to_string(),
+    ///     "r0\t6\t2\t6\t+\tsq0\t13\t2\t6\t4\t4\t255\tcg:Z:4M"
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_alignment_record<R>(header: &sam::Header, record: &R) -> io::Result<Self>
+    where
+        R: sam::alignment::Record + ?Sized,
+    {
+        let flags = record.flags()?;
+
+        if flags.is_unmapped() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record is unmapped",
+            ));
+        }
+
+        let query_sequence_name = match record.name() {
+            Some(name) => str::from_utf8(name.as_bytes())
+                .map(String::from)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            None => MISSING_NAME.into(),
+        };
+
+        let (target_sequence_name, reference_sequence) = record
+            .reference_sequence(header)
+            .transpose()?
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "missing reference sequence")
+            })?;
+
+        let target_sequence_name = str::from_utf8(target_sequence_name)
+            .map(String::from)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let target_start = record
+            .alignment_start()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing alignment start"))?;
+
+        let target_end = record
+            .alignment_end()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing alignment end"))?;
+
+        let ops: Vec<_> = record.cigar().iter().collect::<io::Result<_>>()?;
+
+        let leading_clip_len = clip_len(ops.iter());
+        let trailing_clip_len = clip_len(ops.iter().rev());
+
+        let query_sequence_length: usize = ops
+            .iter()
+            .filter(|op| op.kind().consumes_read() || op.kind() == Kind::HardClip)
+            .map(|op| op.len())
+            .sum();
+
+        let (strand, start_clip_len, end_clip_len) = if flags.is_reverse_complemented() {
+            (Strand::Reverse, trailing_clip_len, leading_clip_len)
+        } else {
+            (Strand::Forward, leading_clip_len, trailing_clip_len)
+        };
+
+        let query_start = Position::new(start_clip_len + 1).ok_or_else(invalid_query_range)?;
+        let query_end = query_sequence_length
+            .checked_sub(end_clip_len)
+            .and_then(Position::new)
+            .ok_or_else(invalid_query_range)?;
+
+        let alignment_ops: Cigar = ops.into_iter().filter(|op| !is_clip(op)).collect();
+
+        let matches = count_matches(record, &alignment_ops)?;
+
+        let alignment_block_length = alignment_ops
+            .as_ref()
+            .iter()
+            .filter(|op| op.kind() != Kind::Skip && op.kind() != Kind::Pad)
+            .map(|op| op.len())
+            .sum();
+
+        let mut data = Data::default();
+
+        for result in record.data().iter() {
+            let (tag, value) = result?;
+            data.insert(tag, value.try_into()?);
+        }
+
+        let mut buf = Vec::new();
+        write_cigar(&mut buf, &alignment_ops)?;
+        let cigar =
+            String::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        data.insert(CIGAR, ValueBuf::String(cigar.into()));
+
+        let mut builder = Self::builder()
+            .set_query_sequence_name(query_sequence_name)
+            .set_query_sequence_length(query_sequence_length)
+            .set_query_start(query_start)
+            .set_query_end(query_end)
+            .set_strand(strand)
+            .set_target_sequence_name(target_sequence_name)
+            .set_target_sequence_length(usize::from(reference_sequence.length()))
+            .set_target_start(target_start)
+            .set_target_end(target_end)
+            .set_matches(matches)
+            .set_alignment_block_length(alignment_block_length)
+            .set_data(data);
+
+        if let Some(mapping_quality) = record.mapping_quality().transpose()? {
+            builder = builder.set_mapping_quality(mapping_quality);
+        }
+
+        builder
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Converts this PAF record to an alignment record.
+    ///
+    /// The record must have an alignment CIGAR (`cg:Z`). Unaligned query bases are soft clipped.
+    /// The target sequence name must be a reference sequence in the header. All other optional
+    /// fields are copied to the alignment record data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_core::Position;
+    /// use noodles_paf as paf;
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::record::{
+    ///         cigar::{op::Kind, Op},
+    ///         Flags,
+    ///     },
+    ///     header::record::value::{map::ReferenceSequence, Map},
+    /// };
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence(
+    ///         "sq0",
+    ///         Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+    ///     )
+    ///     .build();
+    ///
+    /// let record: paf::Record = "r0\t6\t2\t6\t-\tsq0\t13\t2\t6\t4\t4\t60\tcg:Z:4M".parse()?;
+    /// let alignment_record = record.try_into_alignment_record(&header)?;
+    ///
+    /// assert_eq!(alignment_record.flags(), Flags::REVERSE_COMPLEMENTED);
+    /// assert_eq!(alignment_record.reference_sequence_id(), Some(0));
+    /// assert_eq!(alignment_record.alignment_start(), Position::new(3));
+    /// assert_eq!(
+    ///     alignment_record.cigar().as_ref(),
+    ///     [Op::new(Kind::Match, 4), Op::new(Kind::SoftClip, 2)]
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_into_alignment_record(&self, header: &sam::Header) -> io::Result<RecordBuf> {
+        let cigar = match self.data().get(&CIGAR) {
+            Some(ValueBuf::String(s)) => parse_cigar(s)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid cg field"))?,
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid cg field type",
+                ))
+            }
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "missing cg field",
+                ))
+            }
+        };
+
+        let query_span = span(self.query_start(), self.query_end())?;
+        let target_span = span(self.target_start(), self.target_end())?;
+
+        if cigar.read_length() != query_span || cigar.alignment_span() != target_span {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "cg field does not match the alignment coordinates",
+            ));
+        }
+
+        let reference_sequence_id = header
+            .reference_sequences()
+            .get_index_of(self.target_sequence_name().as_bytes())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "invalid target sequence name: {}",
+                        self.target_sequence_name()
+                    ),
+                )
+            })?;
+
+        let start_clip_len = usize::from(self.query_start()) - 1;
+        let end_clip_len = self
+            .query_sequence_length()
+            .checked_sub(usize::from(self.query_end()))
+            .ok_or_else(invalid_query_range)?;
+
+        let (flags, leading_clip_len, trailing_clip_len) = match self.strand() {
+            Strand::Forward => (Flags::empty(), start_clip_len, end_clip_len),
+            Strand::Reverse => (Flags::REVERSE_COMPLEMENTED, end_clip_len, start_clip_len),
+        };
+
+        let mut ops = Vec::new();
+
+        if leading_clip_len > 0 {
+            ops.push(Op::new(Kind::SoftClip, leading_clip_len));
+        }
+
+        ops.extend(cigar.as_ref().iter().copied());
+
+        if trailing_clip_len > 0 {
+            ops.push(Op::new(Kind::SoftClip, trailing_clip_len));
+        }
+
+        let data = self
+            .data()
+            .iter()
+            .filter(|(tag, _)| *tag != CIGAR)
+            .map(|(tag, value)| (tag, value.clone()))
+            .collect();
+
+        let mut builder = RecordBuf::builder()
+            .set_name(self.query_sequence_name().as_bytes().into())
+            .set_flags(flags)
+            .set_reference_sequence_id(reference_sequence_id)
+            .set_alignment_start(self.target_start())
+            .set_cigar(ops.into_iter().collect())
+            .set_data(data);
+
+        if let Some(mapping_quality) = self.mapping_quality() {
+            builder = builder.set_mapping_quality(mapping_quality);
+        }
+
+        Ok(builder.build())
+    }
+}
+
+fn span(start: Position, end: Position) -> io::Result<usize> {
+    usize::from(end)
+        .checked_sub(usize::from(start))
+        .map(|n| n + 1)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid interval"))
+}
+
+fn invalid_query_range() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid query range")
+}
+
+fn is_clip(op: &Op) -> bool {
+    matches!(op.kind(), Kind::SoftClip | Kind::HardClip)
+}
+
+fn clip_len<'a, I>(ops: I) -> usize
+where
+    I: Iterator<Item = &'a Op>,
+{
+    ops.take_while(|op| is_clip(op)).map(|op| op.len()).sum()
+}
+
+fn count_matches<R>(record: &R, cigar: &Cigar) -> io::Result<usize>
+where
+    R: sam::alignment::Record + ?Sized,
+{
+    let mut aligned_len = 0;
+    let mut sequence_match_len = 0;
+    let mut has_sequence_match_ops = false;
+    let mut gap_len = 0;
+
+    for op in cigar.as_ref() {
+        match op.kind() {
+            Kind::Match => aligned_len += op.len(),
+            Kind::SequenceMatch => {
+                has_sequence_match_ops = true;
+                sequence_match_len += op.len();
+            }
+            Kind::SequenceMismatch => has_sequence_match_ops = true,
+            Kind::Insertion | Kind::Deletion => gap_len += op.len(),
+            _ => {}
+        }
+    }
+
+    if has_sequence_match_ops {
+        return Ok(sequence_match_len);
+    }
+
+    let data = record.data();
+
+    let Some(value) = data.get(&Tag::EDIT_DISTANCE).transpose()? else {
+        return Ok(aligned_len);
+    };
+
+    let edit_distance = value
+        .as_int()
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid NM field"))?;
+
+    Ok((aligned_len + gap_len).saturating_sub(edit_distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_sam::{
+        alignment::record::MappingQuality,
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        Ok(sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+            )
+            .build())
+    }
+
+    #[test]
+    fn test_try_from_alignment_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let record = RecordBuf::builder()
+            .set_name(b"r0".into())
+            .set_flags(Flags::REVERSE_COMPLEMENTED)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(3)?)
+            .set_mapping_quality(MappingQuality::new(60).ok_or("invalid mapping quality")?)
+            .set_cigar(
+                [
+                    Op::new(Kind::HardClip, 1),
+                    Op::new(Kind::Match, 2),
+                    Op::new(Kind::Insertion, 1),
+                    Op::new(Kind::Match, 2),
+                    Op::new(Kind::Deletion, 1),
+                    Op::new(Kind::Match, 1),
+                    Op::new(Kind::SoftClip, 3),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .set_data(
+                [(Tag::EDIT_DISTANCE, ValueBuf::Int32(3))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build();
+
+        let actual = Record::try_from_alignment_record(&header, &record)?;
+
+        assert_eq!(
+            actual.to_string(),
+            "r0\t10\t3\t9\t-\tsq0\t13\t2\t8\t4\t7\t60\tNM:i:3\tcg:Z:2M1I2M1D1M"
+        );
+
+        let record = RecordBuf::default();
+        assert!(matches!(
+            Record::try_from_alignment_record(&header, &record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_into_alignment_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let record: Record =
+            "r0\t10\t3\t9\t-\tsq0\t13\t2\t8\t4\t7\t60\tNM:i:3\tcg:Z:2M1I2M1D1M".parse()?;
+        let actual = record.try_into_alignment_record(&header)?;
+
+        let expected = RecordBuf::builder()
+            .set_name(b"r0".into())
+            .set_flags(Flags::REVERSE_COMPLEMENTED)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(3)?)
+            .set_mapping_quality(MappingQuality::new(60).ok_or("invalid mapping quality")?)
+            .set_cigar(
+                [
+                    Op::new(Kind::SoftClip, 1),
+                    Op::new(Kind::Match, 2),
+                    Op::new(Kind::Insertion, 1),
+                    Op::new(Kind::Match, 2),
+                    Op::new(Kind::Deletion, 1),
+                    Op::new(Kind::Match, 1),
+                    Op::new(Kind::SoftClip, 3),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .set_data(
+                [(Tag::EDIT_DISTANCE, ValueBuf::Int32(3))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build();
+
+        assert_eq!(actual, expected);
+
+        let record: Record = "r0\t10\t3\t9\t-\tsq0\t13\t2\t8\t4\t7\t60".parse()?;
+        assert!(matches!(
+            record.try_into_alignment_record(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let record: Record = "r0\t10\t3\t9\t-\tsq0\t13\t2\t8\t4\t7\t60\tcg:Z:5M".parse()?;
+        assert!(matches!(
+            record.try_into_alignment_record(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let record: Record = "r0\t10\t3\t9\t-\tsq1\t13\t2\t8\t4\t7\t60\tcg:Z:2M1I2M1D1M".parse()?;
+        assert!(matches!(
+            record.try_into_alignment_record(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_span() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(span(Position::try_from(2)?, Position::try_from(7)?)?, 6);
+        assert_eq!(span(Position::MIN, Position::MIN)?, 1);
+
+        assert!(matches!(
+            span(Position::try_from(7)?, Position::try_from(2)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use std::io::{self, Write};
+
+use super::Record;
+
+/// A PAF writer.
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a PAF writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let writer = paf::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let writer = paf::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let mut writer = paf::Writer::new(Vec::new());
+    /// assert!(writer.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    /// let writer = paf::Writer::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes a PAF record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_paf as paf;
+    ///
+    /// let mut writer = paf::Writer::new(Vec::new());
+    ///
+    /// let record = paf::Record::default();
+    /// writer.write_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"\t0\t0\t1\t+\t\t0\t0\t1\t0\t0\t255\n");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        write_record(&mut self.inner, record)
+    }
+}
+
+fn write_record<W>(writer: &mut W, record: &Record) -> io::Result<()>
+where
+    W: Write,
+{
+    writeln!(writer, "{record}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_record() -> Result<(), Box<dyn std::error::Error>> {
+        const LINE: &str = "q0\t8\t0\t8\t+\tt0\t13\t2\t10\t8\t8\t60\ttp:A:P";
+
+        let mut buf = Vec::new();
+        let record: Record = LINE.parse()?;
+        write_record(&mut buf, &record)?;
+
+        let mut expected = LINE.as_bytes().to_vec();
+        expected.push(b'\n');
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 //! Alignment record.
 
+pub mod fixmate;
 pub mod io;
+pub mod lossy;
 pub mod record;
 pub mod record_buf;
 
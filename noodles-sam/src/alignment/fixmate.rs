@@ -0,0 +1,431 @@
+//! Mate information fixing.
+
+use std::{error, fmt, io};
+
+use bstr::BString;
+use noodles_core::Position;
+
+use super::{
+    record::{data::field::Tag, Flags, MappingQuality},
+    record_buf::data::field::Value,
+    RecordBuf,
+};
+use crate::io::writer::record::write_cigar;
+
+/// The data field tag for the mate score (`ms`).
+///
+/// This is the sum of the mate's quality scores that are greater than or equal to 15.
+pub const MATE_SCORE: Tag = Tag::new(b'm', b's');
+
+const MIN_MATE_SCORE_QUALITY_SCORE: u8 = 15;
+
+/// Fixes the mate information of records in a name group.
+///
+/// The given records are expected to share the same read name, e.g., a group of records from a
+/// queryname-grouped input. The primary first and last segments are treated as mates. For each,
+/// this sets
+///
+///   * the mate reference sequence ID and mate alignment start;
+///   * the mate unmapped and mate reverse complemented flags;
+///   * the template length;
+///   * the mate CIGAR (`MC`) and mate mapping quality (`MQ`) data fields, when the mate is
+///     mapped; and
+///   * the mate score (`ms`) data field.
+///
+/// An unmapped segment with a mapped mate is placed at the mate's position, and the properly
+/// segmented flag is cleared when either segment is unmapped.
+///
+/// Secondary and supplementary alignments receive the same mate information and template
+/// length as the primary alignment of their segment.
+///
+/// Groups with no segmented records (single-end reads) are left unchanged.
+///
+/// This is similar to `samtools fixmate -m`.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::alignment::{
+///     fixmate::fixmate,
+///     record::{cigar::{op::Kind, Op}, Flags},
+///     record_buf::Cigar,
+///     RecordBuf,
+/// };
+///
+/// let cigar: Cigar = [Op::new(Kind::Match, 4)].into_iter().collect();
+///
+/// let mut records = [
+///     RecordBuf::builder()
+///         .set_flags(Flags::SEGMENTED | Flags::FIRST_SEGMENT)
+///         .set_reference_sequence_id(0)
+///         .set_alignment_start(Position::try_from(8)?)
+///         .set_cigar(cigar.clone())
+///         .build(),
+///     RecordBuf::builder()
+///         .set_flags(Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED)
+///         .set_reference_sequence_id(0)
+///         .set_alignment_start(Position::try_from(21)?)
+///         .set_cigar(cigar)
+///         .build(),
+/// ];
+///
+/// fixmate(&mut records)?;
+///
+/// assert_eq!(records[0].mate_alignment_start(), Position::new(21));
+/// assert!(records[0].flags().is_mate_reverse_complemented());
+/// assert_eq!(records[0].template_length(), 17);
+/// assert_eq!(records[1].template_length(), -17);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn fixmate(records: &mut [RecordBuf]) -> Result<(), FixmateError> {
+    if !records.iter().any(|record| record.flags().is_segmented()) {
+        return Ok(());
+    }
+
+    let i = find_primary(records, Flags::FIRST_SEGMENT)?;
+    let j = find_primary(records, Flags::LAST_SEGMENT)?;
+
+    let (a, b) = get_pair_mut(records, i, j);
+    sync_unmapped_position(a, b);
+    sync_unmapped_position(b, a);
+
+    let a_mate = MateInfo::try_from(&*a).map_err(FixmateError::InvalidCigar)?;
+    let b_mate = MateInfo::try_from(&*b).map_err(FixmateError::InvalidCigar)?;
+
+    let (a_template_length, b_template_length) = calculate_template_lengths(a, b);
+
+    for (k, record) in records.iter_mut().enumerate() {
+        let flags = record.flags();
+
+        let (mate, template_length) = if k == i {
+            (&b_mate, a_template_length)
+        } else if k == j {
+            (&a_mate, b_template_length)
+        } else if flags.is_first_segment() {
+            (&b_mate, a_template_length)
+        } else if flags.is_last_segment() {
+            (&a_mate, b_template_length)
+        } else {
+            continue;
+        };
+
+        mate.apply(record);
+        *record.template_length_mut() = template_length;
+
+        if k == i || k == j {
+            let either_unmapped = a_mate.is_unmapped || b_mate.is_unmapped;
+
+            if either_unmapped {
+                record.flags_mut().remove(Flags::PROPERLY_SEGMENTED);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// An error returned when mate information fails to be fixed.
+#[derive(Debug)]
+pub enum FixmateError {
+    /// The primary alignment of a segment is missing.
+    MissingPrimarySegment(Flags),
+    /// A segment has more than one primary alignment.
+    DuplicatePrimarySegment(Flags),
+    /// The CIGAR of a primary alignment is invalid.
+    InvalidCigar(io::Error),
+}
+
+impl error::Error for FixmateError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidCigar(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FixmateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPrimarySegment(segment) => {
+                write!(f, "missing primary segment: {segment:?}")
+            }
+            Self::DuplicatePrimarySegment(segment) => {
+                write!(f, "duplicate primary segment: {segment:?}")
+            }
+            Self::InvalidCigar(_) => write!(f, "invalid CIGAR"),
+        }
+    }
+}
+
+struct MateInfo {
+    reference_sequence_id: Option<usize>,
+    alignment_start: Option<Position>,
+    is_unmapped: bool,
+    is_reverse_complemented: bool,
+    mapping_quality: Option<MappingQuality>,
+    cigar: BString,
+    score: i32,
+}
+
+impl MateInfo {
+    fn apply(&self, record: &mut RecordBuf) {
+        *record.mate_reference_sequence_id_mut() = self.reference_sequence_id;
+        *record.mate_alignment_start_mut() = self.alignment_start;
+
+        let flags = record.flags_mut();
+        flags.set(Flags::MATE_UNMAPPED, self.is_unmapped);
+        flags.set(
+            Flags::MATE_REVERSE_COMPLEMENTED,
+            self.is_reverse_complemented,
+        );
+
+        let data = record.data_mut();
+
+        if self.is_unmapped {
+            data.remove(&Tag::MATE_CIGAR);
+            data.remove(&Tag::MATE_MAPPING_QUALITY);
+        } else {
+            data.insert(Tag::MATE_CIGAR, Value::String(self.cigar.clone()));
+
+            match self.mapping_quality {
+                Some(mapping_quality) => {
+                    data.insert(Tag::MATE_MAPPING_QUALITY, mapping_quality.get().into());
+                }
+                None => {
+                    data.remove(&Tag::MATE_MAPPING_QUALITY);
+                }
+            }
+        }
+
+        data.insert(MATE_SCORE, self.score.into());
+    }
+}
+
+impl TryFrom<&RecordBuf> for MateInfo {
+    type Error = io::Error;
+
+    fn try_from(record: &RecordBuf) -> Result<Self, Self::Error> {
+        let flags = record.flags();
+
+        let mut cigar = Vec::new();
+        write_cigar(&mut cigar, record.cigar())?;
+
+        let score = record
+            .quality_scores()
+            .as_ref()
+            .iter()
+            .filter(|&&score| score >= MIN_MATE_SCORE_QUALITY_SCORE)
+            .map(|&score| i32::from(score))
+            .sum();
+
+        Ok(Self {
+            reference_sequence_id: record.reference_sequence_id(),
+            alignment_start: record.alignment_start(),
+            is_unmapped: flags.is_unmapped(),
+            is_reverse_complemented: flags.is_reverse_complemented(),
+            mapping_quality: record.mapping_quality(),
+            cigar: cigar.into(),
+            score,
+        })
+    }
+}
+
+fn find_primary(records: &[RecordBuf], segment: Flags) -> Result<usize, FixmateError> {
+    let mut indices = records.iter().enumerate().filter_map(|(i, record)| {
+        let flags = record.flags();
+
+        if flags.contains(segment) && !flags.is_secondary() && !flags.is_supplementary() {
+            Some(i)
+        } else {
+            None
+        }
+    });
+
+    let i = indices
+        .next()
+        .ok_or(FixmateError::MissingPrimarySegment(segment))?;
+
+    if indices.next().is_some() {
+        return Err(FixmateError::DuplicatePrimarySegment(segment));
+    }
+
+    Ok(i)
+}
+
+fn get_pair_mut(records: &mut [RecordBuf], i: usize, j: usize) -> (&mut RecordBuf, &mut RecordBuf) {
+    if i < j {
+        let (left, right) = records.split_at_mut(j);
+        (&mut left[i], &mut right[0])
+    } else {
+        let (left, right) = records.split_at_mut(i);
+        (&mut right[0], &mut left[j])
+    }
+}
+
+fn sync_unmapped_position(record: &mut RecordBuf, mate: &RecordBuf) {
+    if !record.flags().is_unmapped() {
+        return;
+    }
+
+    if mate.flags().is_unmapped() {
+        *record.reference_sequence_id_mut() = None;
+        *record.alignment_start_mut() = None;
+    } else {
+        *record.reference_sequence_id_mut() = mate.reference_sequence_id();
+        *record.alignment_start_mut() = mate.alignment_start();
+    }
+}
+
+// § 1.4.9 "TLEN" (2023-05-24): The leftmost segment has a plus sign and the rightmost has a
+// minus sign.
+fn calculate_template_lengths(a: &RecordBuf, b: &RecordBuf) -> (i32, i32) {
+    if a.flags().is_unmapped()
+        || b.flags().is_unmapped()
+        || a.reference_sequence_id() != b.reference_sequence_id()
+    {
+        return (0, 0);
+    }
+
+    let (Some(a_start), Some(a_end), Some(b_start), Some(b_end)) = (
+        a.alignment_start(),
+        a.alignment_end(),
+        b.alignment_start(),
+        b.alignment_end(),
+    ) else {
+        return (0, 0);
+    };
+
+    let start = a_start.min(b_start);
+    let end = a_end.max(b_end);
+    let len = i32::try_from(usize::from(end) - usize::from(start) + 1).unwrap_or(i32::MAX);
+
+    if a_start <= b_start {
+        (len, -len)
+    } else {
+        (-len, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::{
+        record::cigar::{op::Kind, Op},
+        record_buf::{Cigar, QualityScores},
+    };
+
+    fn build_record(flags: Flags, start: usize) -> Result<RecordBuf, Box<dyn std::error::Error>> {
+        let cigar: Cigar = [Op::new(Kind::Match, 4)].into_iter().collect();
+
+        Ok(RecordBuf::builder()
+            .set_flags(flags)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(start)?)
+            .set_mapping_quality(MappingQuality::try_from(13)?)
+            .set_cigar(cigar)
+            .set_quality_scores(QualityScores::from(vec![8, 15, 21, 34]))
+            .build())
+    }
+
+    #[test]
+    fn test_fixmate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records = [
+            build_record(
+                Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED,
+                8,
+            )?,
+            build_record(Flags::SEGMENTED | Flags::FIRST_SEGMENT, 21)?,
+            build_record(
+                Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::SUPPLEMENTARY,
+                55,
+            )?,
+        ];
+
+        fixmate(&mut records)?;
+
+        let [r2, r1, s1] = &records;
+
+        assert_eq!(r1.mate_reference_sequence_id(), Some(0));
+        assert_eq!(r1.mate_alignment_start(), Position::new(8));
+        assert!(r1.flags().is_mate_reverse_complemented());
+        assert_eq!(r1.template_length(), -17);
+        assert_eq!(r1.data().get(&Tag::MATE_CIGAR), Some(&Value::from("4M")));
+        assert_eq!(
+            r1.data().get(&Tag::MATE_MAPPING_QUALITY),
+            Some(&Value::from(13u8))
+        );
+        assert_eq!(r1.data().get(&MATE_SCORE), Some(&Value::from(70)));
+
+        assert_eq!(r2.mate_alignment_start(), Position::new(21));
+        assert!(!r2.flags().is_mate_reverse_complemented());
+        assert_eq!(r2.template_length(), 17);
+
+        assert_eq!(s1.mate_alignment_start(), Position::new(8));
+        assert!(s1.flags().is_mate_reverse_complemented());
+        assert_eq!(s1.template_length(), -17);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixmate_with_unmapped_mate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records = [
+            build_record(
+                Flags::SEGMENTED | Flags::PROPERLY_SEGMENTED | Flags::FIRST_SEGMENT,
+                8,
+            )?,
+            RecordBuf::builder()
+                .set_flags(
+                    Flags::SEGMENTED
+                        | Flags::PROPERLY_SEGMENTED
+                        | Flags::LAST_SEGMENT
+                        | Flags::UNMAPPED,
+                )
+                .build(),
+        ];
+
+        fixmate(&mut records)?;
+
+        let [r1, r2] = &records;
+
+        assert!(r1.flags().is_mate_unmapped());
+        assert!(!r1.flags().is_properly_segmented());
+        assert_eq!(r1.template_length(), 0);
+        assert!(r1.data().get(&Tag::MATE_CIGAR).is_none());
+
+        assert_eq!(r2.reference_sequence_id(), Some(0));
+        assert_eq!(r2.alignment_start(), Position::new(8));
+        assert_eq!(r2.mate_alignment_start(), Position::new(8));
+        assert!(!r2.flags().is_properly_segmented());
+        assert_eq!(r2.data().get(&Tag::MATE_CIGAR), Some(&Value::from("4M")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixmate_with_invalid_group() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records = [build_record(Flags::SEGMENTED | Flags::FIRST_SEGMENT, 8)?];
+        assert!(matches!(
+            fixmate(&mut records),
+            Err(FixmateError::MissingPrimarySegment(Flags::LAST_SEGMENT))
+        ));
+
+        let mut records = [
+            build_record(Flags::SEGMENTED | Flags::FIRST_SEGMENT, 8)?,
+            build_record(Flags::SEGMENTED | Flags::FIRST_SEGMENT, 13)?,
+            build_record(Flags::SEGMENTED | Flags::LAST_SEGMENT, 21)?,
+        ];
+        assert!(matches!(
+            fixmate(&mut records),
+            Err(FixmateError::DuplicatePrimarySegment(Flags::FIRST_SEGMENT))
+        ));
+
+        let mut records = [build_record(Flags::empty(), 8)?];
+        fixmate(&mut records)?;
+        assert!(records[0].mate_alignment_start().is_none());
+
+        Ok(())
+    }
+}
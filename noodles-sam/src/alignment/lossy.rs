@@ -0,0 +1,202 @@
+//! Lossy alignment record transforms.
+//!
+//! These reduce the size of alignment output by binning quality scores, dropping the quality
+//! scores of secondary alignments, and discarding read names. Discarded read names can be
+//! deterministically regenerated on read using a [`NameGenerator`].
+
+mod builder;
+mod name_generator;
+pub mod quality_score_bins;
+
+pub use self::{
+    builder::Builder, name_generator::NameGenerator, quality_score_bins::QualityScoreBins,
+};
+
+use super::{
+    record::Flags,
+    record_buf::{QualityScores, RecordBuf},
+};
+
+/// A lossy alignment record transform.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Transform {
+    quality_score_bins: Option<QualityScoreBins>,
+    discard_secondary_quality_scores: bool,
+    discard_read_names: bool,
+}
+
+impl Transform {
+    /// Returns a builder to create a lossy alignment record transform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::Transform;
+    /// let builder = Transform::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the quality score bins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::Transform;
+    /// let transform = Transform::default();
+    /// assert!(transform.quality_score_bins().is_none());
+    /// ```
+    pub fn quality_score_bins(&self) -> Option<&QualityScoreBins> {
+        self.quality_score_bins.as_ref()
+    }
+
+    /// Returns whether the quality scores of secondary alignments are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::Transform;
+    /// let transform = Transform::default();
+    /// assert!(!transform.discards_secondary_quality_scores());
+    /// ```
+    pub fn discards_secondary_quality_scores(&self) -> bool {
+        self.discard_secondary_quality_scores
+    }
+
+    /// Returns whether read names are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::Transform;
+    /// let transform = Transform::default();
+    /// assert!(!transform.discards_read_names());
+    /// ```
+    pub fn discards_read_names(&self) -> bool {
+        self.discard_read_names
+    }
+
+    /// Returns whether this transform changes any record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::Transform;
+    ///
+    /// assert!(Transform::default().is_identity());
+    ///
+    /// let transform = Transform::builder().discard_read_names(true).build();
+    /// assert!(!transform.is_identity());
+    /// ```
+    pub fn is_identity(&self) -> bool {
+        self.quality_score_bins.is_none()
+            && !self.discard_secondary_quality_scores
+            && !self.discard_read_names
+    }
+
+    /// Applies the transform to the quality scores of a record with the given flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::{
+    ///     lossy::{QualityScoreBins, Transform},
+    ///     record::Flags,
+    ///     record_buf::QualityScores,
+    /// };
+    ///
+    /// let transform = Transform::builder()
+    ///     .set_quality_score_bins(QualityScoreBins::illumina_8_level())
+    ///     .discard_secondary_quality_scores(true)
+    ///     .build();
+    ///
+    /// let mut quality_scores = QualityScores::from(vec![8, 21, 38]);
+    /// transform.apply_to_quality_scores(Flags::empty(), &mut quality_scores);
+    /// assert_eq!(quality_scores, QualityScores::from(vec![6, 22, 37]));
+    ///
+    /// transform.apply_to_quality_scores(Flags::SECONDARY, &mut quality_scores);
+    /// assert!(quality_scores.is_empty());
+    /// ```
+    pub fn apply_to_quality_scores(&self, flags: Flags, quality_scores: &mut QualityScores) {
+        if self.discard_secondary_quality_scores && flags.is_secondary() {
+            quality_scores.as_mut().clear();
+        } else if let Some(bins) = self.quality_score_bins() {
+            bins.apply(quality_scores.as_mut());
+        }
+    }
+
+    /// Applies the transform to an alignment record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::{
+    ///     lossy::{QualityScoreBins, Transform},
+    ///     record_buf::{Name, QualityScores, Sequence},
+    ///     RecordBuf,
+    /// };
+    ///
+    /// let transform = Transform::builder()
+    ///     .set_quality_score_bins(QualityScoreBins::illumina_8_level())
+    ///     .discard_read_names(true)
+    ///     .build();
+    ///
+    /// let mut record = RecordBuf::builder()
+    ///     .set_name(Name::from(b"r0"))
+    ///     .set_sequence(Sequence::from(b"ACG"))
+    ///     .set_quality_scores(QualityScores::from(vec![8, 21, 38]))
+    ///     .build();
+    ///
+    /// transform.apply(&mut record);
+    ///
+    /// assert!(record.name().is_none());
+    /// assert_eq!(record.quality_scores(), &QualityScores::from(vec![6, 22, 37]));
+    /// ```
+    pub fn apply(&self, record: &mut RecordBuf) {
+        if self.discard_read_names {
+            *record.name_mut() = None;
+        }
+
+        let flags = record.flags();
+        self.apply_to_quality_scores(flags, record.quality_scores_mut());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        use crate::alignment::record_buf::Name;
+
+        let transform = Transform::builder()
+            .set_quality_score_bins(QualityScoreBins::illumina_8_level())
+            .discard_secondary_quality_scores(true)
+            .build();
+
+        let mut record = RecordBuf::builder()
+            .set_name(Name::from(b"r0"))
+            .set_flags(Flags::SECONDARY)
+            .set_quality_scores(QualityScores::from(vec![8, 21, 38]))
+            .build();
+
+        transform.apply(&mut record);
+
+        assert_eq!(record.name(), Some(&Name::from(b"r0")));
+        assert!(record.quality_scores().is_empty());
+
+        let mut record = RecordBuf::builder()
+            .set_flags(Flags::SUPPLEMENTARY)
+            .set_quality_scores(QualityScores::from(vec![8, 21, 38]))
+            .build();
+
+        transform.apply(&mut record);
+
+        assert_eq!(
+            record.quality_scores(),
+            &QualityScores::from(vec![6, 22, 37])
+        );
+    }
+}
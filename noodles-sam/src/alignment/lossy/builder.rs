@@ -0,0 +1,82 @@
+use super::{QualityScoreBins, Transform};
+
+/// A lossy alignment record transform builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    quality_score_bins: Option<QualityScoreBins>,
+    discard_secondary_quality_scores: bool,
+    discard_read_names: bool,
+}
+
+impl Builder {
+    /// Sets the quality score bins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::{QualityScoreBins, Transform};
+    ///
+    /// let bins = QualityScoreBins::illumina_8_level();
+    /// let transform = Transform::builder()
+    ///     .set_quality_score_bins(bins.clone())
+    ///     .build();
+    ///
+    /// assert_eq!(transform.quality_score_bins(), Some(&bins));
+    /// ```
+    pub fn set_quality_score_bins(mut self, quality_score_bins: QualityScoreBins) -> Self {
+        self.quality_score_bins = Some(quality_score_bins);
+        self
+    }
+
+    /// Sets whether to discard the quality scores of secondary alignments.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::Transform;
+    ///
+    /// let transform = Transform::builder()
+    ///     .discard_secondary_quality_scores(true)
+    ///     .build();
+    ///
+    /// assert!(transform.discards_secondary_quality_scores());
+    /// ```
+    pub fn discard_secondary_quality_scores(mut self, value: bool) -> Self {
+        self.discard_secondary_quality_scores = value;
+        self
+    }
+
+    /// Sets whether to discard read names.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::Transform;
+    /// let transform = Transform::builder().discard_read_names(true).build();
+    /// assert!(transform.discards_read_names());
+    /// ```
+    pub fn discard_read_names(mut self, value: bool) -> Self {
+        self.discard_read_names = value;
+        self
+    }
+
+    /// Builds a lossy alignment record transform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::Transform;
+    /// let transform = Transform::builder().build();
+    /// ```
+    pub fn build(self) -> Transform {
+        Transform {
+            quality_score_bins: self.quality_score_bins,
+            discard_secondary_quality_scores: self.discard_secondary_quality_scores,
+            discard_read_names: self.discard_read_names,
+        }
+    }
+}
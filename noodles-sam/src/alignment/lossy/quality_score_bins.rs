@@ -0,0 +1,147 @@
+//! Quality score bins.
+
+use std::{error, fmt};
+
+/// Quality score bins.
+///
+/// Each bin maps a contiguous range of quality scores to a single representative score. Scores
+/// below the start of the first bin are left unchanged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QualityScoreBins(Vec<(u8, u8)>);
+
+impl QualityScoreBins {
+    /// Creates quality score bins from a list of (start, value) pairs.
+    ///
+    /// A bin includes all scores from its start up to but not including the start of the next
+    /// bin. The last bin includes all scores greater than or equal to its start. Bin starts must
+    /// be strictly increasing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::QualityScoreBins;
+    ///
+    /// let bins = QualityScoreBins::new(vec![(0, 5), (20, 30)])?;
+    /// assert_eq!(bins.get(13), 5);
+    /// assert_eq!(bins.get(34), 30);
+    /// # Ok::<_, noodles_sam::alignment::lossy::quality_score_bins::NewError>(())
+    /// ```
+    pub fn new(bins: Vec<(u8, u8)>) -> Result<Self, NewError> {
+        if bins.is_empty() {
+            return Err(NewError::Empty);
+        }
+
+        if !bins.windows(2).all(|w| w[0].0 < w[1].0) {
+            return Err(NewError::Unsorted);
+        }
+
+        Ok(Self(bins))
+    }
+
+    /// Returns the Illumina 8-level quality score binning scheme.
+    ///
+    /// Scores 0 and 1 are left unchanged; 2–9 → 6; 10–19 → 15; 20–24 → 22; 25–29 → 27; 30–34 →
+    /// 33; 35–39 → 37; and ≥ 40 → 40.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::QualityScoreBins;
+    ///
+    /// let bins = QualityScoreBins::illumina_8_level();
+    /// assert_eq!(bins.get(1), 1);
+    /// assert_eq!(bins.get(8), 6);
+    /// assert_eq!(bins.get(31), 33);
+    /// assert_eq!(bins.get(41), 40);
+    /// ```
+    pub fn illumina_8_level() -> Self {
+        Self(vec![
+            (2, 6),
+            (10, 15),
+            (20, 22),
+            (25, 27),
+            (30, 33),
+            (35, 37),
+            (40, 40),
+        ])
+    }
+
+    /// Returns the binned value of the given quality score.
+    pub fn get(&self, score: u8) -> u8 {
+        match self.0.partition_point(|(start, _)| *start <= score) {
+            0 => score,
+            i => self.0[i - 1].1,
+        }
+    }
+
+    /// Bins the given quality scores in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::lossy::QualityScoreBins;
+    ///
+    /// let bins = QualityScoreBins::illumina_8_level();
+    ///
+    /// let mut scores = [0, 8, 21, 38];
+    /// bins.apply(&mut scores);
+    /// assert_eq!(scores, [0, 6, 22, 37]);
+    /// ```
+    pub fn apply(&self, scores: &mut [u8]) {
+        for score in scores {
+            *score = self.get(*score);
+        }
+    }
+}
+
+/// An error returned when quality score bins fail to be created.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NewError {
+    /// There are no bins.
+    Empty,
+    /// The bin starts are not strictly increasing.
+    Unsorted,
+}
+
+impl error::Error for NewError {}
+
+impl fmt::Display for NewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty bins"),
+            Self::Unsorted => write!(f, "unsorted bins"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            QualityScoreBins::new(vec![(0, 5), (20, 30)]),
+            Ok(QualityScoreBins(vec![(0, 5), (20, 30)]))
+        );
+        assert_eq!(QualityScoreBins::new(Vec::new()), Err(NewError::Empty));
+        assert_eq!(
+            QualityScoreBins::new(vec![(20, 30), (20, 5)]),
+            Err(NewError::Unsorted)
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let bins = QualityScoreBins::illumina_8_level();
+
+        let actual: Vec<_> = (0..=45).map(|score| bins.get(score)).collect();
+
+        let expected = [
+            0, 1, 6, 6, 6, 6, 6, 6, 6, 6, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 22, 22, 22, 22,
+            22, 27, 27, 27, 27, 27, 33, 33, 33, 33, 33, 37, 37, 37, 37, 37, 40, 40, 40, 40, 40, 40,
+        ];
+
+        assert_eq!(actual, expected);
+    }
+}
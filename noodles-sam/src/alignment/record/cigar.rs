@@ -1,5 +1,6 @@
 //! Alignment record CIGAR operations.
 
+pub mod aligned_blocks;
 pub mod op;
 
 #[doc(hidden)]
@@ -7,7 +8,12 @@ pub mod iter;
 
 use std::io;
 
-pub use self::op::Op;
+use noodles_core::Position;
+
+pub use self::{
+    aligned_blocks::{AlignedBlock, AlignedBlocks},
+    op::Op,
+};
 
 /// Alignment record CIGAR operations.
 pub trait Cigar {
@@ -49,6 +55,239 @@ pub trait Cigar {
 
         Ok(length)
     }
+
+    /// Returns an iterator over aligned blocks.
+    ///
+    /// An aligned block is a run of read bases aligned to the reference sequence, i.e., an
+    /// alignment match (`M`), sequence match (`=`), or sequence mismatch (`X`) operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::{
+    ///     record::{cigar::{op::Kind, AlignedBlock, Op}, Cigar as _},
+    ///     record_buf::Cigar,
+    /// };
+    ///
+    /// let cigar: Cigar = [
+    ///     Op::new(Kind::SoftClip, 2),
+    ///     Op::new(Kind::Match, 4),
+    ///     Op::new(Kind::Deletion, 3),
+    ///     Op::new(Kind::Match, 5),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let alignment_start = Position::try_from(8)?;
+    /// let blocks: Vec<_> = cigar.aligned_blocks(alignment_start).collect::<io::Result<_>>()?;
+    ///
+    /// assert_eq!(blocks, [
+    ///     AlignedBlock::new(2, Position::try_from(8)?, 4),
+    ///     AlignedBlock::new(6, Position::try_from(15)?, 5),
+    /// ]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn aligned_blocks(&self, alignment_start: Position) -> AlignedBlocks<'_> {
+        AlignedBlocks::new(self.iter(), alignment_start)
+    }
+
+    /// Maps a reference sequence position to a 0-based read offset.
+    ///
+    /// This returns `None` if the position is not aligned to a read base, e.g., it is outside
+    /// the alignment or in a deletion or skipped region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::{
+    ///     record::{cigar::{op::Kind, Op}, Cigar as _},
+    ///     record_buf::Cigar,
+    /// };
+    ///
+    /// let cigar: Cigar = [
+    ///     Op::new(Kind::SoftClip, 2),
+    ///     Op::new(Kind::Match, 4),
+    ///     Op::new(Kind::Deletion, 3),
+    ///     Op::new(Kind::Match, 5),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let alignment_start = Position::try_from(8)?;
+    /// assert_eq!(cigar.query_offset(alignment_start, Position::try_from(9)?)?, Some(3));
+    /// assert_eq!(cigar.query_offset(alignment_start, Position::try_from(13)?)?, None);
+    /// assert_eq!(cigar.query_offset(alignment_start, Position::try_from(16)?)?, Some(7));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn query_offset(
+        &self,
+        alignment_start: Position,
+        position: Position,
+    ) -> io::Result<Option<usize>> {
+        for result in self.aligned_blocks(alignment_start) {
+            let block = result?;
+
+            if block.reference_start() > position {
+                break;
+            }
+
+            if let Some(offset) = block.query_offset(position) {
+                return Ok(Some(offset));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Maps a 0-based read offset to a reference sequence position.
+    ///
+    /// This returns `None` if the read base is not aligned to the reference sequence, e.g., it
+    /// is in an insertion or soft clip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::{
+    ///     record::{cigar::{op::Kind, Op}, Cigar as _},
+    ///     record_buf::Cigar,
+    /// };
+    ///
+    /// let cigar: Cigar = [
+    ///     Op::new(Kind::SoftClip, 2),
+    ///     Op::new(Kind::Match, 4),
+    ///     Op::new(Kind::Deletion, 3),
+    ///     Op::new(Kind::Match, 5),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let alignment_start = Position::try_from(8)?;
+    /// assert_eq!(cigar.reference_position(alignment_start, 1)?, None);
+    /// assert_eq!(cigar.reference_position(alignment_start, 3)?, Position::new(9));
+    /// assert_eq!(cigar.reference_position(alignment_start, 7)?, Position::new(16));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn reference_position(
+        &self,
+        alignment_start: Position,
+        query_offset: usize,
+    ) -> io::Result<Option<Position>> {
+        for result in self.aligned_blocks(alignment_start) {
+            let block = result?;
+
+            if block.query_start() > query_offset {
+                break;
+            }
+
+            if let Some(position) = block.reference_position(query_offset) {
+                return Ok(Some(position));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Calculates the unclipped start position.
+    ///
+    /// This is the alignment start moved left by the lengths of the leading soft clips (`S`) and
+    /// hard clips (`H`). It returns `None` if the unclipped start would precede the start of the
+    /// reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::{
+    ///     record::{cigar::{op::Kind, Op}, Cigar as _},
+    ///     record_buf::Cigar,
+    /// };
+    ///
+    /// let cigar: Cigar = [
+    ///     Op::new(Kind::HardClip, 3),
+    ///     Op::new(Kind::SoftClip, 2),
+    ///     Op::new(Kind::Match, 4),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let alignment_start = Position::try_from(8)?;
+    /// assert_eq!(cigar.unclipped_start(alignment_start)?, Position::new(3));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn unclipped_start(&self, alignment_start: Position) -> io::Result<Option<Position>> {
+        let mut clip_len = 0;
+
+        for result in self.iter() {
+            let op = result?;
+
+            match op.kind() {
+                op::Kind::SoftClip | op::Kind::HardClip => clip_len += op.len(),
+                _ => break,
+            }
+        }
+
+        Ok(usize::from(alignment_start)
+            .checked_sub(clip_len)
+            .and_then(Position::new))
+    }
+
+    /// Calculates the unclipped end position.
+    ///
+    /// This is the alignment end moved right by the lengths of the trailing soft clips (`S`) and
+    /// hard clips (`H`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::{
+    ///     record::{cigar::{op::Kind, Op}, Cigar as _},
+    ///     record_buf::Cigar,
+    /// };
+    ///
+    /// let cigar: Cigar = [
+    ///     Op::new(Kind::Match, 4),
+    ///     Op::new(Kind::SoftClip, 2),
+    ///     Op::new(Kind::HardClip, 3),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let alignment_start = Position::try_from(8)?;
+    /// assert_eq!(cigar.unclipped_end(alignment_start)?, Position::new(16));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn unclipped_end(&self, alignment_start: Position) -> io::Result<Option<Position>> {
+        let mut span = 0;
+        let mut clip_len = 0;
+
+        for result in self.iter() {
+            let op = result?;
+            let kind = op.kind();
+
+            if matches!(kind, op::Kind::SoftClip | op::Kind::HardClip) {
+                clip_len += op.len();
+            } else {
+                clip_len = 0;
+
+                if kind.consumes_reference() {
+                    span += op.len();
+                }
+            }
+        }
+
+        Ok((usize::from(alignment_start) + span + clip_len)
+            .checked_sub(1)
+            .and_then(Position::new))
+    }
 }
 
 impl<'a> IntoIterator for &'a dyn Cigar {
@@ -132,4 +371,49 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_query_offset_and_reference_position() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar: &dyn Cigar = &T(vec![
+            Op::new(Kind::HardClip, 5),
+            Op::new(Kind::SoftClip, 2),
+            Op::new(Kind::Match, 3),
+            Op::new(Kind::Insertion, 2),
+            Op::new(Kind::Skip, 10),
+            Op::new(Kind::SequenceMatch, 4),
+        ]);
+
+        let alignment_start = Position::try_from(100)?;
+
+        // 100-102 M, I, 103-112 N, 113-116 =
+        let cases = [
+            (99, None),
+            (100, Some(2)),
+            (102, Some(4)),
+            (103, None),
+            (113, Some(7)),
+            (116, Some(10)),
+            (117, None),
+        ];
+
+        for (position, expected) in cases {
+            let position = Position::try_from(position)?;
+            assert_eq!(cigar.query_offset(alignment_start, position)?, expected);
+
+            if let Some(query_offset) = expected {
+                assert_eq!(
+                    cigar.reference_position(alignment_start, query_offset)?,
+                    Some(position)
+                );
+            }
+        }
+
+        assert_eq!(cigar.reference_position(alignment_start, 1)?, None);
+        assert_eq!(cigar.reference_position(alignment_start, 5)?, None);
+
+        assert_eq!(cigar.unclipped_start(alignment_start)?, Position::new(93));
+        assert_eq!(cigar.unclipped_end(alignment_start)?, Position::new(116));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,175 @@
+//! Alignment record CIGAR aligned blocks.
+
+use std::io;
+
+use noodles_core::Position;
+
+use super::{op::Kind, Op};
+
+/// A contiguous run of read bases aligned to the reference sequence.
+///
+/// An aligned block is created from a CIGAR operation that consumes both the read and the
+/// reference sequence, i.e., an alignment match (`M`), sequence match (`=`), or sequence
+/// mismatch (`X`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AlignedBlock {
+    query_start: usize,
+    reference_start: Position,
+    len: usize,
+}
+
+impl AlignedBlock {
+    /// Creates an aligned block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::record::cigar::AlignedBlock;
+    /// let block = AlignedBlock::new(0, Position::MIN, 4);
+    /// ```
+    pub fn new(query_start: usize, reference_start: Position, len: usize) -> Self {
+        Self {
+            query_start,
+            reference_start,
+            len,
+        }
+    }
+
+    /// Returns the 0-based start offset in the read.
+    pub fn query_start(&self) -> usize {
+        self.query_start
+    }
+
+    /// Returns the start position in the reference sequence.
+    pub fn reference_start(&self) -> Position {
+        self.reference_start
+    }
+
+    /// Returns the length of the block.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the block is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the end position in the reference sequence.
+    pub fn reference_end(&self) -> Option<Position> {
+        usize::from(self.reference_start)
+            .checked_add(self.len)
+            .and_then(|n| n.checked_sub(1))
+            .and_then(Position::new)
+    }
+
+    pub(super) fn query_offset(&self, position: Position) -> Option<usize> {
+        let start = usize::from(self.reference_start);
+        let position = usize::from(position);
+
+        (start..start + self.len)
+            .contains(&position)
+            .then(|| self.query_start + (position - start))
+    }
+
+    pub(crate) fn reference_position(&self, query_offset: usize) -> Option<Position> {
+        (self.query_start..self.query_start + self.len)
+            .contains(&query_offset)
+            .then(|| usize::from(self.reference_start) + (query_offset - self.query_start))
+            .and_then(Position::new)
+    }
+}
+
+/// An iterator over the aligned blocks of a CIGAR.
+///
+/// This is created by calling [`super::Cigar::aligned_blocks`].
+pub struct AlignedBlocks<'a> {
+    ops: Box<dyn Iterator<Item = io::Result<Op>> + 'a>,
+    query_position: usize,
+    reference_position: usize,
+}
+
+impl<'a> AlignedBlocks<'a> {
+    pub(super) fn new(
+        ops: Box<dyn Iterator<Item = io::Result<Op>> + 'a>,
+        alignment_start: Position,
+    ) -> Self {
+        Self {
+            ops,
+            query_position: 0,
+            reference_position: usize::from(alignment_start),
+        }
+    }
+}
+
+impl Iterator for AlignedBlocks<'_> {
+    type Item = io::Result<AlignedBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let op = match self.ops.next()? {
+                Ok(op) => op,
+                Err(e) => return Some(Err(e)),
+            };
+
+            let kind = op.kind();
+            let len = op.len();
+
+            let block = match kind {
+                Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => Some(
+                    Position::new(self.reference_position)
+                        .map(|reference_start| {
+                            AlignedBlock::new(self.query_position, reference_start, len)
+                        })
+                        .ok_or_else(|| {
+                            io::Error::new(io::ErrorKind::InvalidData, "invalid reference position")
+                        }),
+                ),
+                _ => None,
+            };
+
+            if kind.consumes_read() {
+                self.query_position += len;
+            }
+
+            if kind.consumes_reference() {
+                self.reference_position += len;
+            }
+
+            if block.is_some() {
+                return block;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next() -> Result<(), Box<dyn std::error::Error>> {
+        let ops = [
+            Op::new(Kind::SoftClip, 2),
+            Op::new(Kind::Match, 4),
+            Op::new(Kind::Insertion, 1),
+            Op::new(Kind::SequenceMatch, 3),
+            Op::new(Kind::Deletion, 2),
+            Op::new(Kind::SequenceMismatch, 1),
+        ];
+
+        let blocks = AlignedBlocks::new(Box::new(ops.into_iter().map(Ok)), Position::try_from(8)?);
+        let actual: Vec<_> = blocks.collect::<io::Result<_>>()?;
+
+        let expected = [
+            AlignedBlock::new(2, Position::try_from(8)?, 4),
+            AlignedBlock::new(7, Position::try_from(12)?, 3),
+            AlignedBlock::new(10, Position::try_from(17)?, 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
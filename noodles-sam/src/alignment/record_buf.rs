@@ -2,6 +2,7 @@
 
 mod builder;
 mod cigar;
+pub mod clip;
 mod convert;
 pub mod data;
 mod name;
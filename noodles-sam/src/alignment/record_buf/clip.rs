@@ -0,0 +1,330 @@
+//! Alignment record buffer clipping.
+
+use std::io;
+
+use noodles_core::{region::Interval, Position};
+
+use super::{Cigar, RecordBuf};
+use crate::alignment::record::cigar::{op::Kind, Op};
+
+/// Soft clips the read bases that are aligned outside the given reference sequence interval.
+///
+/// The CIGAR and alignment start are updated. The sequence and quality scores are unchanged, as
+/// soft-clipped bases remain in the record. Deletions and skipped regions outside the interval
+/// are removed, and insertions that end up next to a clip are soft clipped.
+///
+/// This returns an error if the record has no alignment start or no read bases are aligned
+/// within the interval.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::alignment::{
+///     record::cigar::{op::Kind, Op},
+///     record_buf::{clip::soft_clip_to_interval, Cigar},
+///     RecordBuf,
+/// };
+///
+/// let mut record = RecordBuf::builder()
+///     .set_alignment_start(Position::try_from(8)?)
+///     .set_cigar([Op::new(Kind::Match, 10)].into_iter().collect())
+///     .build();
+///
+/// let start = Position::try_from(10)?;
+/// let end = Position::try_from(13)?;
+/// soft_clip_to_interval(&mut record, start..=end)?;
+///
+/// let expected: Cigar = [
+///     Op::new(Kind::SoftClip, 2),
+///     Op::new(Kind::Match, 4),
+///     Op::new(Kind::SoftClip, 4),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(record.cigar(), &expected);
+/// assert_eq!(record.alignment_start(), Some(start));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn soft_clip_to_interval<I>(record: &mut RecordBuf, interval: I) -> io::Result<()>
+where
+    I: Into<Interval>,
+{
+    let alignment_start = record
+        .alignment_start()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing alignment start"))?;
+
+    let interval = interval.into();
+    let interval_start = interval.start().map(usize::from).unwrap_or(usize::MIN);
+    let interval_end = interval
+        .end()
+        .map(|position| usize::from(position).saturating_add(1))
+        .unwrap_or(usize::MAX);
+
+    let mut ops = Vec::new();
+    let mut reference_position = usize::from(alignment_start);
+    let mut new_alignment_start = None;
+
+    for op in record.cigar().as_ref() {
+        let (kind, len) = (op.kind(), op.len());
+
+        let start = reference_position;
+        let end = start + if kind.consumes_reference() { len } else { 0 };
+        let inside_start = interval_start.clamp(start, end);
+        let inside_end = interval_end.clamp(start, end);
+
+        match kind {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                push_op(&mut ops, Kind::SoftClip, inside_start - start);
+
+                if inside_end > inside_start {
+                    new_alignment_start.get_or_insert(inside_start);
+                    push_op(&mut ops, kind, inside_end - inside_start);
+                }
+
+                push_op(&mut ops, Kind::SoftClip, end - inside_end);
+            }
+            Kind::Deletion | Kind::Skip => {
+                push_op(&mut ops, kind, inside_end.saturating_sub(inside_start));
+            }
+            _ => push_op(&mut ops, kind, len),
+        }
+
+        reference_position = end;
+    }
+
+    let new_alignment_start = new_alignment_start.and_then(Position::new).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no read bases are aligned within the interval",
+        )
+    })?;
+
+    *record.cigar_mut() = normalize_clips(ops);
+    *record.alignment_start_mut() = Some(new_alignment_start);
+
+    Ok(())
+}
+
+/// Converts soft clips to hard clips.
+///
+/// The soft-clipped bases are removed from the sequence and quality scores.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::alignment::{
+///     record::cigar::{op::Kind, Op},
+///     record_buf::{clip::convert_soft_clips_to_hard_clips, Cigar, QualityScores, Sequence},
+///     RecordBuf,
+/// };
+///
+/// let mut record = RecordBuf::builder()
+///     .set_cigar(
+///         [
+///             Op::new(Kind::SoftClip, 1),
+///             Op::new(Kind::Match, 2),
+///             Op::new(Kind::SoftClip, 1),
+///         ]
+///         .into_iter()
+///         .collect(),
+///     )
+///     .set_sequence(Sequence::from(b"ACGT"))
+///     .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50]))
+///     .build();
+///
+/// convert_soft_clips_to_hard_clips(&mut record);
+///
+/// let expected: Cigar = [
+///     Op::new(Kind::HardClip, 1),
+///     Op::new(Kind::Match, 2),
+///     Op::new(Kind::HardClip, 1),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(record.cigar(), &expected);
+/// assert_eq!(record.sequence(), &Sequence::from(b"CG"));
+/// assert_eq!(record.quality_scores(), &QualityScores::from(vec![35, 43]));
+/// ```
+pub fn convert_soft_clips_to_hard_clips(record: &mut RecordBuf) {
+    let mut ops = Vec::new();
+    let mut leading_len = 0;
+    let mut trailing_len = 0;
+    let mut is_leading = true;
+
+    for op in record.cigar().as_ref() {
+        match op.kind() {
+            Kind::SoftClip => {
+                if is_leading {
+                    leading_len += op.len();
+                } else {
+                    trailing_len += op.len();
+                }
+
+                push_op(&mut ops, Kind::HardClip, op.len());
+            }
+            Kind::HardClip => push_op(&mut ops, Kind::HardClip, op.len()),
+            kind => {
+                is_leading = false;
+                trailing_len = 0;
+                push_op(&mut ops, kind, op.len());
+            }
+        }
+    }
+
+    *record.cigar_mut() = Cigar::from(ops);
+
+    trim(record.sequence_mut().as_mut(), leading_len, trailing_len);
+    trim(
+        record.quality_scores_mut().as_mut(),
+        leading_len,
+        trailing_len,
+    );
+}
+
+fn push_op(ops: &mut Vec<Op>, kind: Kind, len: usize) {
+    if len == 0 {
+        return;
+    }
+
+    match ops.last_mut() {
+        Some(last) if last.kind() == kind => *last = Op::new(kind, last.len() + len),
+        _ => ops.push(Op::new(kind, len)),
+    }
+}
+
+fn is_aligned(kind: Kind) -> bool {
+    matches!(
+        kind,
+        Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch
+    )
+}
+
+// Ops between a clip and the first (or last) aligned block are either soft clipped (insertions)
+// or removed (deletions, skips, and pads).
+fn normalize_clips(ops: Vec<Op>) -> Cigar {
+    let first = ops.iter().position(|op| is_aligned(op.kind()));
+    let last = ops.iter().rposition(|op| is_aligned(op.kind()));
+
+    let (Some(first), Some(last)) = (first, last) else {
+        return Cigar::from(ops);
+    };
+
+    let mut normalized_ops = Vec::with_capacity(ops.len());
+
+    for (i, op) in ops.into_iter().enumerate() {
+        let kind = if i < first || i > last {
+            match op.kind() {
+                Kind::Insertion => Kind::SoftClip,
+                Kind::Deletion | Kind::Skip | Kind::Pad => continue,
+                kind => kind,
+            }
+        } else {
+            op.kind()
+        };
+
+        push_op(&mut normalized_ops, kind, op.len());
+    }
+
+    Cigar::from(normalized_ops)
+}
+
+fn trim(buf: &mut Vec<u8>, leading_len: usize, trailing_len: usize) {
+    if buf.is_empty() {
+        return;
+    }
+
+    let end = buf.len().saturating_sub(trailing_len);
+    buf.truncate(end);
+    buf.drain(..leading_len.min(end));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_cigar(ops: &[(Kind, usize)]) -> Cigar {
+        ops.iter().map(|&(kind, len)| Op::new(kind, len)).collect()
+    }
+
+    #[test]
+    fn test_soft_clip_to_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = RecordBuf::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar(build_cigar(&[
+                (Kind::HardClip, 2),
+                (Kind::SoftClip, 1),
+                (Kind::Match, 3),
+                (Kind::Deletion, 2),
+                (Kind::Insertion, 1),
+                (Kind::Match, 4),
+                (Kind::Deletion, 1),
+                (Kind::Match, 2),
+            ]))
+            .build();
+
+        // 8-10 M, 11-12 D, I, 13-16 M, 17 D, 18-19 M
+        soft_clip_to_interval(
+            &mut record,
+            Position::try_from(12)?..=Position::try_from(17)?,
+        )?;
+
+        assert_eq!(
+            record.cigar(),
+            &build_cigar(&[
+                (Kind::HardClip, 2),
+                (Kind::SoftClip, 5),
+                (Kind::Match, 4),
+                (Kind::SoftClip, 2),
+            ])
+        );
+        assert_eq!(record.alignment_start(), Position::new(13));
+
+        let mut record = RecordBuf::builder()
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar(build_cigar(&[(Kind::Match, 4)]))
+            .build();
+
+        assert!(matches!(
+            soft_clip_to_interval(&mut record, Position::try_from(13)?..=Position::try_from(21)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let mut record = RecordBuf::default();
+        assert!(soft_clip_to_interval(&mut record, ..).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_soft_clips_to_hard_clips() {
+        let mut record = RecordBuf::builder()
+            .set_cigar(build_cigar(&[
+                (Kind::HardClip, 1),
+                (Kind::SoftClip, 2),
+                (Kind::Match, 2),
+                (Kind::Insertion, 1),
+                (Kind::Match, 1),
+                (Kind::SoftClip, 1),
+            ]))
+            .set_sequence(b"ACGTACG".into())
+            .build();
+
+        convert_soft_clips_to_hard_clips(&mut record);
+
+        assert_eq!(
+            record.cigar(),
+            &build_cigar(&[
+                (Kind::HardClip, 3),
+                (Kind::Match, 2),
+                (Kind::Insertion, 1),
+                (Kind::Match, 1),
+                (Kind::HardClip, 1),
+            ])
+        );
+        assert_eq!(record.sequence().as_ref(), b"GTAC");
+        assert!(record.quality_scores().is_empty());
+    }
+}
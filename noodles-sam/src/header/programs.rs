@@ -45,6 +45,10 @@ impl Programs {
     /// If the program is the first program in the graph, this is similar to calling
     /// `IndexMap::insert` on the inner graph. If no previous program is set, this attaches the
     /// program to all program chains using leaf programs as the given program's previous program.
+    /// Otherwise, the program is only linked to the given previous program.
+    ///
+    /// If the ID prefix is already taken or the program is attached to more than one chain, IDs
+    /// are made unique by appending the previous program ID, e.g., `pg0-pg1`.
     ///
     /// # Examples
     ///
@@ -81,7 +85,12 @@ impl Programs {
             return Ok(());
         }
 
-        let previous_program_ids: Vec<BString> = self.leaves()?.map(|(id, _)| id.into()).collect();
+        let previous_program_ids: Vec<BString> =
+            match map.other_fields().get(&tag::PREVIOUS_PROGRAM_ID) {
+                Some(previous_program_id) => vec![previous_program_id.clone()],
+                None => self.leaves()?.map(|(id, _)| id.into()).collect(),
+            };
+
         let contains_prefix_id = self.0.contains_key(&id_prefix);
 
         for (i, previous_program_id) in previous_program_ids.into_iter().enumerate() {
@@ -287,6 +296,13 @@ mod tests {
         );
         assert_eq!(programs, expected);
 
+        let map = Map::builder()
+            .insert(tag::PREVIOUS_PROGRAM_ID, "pg0")
+            .build()?;
+        programs.add("pg3", map.clone())?;
+        assert_eq!(programs.as_ref().get(&b"pg3-pg0"[..]), Some(&map));
+        assert_eq!(programs.as_ref().len(), 6);
+
         Ok(())
     }
 
@@ -2,7 +2,7 @@ mod field;
 
 use std::io::{self, Write};
 
-use self::field::write_field;
+pub use self::field::write_field;
 use crate::alignment::record::Data;
 
 pub(super) fn write_data<W, D>(writer: &mut W, data: D) -> io::Result<()>
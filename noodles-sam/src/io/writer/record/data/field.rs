@@ -7,6 +7,21 @@ use std::io::{self, Write};
 use self::{tag::write_tag, ty::write_type, value::write_value};
 use crate::alignment::record::data::field::{Tag, Value};
 
+/// Writes a SAM record data field.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{
+///     alignment::record::data::field::{Tag, Value},
+///     io::writer::record::write_field,
+/// };
+///
+/// let mut buf = Vec::new();
+/// write_field(&mut buf, Tag::ALIGNMENT_HIT_COUNT, &Value::UInt8(1))?;
+/// assert_eq!(buf, b"NH:i:1");
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn write_field<W>(writer: &mut W, tag: Tag, value: &Value) -> io::Result<()>
 where
     W: Write,
@@ -14,8 +14,12 @@ use noodles_core::Position;
 
 pub(crate) use self::fields::Fields;
 pub use self::{
-    cigar::Cigar, data::Data, name::Name, quality_scores::QualityScores,
-    reference_sequence_name::ReferenceSequenceName, sequence::Sequence,
+    cigar::{parse_cigar, Cigar},
+    data::Data,
+    name::Name,
+    quality_scores::QualityScores,
+    reference_sequence_name::ReferenceSequenceName,
+    sequence::Sequence,
 };
 use crate::{
     alignment::record::{Flags, MappingQuality},
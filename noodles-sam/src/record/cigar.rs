@@ -2,6 +2,33 @@ use std::{fmt, io, iter};
 
 use crate::{alignment::record::cigar::Op, io::reader::record_buf::cigar::op};
 
+/// Parses a SAM record CIGAR string.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{
+///     alignment::record::cigar::{op::Kind, Op},
+///     record::parse_cigar,
+/// };
+///
+/// let cigar = parse_cigar(b"8M13N")?;
+/// assert_eq!(cigar.as_ref(), [Op::new(Kind::Match, 8), Op::new(Kind::Skip, 13)]);
+///
+/// assert!(parse_cigar(b"").is_err());
+/// assert!(parse_cigar(b"8Z").is_err());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn parse_cigar(src: &[u8]) -> io::Result<crate::alignment::record_buf::Cigar> {
+    use crate::io::reader::record_buf::parse_cigar;
+
+    let mut cigar = crate::alignment::record_buf::Cigar::default();
+
+    parse_cigar(src, &mut cigar).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(cigar)
+}
+
 /// Raw SAM record CIGAR operations.
 #[derive(Eq, PartialEq)]
 pub struct Cigar<'a>(&'a [u8]);
@@ -113,15 +140,10 @@ impl<'a> TryFrom<Cigar<'a>> for crate::alignment::record_buf::Cigar {
     type Error = io::Error;
 
     fn try_from(Cigar(src): Cigar<'a>) -> Result<Self, Self::Error> {
-        use crate::io::reader::record_buf::parse_cigar;
-
-        let mut cigar = Self::default();
-
-        if !src.is_empty() {
-            parse_cigar(src, &mut cigar)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if src.is_empty() {
+            Ok(Self::default())
+        } else {
+            parse_cigar(src)
         }
-
-        Ok(cigar)
     }
 }
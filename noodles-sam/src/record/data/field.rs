@@ -10,7 +10,27 @@ use self::ty::Type;
 use self::{tag::parse_tag, ty::parse_type, value::parse_value};
 use crate::alignment::record::data::field::{Tag, Value};
 
-pub(super) fn parse_field<'a>(src: &mut &'a [u8]) -> io::Result<(Tag, Value<'a>)> {
+/// Parses a SAM record data field.
+///
+/// This consumes the field and, if present, its field terminator.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{
+///     alignment::record::data::field::{Tag, Value},
+///     record::data::field::parse_field,
+/// };
+///
+/// let mut src = &b"NH:i:1"[..];
+/// let (tag, value) = parse_field(&mut src)?;
+///
+/// assert_eq!(tag, Tag::ALIGNMENT_HIT_COUNT);
+/// assert!(matches!(value, Value::Int32(1)));
+/// assert!(src.is_empty());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn parse_field<'a>(src: &mut &'a [u8]) -> io::Result<(Tag, Value<'a>)> {
     let tag = parse_tag(src)?;
     consume_delimiter(src)?;
     let ty = parse_type(src)?;
@@ -1,9 +1,29 @@
 //! SAM record data field value and types.
 
+mod alignment_score;
 mod array;
+mod barcode;
 pub mod base_modifications;
-
-pub use self::base_modifications::BaseModifications;
+mod edit_distance;
+mod mate_cigar;
+pub mod mismatched_positions;
+mod original_quality_scores;
+pub mod other_alignments;
+mod read_group;
+mod typed_value;
+
+pub use self::{
+    alignment_score::{AlignmentScore, SuboptimalAlignmentScore},
+    barcode::{CellBarcodeId, UmiBarcodeId},
+    base_modifications::BaseModifications,
+    edit_distance::EditDistance,
+    mate_cigar::MateCigar,
+    mismatched_positions::MismatchedPositions,
+    original_quality_scores::OriginalQualityScores,
+    other_alignments::OtherAlignments,
+    read_group::ReadGroup,
+    typed_value::TypedValue,
+};
 
 use std::io;
 
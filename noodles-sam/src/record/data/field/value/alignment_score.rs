@@ -0,0 +1,98 @@
+//! SAM record data field values for alignment scores.
+
+use std::io;
+
+use super::typed_value::{get_int, invalid_value, TypedValue};
+use crate::alignment::{
+    record::data::field::{Tag, Value},
+    record_buf::data::field::Value as ValueBuf,
+};
+
+/// The alignment score generated by the aligner (`AS`).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct AlignmentScore(i32);
+
+impl From<i32> for AlignmentScore {
+    fn from(n: i32) -> Self {
+        Self(n)
+    }
+}
+
+impl From<AlignmentScore> for i32 {
+    fn from(alignment_score: AlignmentScore) -> Self {
+        alignment_score.0
+    }
+}
+
+impl TypedValue for AlignmentScore {
+    const TAG: Tag = Tag::ALIGNMENT_SCORE;
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        try_from_value(Self::TAG, value).map(Self)
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        Ok(ValueBuf::Int32(self.0))
+    }
+}
+
+/// The alignment score of the best suboptimal alignment (`XS`).
+///
+/// This is not a standard data field but is the convention used by aligners such as BWA and
+/// Bowtie 2.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SuboptimalAlignmentScore(i32);
+
+impl From<i32> for SuboptimalAlignmentScore {
+    fn from(n: i32) -> Self {
+        Self(n)
+    }
+}
+
+impl From<SuboptimalAlignmentScore> for i32 {
+    fn from(alignment_score: SuboptimalAlignmentScore) -> Self {
+        alignment_score.0
+    }
+}
+
+impl TypedValue for SuboptimalAlignmentScore {
+    const TAG: Tag = Tag::new(b'X', b'S');
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        try_from_value(Self::TAG, value).map(Self)
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        Ok(ValueBuf::Int32(self.0))
+    }
+}
+
+fn try_from_value(tag: Tag, value: Value<'_>) -> io::Result<i32> {
+    let n = get_int(tag, &value)?;
+    i32::try_from(n).map_err(|_| invalid_value(io::ErrorKind::InvalidData, tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_value() -> io::Result<()> {
+        assert_eq!(
+            AlignmentScore::try_from_value(Value::Int16(-8))?,
+            AlignmentScore(-8)
+        );
+
+        assert_eq!(
+            SuboptimalAlignmentScore::try_from_value(Value::UInt8(13))?,
+            SuboptimalAlignmentScore(13)
+        );
+
+        assert!(matches!(
+            AlignmentScore::try_from_value(Value::UInt32(u32::MAX)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+//! SAM record data field values for single-cell barcodes.
+
+use std::io;
+
+use super::typed_value::{is_graphic, try_from_string, try_into_string, TypedValue};
+use crate::alignment::{
+    record::data::field::{Tag, Value},
+    record_buf::data::field::Value as ValueBuf,
+};
+
+/// The cell identifier (`CB`).
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CellBarcodeId(String);
+
+impl AsRef<str> for CellBarcodeId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for CellBarcodeId {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<CellBarcodeId> for String {
+    fn from(barcode: CellBarcodeId) -> Self {
+        barcode.0
+    }
+}
+
+impl TypedValue for CellBarcodeId {
+    const TAG: Tag = Tag::CELL_BARCODE_ID;
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        try_into_string(Self::TAG, &value, is_graphic).map(Self)
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        try_from_string(Self::TAG, self.0, is_graphic)
+    }
+}
+
+/// The corrected unique molecular identifier (`UB`).
+///
+/// This is not a standard data field but is the convention used by single-cell pipelines such as
+/// Cell Ranger and STARsolo. It uses the same format as the cell identifier (`CB`).
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct UmiBarcodeId(String);
+
+impl AsRef<str> for UmiBarcodeId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for UmiBarcodeId {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<UmiBarcodeId> for String {
+    fn from(barcode: UmiBarcodeId) -> Self {
+        barcode.0
+    }
+}
+
+impl TypedValue for UmiBarcodeId {
+    const TAG: Tag = Tag::new(b'U', b'B');
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        try_into_string(Self::TAG, &value, is_graphic).map(Self)
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        try_from_string(Self::TAG, self.0, is_graphic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::BStr;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from_value() -> io::Result<()> {
+        assert_eq!(
+            CellBarcodeId::try_from_value(Value::String(BStr::new("ACGT-1")))?,
+            CellBarcodeId::from(String::from("ACGT-1"))
+        );
+
+        assert!(matches!(
+            CellBarcodeId::try_from_value(Value::String(BStr::new(""))),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            UmiBarcodeId::try_from_value(Value::String(BStr::new("AC GT"))),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
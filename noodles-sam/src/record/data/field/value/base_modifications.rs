@@ -1,11 +1,19 @@
 //! SAM record data field value for base modifications.
 
+mod call;
 pub mod group;
 mod parser;
+pub mod pileup;
 
-pub use self::group::Group;
+pub use self::{call::Call, group::Group, parser::ParseError, pileup::Pileup};
 
-use crate::alignment::record_buf::Sequence;
+use std::io;
+
+use crate::alignment::{
+    record::data::field::{value::Array, Tag, Value},
+    record_buf::Sequence,
+    Record,
+};
 
 /// Base modifications.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -51,9 +59,164 @@ impl BaseModifications {
         s: &str,
         is_reverse_complemented: bool,
         sequence: &Sequence,
-    ) -> Result<Self, parser::ParseError> {
+    ) -> Result<Self, ParseError> {
         parser::parse(s, is_reverse_complemented, sequence)
     }
+
+    /// Parses base modifications from a string with modification probabilities.
+    ///
+    /// The probabilities are the raw values of the base modification probabilities (`ML`) data
+    /// field. There must be one probability per position per modification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     alignment::record_buf::Sequence,
+    ///     record::data::field::value::BaseModifications,
+    /// };
+    ///
+    /// let sequence = Sequence::from(b"CACCCGATGACCGGCT");
+    /// let base_modifications = BaseModifications::parse_with_probabilities(
+    ///     "C+mh,1,3;",
+    ///     &[200, 5, 13, 8],
+    ///     false,
+    ///     &sequence,
+    /// )?;
+    ///
+    /// let calls: Vec<_> = base_modifications.calls().collect();
+    /// assert_eq!(calls.len(), 4);
+    /// assert_eq!(calls[0].position(), 2);
+    /// assert_eq!(calls[0].probability(), Some(200));
+    /// assert_eq!(calls[3].position(), 11);
+    /// assert_eq!(calls[3].probability(), Some(8));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parse_with_probabilities(
+        s: &str,
+        probabilities: &[u8],
+        is_reverse_complemented: bool,
+        sequence: &Sequence,
+    ) -> Result<Self, ParseError> {
+        let mut base_modifications = parser::parse(s, is_reverse_complemented, sequence)?;
+        parser::assign_probabilities(&mut base_modifications, probabilities)?;
+        Ok(base_modifications)
+    }
+
+    /// Reads the base modifications of an alignment record.
+    ///
+    /// This uses the base modifications (`MM`), base modification probabilities (`ML`), and
+    /// base modification sequence length (`MN`) data fields. If `MN` is set, it must match the
+    /// sequence length.
+    ///
+    /// This returns `None` if the record does not have base modifications.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::{
+    ///     record::data::field::Tag,
+    ///     record_buf::{data::field::{value::Array, Value}, Sequence},
+    ///     RecordBuf,
+    /// };
+    /// use noodles_sam::record::data::field::value::BaseModifications;
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_sequence(Sequence::from(b"CACCCGATGACCGGCT"))
+    ///     .set_data(
+    ///         [
+    ///             (Tag::BASE_MODIFICATIONS, Value::from("C+m,1,3;")),
+    ///             (
+    ///                 Tag::BASE_MODIFICATION_PROBABILITIES,
+    ///                 Value::Array(Array::UInt8(vec![200, 13])),
+    ///             ),
+    ///         ]
+    ///         .into_iter()
+    ///         .collect(),
+    ///     )
+    ///     .build();
+    ///
+    /// let base_modifications = BaseModifications::try_from_alignment_record(&record)?
+    ///     .expect("missing base modifications");
+    ///
+    /// let positions: Vec<_> = base_modifications.calls().map(|call| call.position()).collect();
+    /// assert_eq!(positions, [2, 11]);
+    ///
+    /// assert!(BaseModifications::try_from_alignment_record(&RecordBuf::default())?.is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn try_from_alignment_record(record: &dyn Record) -> io::Result<Option<Self>> {
+        let data = record.data();
+
+        let s = match data.get(&Tag::BASE_MODIFICATIONS).transpose()? {
+            Some(Value::String(s)) => s,
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid base modifications value type",
+                ))
+            }
+            None => return Ok(None),
+        };
+
+        let s =
+            std::str::from_utf8(s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let probabilities = match data
+            .get(&Tag::BASE_MODIFICATION_PROBABILITIES)
+            .transpose()?
+        {
+            Some(Value::Array(Array::UInt8(values))) => values.iter().collect::<io::Result<_>>()?,
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid base modification probabilities value type",
+                ))
+            }
+            None => Vec::new(),
+        };
+
+        let sequence: Sequence = record.sequence().iter().collect::<Vec<_>>().into();
+
+        if let Some(value) = data
+            .get(&Tag::BASE_MODIFICATION_SEQUENCE_LENGTH)
+            .transpose()?
+        {
+            let len = value
+                .as_int()
+                .and_then(|n| usize::try_from(n).ok())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "invalid base modification sequence length",
+                    )
+                })?;
+
+            if len != sequence.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "base modification sequence length mismatch",
+                ));
+            }
+        }
+
+        let is_reverse_complemented = record.flags()?.is_reverse_complemented();
+
+        let result = if probabilities.is_empty() {
+            Self::parse(s, is_reverse_complemented, &sequence)
+        } else {
+            Self::parse_with_probabilities(s, &probabilities, is_reverse_complemented, &sequence)
+        };
+
+        result
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns an iterator over all base modification calls.
+    pub fn calls(&self) -> impl Iterator<Item = Call> + '_ {
+        self.0.iter().flat_map(|group| group.calls())
+    }
 }
 
 impl AsRef<[Group]> for BaseModifications {
@@ -0,0 +1,162 @@
+use std::io;
+
+use noodles_core::Position;
+
+use super::group::{Modification, Strand, UnmodifiedBase};
+use crate::alignment::record::Cigar;
+
+/// A base modification call at a single sequence position.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Call {
+    position: usize,
+    unmodified_base: UnmodifiedBase,
+    strand: Strand,
+    modification: Modification,
+    probability: Option<u8>,
+}
+
+impl Call {
+    /// Creates a base modification call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::value::base_modifications::{
+    ///     group::{modification, Strand, UnmodifiedBase},
+    ///     Call,
+    /// };
+    ///
+    /// let call = Call::new(
+    ///     2,
+    ///     UnmodifiedBase::C,
+    ///     Strand::Forward,
+    ///     modification::FIVE_METHYLCYTOSINE,
+    ///     Some(200),
+    /// );
+    /// ```
+    pub fn new(
+        position: usize,
+        unmodified_base: UnmodifiedBase,
+        strand: Strand,
+        modification: Modification,
+        probability: Option<u8>,
+    ) -> Self {
+        Self {
+            position,
+            unmodified_base,
+            strand,
+            modification,
+            probability,
+        }
+    }
+
+    /// Returns the 0-based position on the sequence.
+    ///
+    /// This is relative to the sequence as stored in the record.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the unmodified base.
+    pub fn unmodified_base(&self) -> UnmodifiedBase {
+        self.unmodified_base
+    }
+
+    /// Returns the strand relative to the original read.
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the modification.
+    pub fn modification(&self) -> Modification {
+        self.modification
+    }
+
+    /// Returns the modification probability, scaled to [0, 255].
+    ///
+    /// This is `None` if the probability is unknown.
+    pub fn probability(&self) -> Option<u8> {
+        self.probability
+    }
+
+    /// Returns the strand of the modified base relative to the reference sequence.
+    ///
+    /// A modification on the read strand of a reverse complemented record is on the reverse
+    /// strand of the reference sequence, and vice versa.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::value::base_modifications::{
+    ///     group::{modification, Strand, UnmodifiedBase},
+    ///     Call,
+    /// };
+    ///
+    /// let call = Call::new(
+    ///     2,
+    ///     UnmodifiedBase::C,
+    ///     Strand::Forward,
+    ///     modification::FIVE_METHYLCYTOSINE,
+    ///     None,
+    /// );
+    ///
+    /// assert_eq!(call.reference_strand(false), Strand::Forward);
+    /// assert_eq!(call.reference_strand(true), Strand::Reverse);
+    /// ```
+    pub fn reference_strand(&self, is_reverse_complemented: bool) -> Strand {
+        reference_strand(self.strand, is_reverse_complemented)
+    }
+
+    /// Maps the modified base to a position on the reference sequence.
+    ///
+    /// This returns `None` if the base is not aligned, e.g., it is in an insertion or soft clip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::{
+    ///     alignment::{
+    ///         record::cigar::{op::Kind, Op},
+    ///         record_buf::Cigar,
+    ///     },
+    ///     record::data::field::value::base_modifications::{
+    ///         group::{modification, Strand, UnmodifiedBase},
+    ///         Call,
+    ///     },
+    /// };
+    ///
+    /// let call = Call::new(
+    ///     2,
+    ///     UnmodifiedBase::C,
+    ///     Strand::Forward,
+    ///     modification::FIVE_METHYLCYTOSINE,
+    ///     None,
+    /// );
+    ///
+    /// let cigar: Cigar = [Op::new(Kind::SoftClip, 1), Op::new(Kind::Match, 4)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let alignment_start = Position::try_from(8)?;
+    /// assert_eq!(call.reference_position(&cigar, alignment_start)?, Position::new(9));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reference_position<C>(
+        &self,
+        cigar: &C,
+        alignment_start: Position,
+    ) -> io::Result<Option<Position>>
+    where
+        C: Cigar + ?Sized,
+    {
+        cigar.reference_position(alignment_start, self.position)
+    }
+}
+
+pub(super) fn reference_strand(strand: Strand, is_reverse_complemented: bool) -> Strand {
+    match (strand, is_reverse_complemented) {
+        (Strand::Forward, false) | (Strand::Reverse, true) => Strand::Forward,
+        (Strand::Forward, true) | (Strand::Reverse, false) => Strand::Reverse,
+    }
+}
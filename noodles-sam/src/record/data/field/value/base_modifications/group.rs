@@ -9,6 +9,8 @@ pub use self::{
     modification::Modification, status::Status, strand::Strand, unmodified_base::UnmodifiedBase,
 };
 
+use super::Call;
+
 /// A base modifications group.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Group {
@@ -17,6 +19,7 @@ pub struct Group {
     modifications: Vec<Modification>,
     status: Option<Status>,
     positions: Vec<usize>,
+    probabilities: Vec<u8>,
 }
 
 impl Group {
@@ -34,6 +37,7 @@ impl Group {
             modifications,
             status,
             positions,
+            probabilities: Vec::new(),
         }
     }
 
@@ -61,4 +65,70 @@ impl Group {
     pub fn positions(&self) -> &[usize] {
         &self.positions
     }
+
+    /// Returns the modification probabilities.
+    ///
+    /// These are the raw values of the base modification probabilities (`ML`) data field for
+    /// this group, where the probability range [0.0, 1.0] is scaled to [0, 255]. There is one
+    /// probability per position per modification, ordered by position then modification. This
+    /// is empty if the probabilities are unknown.
+    pub fn probabilities(&self) -> &[u8] {
+        &self.probabilities
+    }
+
+    /// Returns an iterator over the base modification calls.
+    ///
+    /// There is one call per position per modification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::value::base_modifications::group::{
+    ///     modification, Strand, UnmodifiedBase,
+    /// };
+    /// use noodles_sam::record::data::field::value::base_modifications::Group;
+    ///
+    /// let group = Group::new(
+    ///     UnmodifiedBase::C,
+    ///     Strand::Forward,
+    ///     vec![modification::FIVE_METHYLCYTOSINE],
+    ///     None,
+    ///     vec![2, 11],
+    /// );
+    ///
+    /// let positions: Vec<_> = group.calls().map(|call| call.position()).collect();
+    /// assert_eq!(positions, [2, 11]);
+    /// ```
+    pub fn calls(&self) -> impl Iterator<Item = Call> + '_ {
+        let modification_count = self.modifications.len();
+
+        self.positions
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, &position)| {
+                self.modifications
+                    .iter()
+                    .enumerate()
+                    .map(move |(j, &modification)| {
+                        let probability =
+                            self.probabilities.get(i * modification_count + j).copied();
+
+                        Call::new(
+                            position,
+                            self.unmodified_base,
+                            self.strand,
+                            modification,
+                            probability,
+                        )
+                    })
+            })
+    }
+
+    pub(super) fn call_count(&self) -> usize {
+        self.positions.len() * self.modifications.len()
+    }
+
+    pub(super) fn set_probabilities(&mut self, probabilities: Vec<u8>) {
+        self.probabilities = probabilities;
+    }
 }
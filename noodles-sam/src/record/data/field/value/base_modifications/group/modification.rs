@@ -33,7 +33,7 @@ pub const EIGHT_OXOGUANINE: Modification = Modification::Code(b'o');
 pub const XANTHOSINE: Modification = Modification::Code(b'n');
 
 /// A base modification.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Modification {
     /// A modification code.
     Code(u8),
@@ -1,7 +1,7 @@
 use std::{error, fmt};
 
 /// The strand on which the modification was observed.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Strand {
     /// The same strand.
     Forward,
@@ -36,6 +36,25 @@ impl UnmodifiedBase {
             Self::N => Self::N,
         }
     }
+
+    /// Returns whether the given sequence base matches this unmodified base.
+    ///
+    /// `N` matches any base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::value::base_modifications::group::UnmodifiedBase;
+    /// assert!(UnmodifiedBase::C.matches(b'C'));
+    /// assert!(!UnmodifiedBase::C.matches(b'G'));
+    /// assert!(UnmodifiedBase::N.matches(b'G'));
+    /// ```
+    pub fn matches(&self, base: u8) -> bool {
+        match self {
+            Self::N => true,
+            _ => u8::from(*self) == base,
+        }
+    }
 }
 
 /// An error returned when a base modifications group unmodified base fails to parse.
@@ -9,13 +9,17 @@ use crate::alignment::record_buf::Sequence;
 /// An error returned when base modifications fail to parse.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
+    /// A group is invalid.
     InvalidGroup(group::ParseError),
+    /// The number of probabilities does not match the number of calls.
+    InvalidProbabilities,
 }
 
 impl error::Error for ParseError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::InvalidGroup(e) => Some(e),
+            Self::InvalidProbabilities => None,
         }
     }
 }
@@ -24,6 +28,7 @@ impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::InvalidGroup(_) => write!(f, "invalid group"),
+            Self::InvalidProbabilities => write!(f, "invalid probabilities"),
         }
     }
 }
@@ -46,6 +51,27 @@ pub(super) fn parse(
     Ok(BaseModifications(groups))
 }
 
+pub(super) fn assign_probabilities(
+    base_modifications: &mut BaseModifications,
+    probabilities: &[u8],
+) -> Result<(), ParseError> {
+    let call_count: usize = base_modifications.0.iter().map(|g| g.call_count()).sum();
+
+    if probabilities.len() != call_count {
+        return Err(ParseError::InvalidProbabilities);
+    }
+
+    let mut src = probabilities;
+
+    for group in &mut base_modifications.0 {
+        let (buf, rest) = src.split_at(group.call_count());
+        group.set_probabilities(buf.to_vec());
+        src = rest;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
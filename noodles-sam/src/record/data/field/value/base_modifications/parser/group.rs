@@ -30,6 +30,8 @@ pub enum ParseError {
     InvalidStatus,
     /// A skip count is invalid.
     InvalidSkipCount(lexical_core::Error),
+    /// A skip count skips past the end of the sequence.
+    SkipCountOutOfBounds,
     /// The terminator is invalid.
     InvalidTerminator,
 }
@@ -55,6 +57,7 @@ impl fmt::Display for ParseError {
             Self::InvalidModifications(_) => write!(f, "invalid modifications"),
             Self::InvalidStatus => write!(f, "invalid status"),
             Self::InvalidSkipCount(_) => write!(f, "invalid skip count"),
+            Self::SkipCountOutOfBounds => write!(f, "skip count out of bounds"),
             Self::InvalidTerminator => write!(f, "invalid terminator"),
         }
     }
@@ -136,7 +139,7 @@ fn decode_positions(
     let mut positions = Vec::with_capacity(skip_counts.len());
 
     let mut iter: Box<dyn Iterator<Item = usize>> = if is_reverse_complemented {
-        let unmodified_base = u8::from(unmodified_base.complement());
+        let unmodified_base = unmodified_base.complement();
 
         Box::new(
             sequence
@@ -144,24 +147,22 @@ fn decode_positions(
                 .iter()
                 .enumerate()
                 .rev()
-                .filter(move |(_, &base)| base == unmodified_base)
+                .filter(move |(_, &base)| unmodified_base.matches(base))
                 .map(|(i, _)| i),
         )
     } else {
-        let unmodified_base = u8::from(unmodified_base);
-
         Box::new(
             sequence
                 .as_ref()
                 .iter()
                 .enumerate()
-                .filter(move |(_, &base)| base == unmodified_base)
+                .filter(move |(_, &base)| unmodified_base.matches(base))
                 .map(|(i, _)| i),
         )
     };
 
     for &count in skip_counts {
-        let i = iter.nth(count).unwrap();
+        let i = iter.nth(count).ok_or(ParseError::SkipCountOutOfBounds)?;
         positions.push(i);
     }
 
@@ -237,6 +238,23 @@ mod tests {
             parse_group(&mut src, is_reverse_complemented, &sequence),
             Err(ParseError::InvalidSkipCount(_))
         ));
+
+        let mut src = &b"N+n,3,10;"[..];
+        let actual = parse_group(&mut src, is_reverse_complemented, &sequence);
+        let expected = Group::new(
+            UnmodifiedBase::N,
+            Strand::Forward,
+            vec![modification::XANTHOSINE],
+            None,
+            vec![3, 14],
+        );
+        assert_eq!(actual, Ok(expected));
+
+        let mut src = &b"A+a,3;"[..];
+        assert_eq!(
+            parse_group(&mut src, is_reverse_complemented, &sequence),
+            Err(ParseError::SkipCountOutOfBounds)
+        );
     }
 
     #[test]
@@ -0,0 +1,407 @@
+//! Base modification pileup.
+
+use std::{collections::BTreeMap, io};
+
+use noodles_core::Position;
+
+use super::{
+    call::reference_strand,
+    group::{Modification, Status, Strand},
+    BaseModifications,
+};
+use crate::{
+    alignment::{
+        record::{cigar::AlignedBlock, Cigar as _},
+        Record,
+    },
+    Header,
+};
+
+/// A reference sequence site of a base modification.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Site {
+    reference_sequence_id: usize,
+    position: Position,
+    strand: Strand,
+    modification: Modification,
+}
+
+impl Site {
+    /// Creates a site.
+    pub fn new(
+        reference_sequence_id: usize,
+        position: Position,
+        strand: Strand,
+        modification: Modification,
+    ) -> Self {
+        Self {
+            reference_sequence_id,
+            position,
+            strand,
+            modification,
+        }
+    }
+
+    /// Returns the reference sequence ID.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the position on the reference sequence.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the strand relative to the reference sequence.
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the modification.
+    pub fn modification(&self) -> Modification {
+        self.modification
+    }
+}
+
+/// Counts of base modification calls at a site.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Counts {
+    total: u64,
+    modified: u64,
+}
+
+impl Counts {
+    /// Returns the number of calls.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns the number of calls that are modified.
+    pub fn modified(&self) -> u64 {
+        self.modified
+    }
+
+    /// Returns the fraction of calls that are modified.
+    ///
+    /// This returns `None` if there are no calls.
+    pub fn frequency(&self) -> Option<f64> {
+        if self.total == 0 {
+            None
+        } else {
+            Some(self.modified as f64 / self.total as f64)
+        }
+    }
+}
+
+/// A pileup of base modification calls over reference sequence positions.
+///
+/// Calls with a probability greater than or equal to the minimum probability are counted as
+/// modified. Calls without a probability are counted as modified. When a group uses implicit
+/// status (`.` or none), matching bases that are not listed are counted as unmodified.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::{
+///     alignment::{
+///         record::{cigar::{op::Kind, Op}, data::field::Tag, Flags},
+///         record_buf::{data::field::Value, Sequence},
+///         RecordBuf,
+///     },
+///     header::record::value::{map::ReferenceSequence, Map},
+///     record::data::field::value::base_modifications::{
+///         group::{modification, Strand},
+///         pileup::Site,
+///         Pileup,
+///     },
+/// };
+///
+/// let header = noodles_sam::Header::builder()
+///     .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(8.try_into()?))
+///     .build();
+///
+/// let record = RecordBuf::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(0)
+///     .set_alignment_start(Position::MIN)
+///     .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+///     .set_sequence(Sequence::from(b"ACGC"))
+///     .set_data([(Tag::BASE_MODIFICATIONS, Value::from("C+m,0;"))].into_iter().collect())
+///     .build();
+///
+/// let mut pileup = Pileup::new(128);
+/// pileup.add_record(&header, &record)?;
+///
+/// let site = Site::new(0, Position::try_from(2)?, Strand::Forward, modification::FIVE_METHYLCYTOSINE);
+/// assert_eq!(pileup.get(&site).and_then(|counts| counts.frequency()), Some(1.0));
+///
+/// let site = Site::new(0, Position::try_from(4)?, Strand::Forward, modification::FIVE_METHYLCYTOSINE);
+/// assert_eq!(pileup.get(&site).and_then(|counts| counts.frequency()), Some(0.0));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Pileup {
+    min_probability: u8,
+    sites: BTreeMap<Site, Counts>,
+}
+
+impl Pileup {
+    /// Creates a base modification pileup.
+    ///
+    /// The minimum probability is scaled to [0, 255], the same as the base modification
+    /// probabilities (`ML`) data field.
+    pub fn new(min_probability: u8) -> Self {
+        Self {
+            min_probability,
+            sites: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of sites.
+    pub fn len(&self) -> usize {
+        self.sites.len()
+    }
+
+    /// Returns whether there are any sites.
+    pub fn is_empty(&self) -> bool {
+        self.sites.is_empty()
+    }
+
+    /// Returns the counts at the given site.
+    pub fn get(&self, site: &Site) -> Option<&Counts> {
+        self.sites.get(site)
+    }
+
+    /// Returns an iterator over sites and counts, in site order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Site, &Counts)> {
+        self.sites.iter()
+    }
+
+    /// Adds the base modification calls of a record.
+    ///
+    /// Unmapped records and records without base modifications are ignored. Calls on bases that
+    /// are not aligned to the reference sequence are also ignored.
+    pub fn add_record(&mut self, header: &Header, record: &dyn Record) -> io::Result<()> {
+        let flags = record.flags()?;
+
+        if flags.is_unmapped() {
+            return Ok(());
+        }
+
+        let (Some(reference_sequence_id), Some(alignment_start)) = (
+            record.reference_sequence_id(header).transpose()?,
+            record.alignment_start().transpose()?,
+        ) else {
+            return Ok(());
+        };
+
+        let Some(base_modifications) = BaseModifications::try_from_alignment_record(record)? else {
+            return Ok(());
+        };
+
+        let is_reverse_complemented = flags.is_reverse_complemented();
+
+        // The aligned blocks are in read order, which allows reference positions to be found by
+        // binary search and read bases to be walked in a single pass.
+        let aligned_blocks: Vec<_> = record
+            .cigar()
+            .aligned_blocks(alignment_start)
+            .collect::<io::Result<_>>()?;
+
+        let sequence: Vec<_> = record.sequence().iter().collect();
+
+        for group in base_modifications.as_ref() {
+            for call in group.calls() {
+                let Some(position) = reference_position(&aligned_blocks, call.position()) else {
+                    continue;
+                };
+
+                let site = Site::new(
+                    reference_sequence_id,
+                    position,
+                    call.reference_strand(is_reverse_complemented),
+                    call.modification(),
+                );
+
+                let is_modified = call
+                    .probability()
+                    .map(|p| p >= self.min_probability)
+                    .unwrap_or(true);
+
+                let counts = self.sites.entry(site).or_default();
+                counts.total += 1;
+
+                if is_modified {
+                    counts.modified += 1;
+                }
+            }
+
+            if matches!(group.status(), Some(Status::Explicit)) {
+                continue;
+            }
+
+            let strand = reference_strand(group.strand(), is_reverse_complemented);
+
+            let unmodified_base = if is_reverse_complemented {
+                group.unmodified_base().complement()
+            } else {
+                group.unmodified_base()
+            };
+
+            let mut called_positions = group.positions().to_vec();
+            called_positions.sort_unstable();
+            let mut called_positions = called_positions.into_iter().peekable();
+
+            for block in &aligned_blocks {
+                let query_end = block.query_start() + block.len();
+                let reference_start = usize::from(block.reference_start());
+
+                for (i, &base) in sequence
+                    .iter()
+                    .enumerate()
+                    .take(query_end)
+                    .skip(block.query_start())
+                {
+                    while called_positions.next_if(|&j| j < i).is_some() {}
+
+                    if !unmodified_base.matches(base) || called_positions.next_if_eq(&i).is_some() {
+                        continue;
+                    }
+
+                    let Some(position) = Position::new(reference_start + (i - block.query_start()))
+                    else {
+                        continue;
+                    };
+
+                    for &modification in group.modifications() {
+                        let site = Site::new(reference_sequence_id, position, strand, modification);
+                        self.sites.entry(site).or_default().total += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn reference_position(aligned_blocks: &[AlignedBlock], query_offset: usize) -> Option<Position> {
+    let i = aligned_blocks.partition_point(|block| block.query_start() <= query_offset);
+    let block = aligned_blocks.get(i.checked_sub(1)?)?;
+    block.reference_position(query_offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alignment::{
+            record::{
+                cigar::{op::Kind, Op},
+                data::field::Tag,
+                Flags,
+            },
+            record_buf::{
+                data::field::{value::Array, Value},
+                Sequence,
+            },
+            RecordBuf,
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+        record::data::field::value::base_modifications::group::modification,
+    };
+
+    #[test]
+    fn test_add_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(13.try_into()?))
+            .build();
+
+        let build_record = |flags, probabilities: Vec<u8>| {
+            RecordBuf::builder()
+                .set_flags(flags)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(5).unwrap())
+                .set_cigar(
+                    [Op::new(Kind::SoftClip, 1), Op::new(Kind::Match, 5)]
+                        .into_iter()
+                        .collect(),
+                )
+                .set_sequence(Sequence::from(b"CACGCG"))
+                .set_data(
+                    [
+                        (Tag::BASE_MODIFICATIONS, Value::from("C+m?,1;")),
+                        (
+                            Tag::BASE_MODIFICATION_PROBABILITIES,
+                            Value::Array(Array::UInt8(probabilities)),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                )
+                .build()
+        };
+
+        let mut pileup = Pileup::new(128);
+        pileup.add_record(&header, &build_record(Flags::empty(), vec![200]))?;
+        pileup.add_record(&header, &build_record(Flags::empty(), vec![13]))?;
+
+        // original orientation: CGCGTG
+        pileup.add_record(
+            &header,
+            &build_record(Flags::REVERSE_COMPLEMENTED, vec![250]),
+        )?;
+
+        assert_eq!(pileup.len(), 2);
+
+        let site = Site::new(
+            0,
+            Position::try_from(6)?,
+            Strand::Forward,
+            modification::FIVE_METHYLCYTOSINE,
+        );
+        let counts = pileup.get(&site).copied().unwrap_or_default();
+        assert_eq!(counts.total(), 2);
+        assert_eq!(counts.modified(), 1);
+        assert_eq!(counts.frequency(), Some(0.5));
+
+        let site = Site::new(
+            0,
+            Position::try_from(6)?,
+            Strand::Reverse,
+            modification::FIVE_METHYLCYTOSINE,
+        );
+        assert!(pileup.get(&site).is_none());
+
+        let site = Site::new(
+            0,
+            Position::try_from(7)?,
+            Strand::Reverse,
+            modification::FIVE_METHYLCYTOSINE,
+        );
+        let counts = pileup.get(&site).copied().unwrap_or_default();
+        assert_eq!(counts.total(), 1);
+        assert_eq!(counts.modified(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reference_position() -> Result<(), noodles_core::position::TryFromIntError> {
+        // 2S4M3D5M
+        let aligned_blocks = [
+            AlignedBlock::new(2, Position::try_from(8)?, 4),
+            AlignedBlock::new(6, Position::try_from(15)?, 5),
+        ];
+
+        assert_eq!(reference_position(&aligned_blocks, 1), None);
+        assert_eq!(reference_position(&aligned_blocks, 2), Position::new(8));
+        assert_eq!(reference_position(&aligned_blocks, 5), Position::new(11));
+        assert_eq!(reference_position(&aligned_blocks, 6), Position::new(15));
+        assert_eq!(reference_position(&aligned_blocks, 10), Position::new(19));
+        assert_eq!(reference_position(&aligned_blocks, 11), None);
+
+        Ok(())
+    }
+}
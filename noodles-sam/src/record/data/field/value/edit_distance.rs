@@ -0,0 +1,78 @@
+//! SAM record data field value for the edit distance.
+
+use std::io;
+
+use super::typed_value::{get_int, invalid_value, TypedValue};
+use crate::alignment::{
+    record::data::field::{Tag, Value},
+    record_buf::data::field::Value as ValueBuf,
+};
+
+/// The edit distance to the reference (`NM`).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct EditDistance(u32);
+
+impl From<u32> for EditDistance {
+    fn from(n: u32) -> Self {
+        Self(n)
+    }
+}
+
+impl From<EditDistance> for u32 {
+    fn from(edit_distance: EditDistance) -> Self {
+        edit_distance.0
+    }
+}
+
+impl TypedValue for EditDistance {
+    const TAG: Tag = Tag::EDIT_DISTANCE;
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        let n = get_int(Self::TAG, &value)?;
+
+        u32::try_from(n)
+            .map(Self)
+            .map_err(|_| invalid_value(io::ErrorKind::InvalidData, Self::TAG))
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        Ok(i32::try_from(self.0)
+            .map(ValueBuf::Int32)
+            .unwrap_or(ValueBuf::UInt32(self.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_value() -> io::Result<()> {
+        assert_eq!(
+            EditDistance::try_from_value(Value::UInt8(2))?,
+            EditDistance(2)
+        );
+
+        assert!(matches!(
+            EditDistance::try_from_value(Value::Int8(-1)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            EditDistance::try_from_value(Value::Float(2.0)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_into_value() -> io::Result<()> {
+        assert_eq!(EditDistance(2).try_into_value()?, ValueBuf::Int32(2));
+        assert_eq!(
+            EditDistance(u32::MAX).try_into_value()?,
+            ValueBuf::UInt32(u32::MAX)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+//! SAM record data field value for the mate CIGAR.
+
+use std::io;
+
+use super::typed_value::{get_string, invalid_value, TypedValue};
+use crate::{
+    alignment::{
+        record::data::field::{Tag, Value},
+        record_buf::{data::field::Value as ValueBuf, Cigar},
+    },
+    io::{reader::record_buf::parse_cigar, writer::record::write_cigar},
+};
+
+/// The CIGAR operations of the mate (`MC`).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MateCigar(Cigar);
+
+impl AsRef<Cigar> for MateCigar {
+    fn as_ref(&self) -> &Cigar {
+        &self.0
+    }
+}
+
+impl From<Cigar> for MateCigar {
+    fn from(cigar: Cigar) -> Self {
+        Self(cigar)
+    }
+}
+
+impl From<MateCigar> for Cigar {
+    fn from(mate_cigar: MateCigar) -> Self {
+        mate_cigar.0
+    }
+}
+
+impl TypedValue for MateCigar {
+    const TAG: Tag = Tag::MATE_CIGAR;
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        let s = get_string(Self::TAG, &value)?;
+
+        let mut cigar = Cigar::default();
+        parse_cigar(s, &mut cigar)
+            .map_err(|_| invalid_value(io::ErrorKind::InvalidData, Self::TAG))?;
+
+        Ok(Self(cigar))
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        if self.0.as_ref().is_empty() {
+            return Err(invalid_value(io::ErrorKind::InvalidInput, Self::TAG));
+        }
+
+        let mut buf = Vec::new();
+        write_cigar(&mut buf, &self.0)?;
+
+        String::from_utf8(buf)
+            .map(ValueBuf::from)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::BStr;
+
+    use super::*;
+    use crate::alignment::record::cigar::{op::Kind, Op};
+
+    #[test]
+    fn test_try_from_value() -> io::Result<()> {
+        let expected = MateCigar::from(
+            [Op::new(Kind::SoftClip, 4), Op::new(Kind::Match, 8)]
+                .into_iter()
+                .collect::<Cigar>(),
+        );
+
+        assert_eq!(
+            MateCigar::try_from_value(Value::String(BStr::new("4S8M")))?,
+            expected
+        );
+
+        assert!(matches!(
+            MateCigar::try_from_value(Value::String(BStr::new("4S8"))),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_into_value() -> io::Result<()> {
+        let mate_cigar = MateCigar::from(
+            [Op::new(Kind::SoftClip, 4), Op::new(Kind::Match, 8)]
+                .into_iter()
+                .collect::<Cigar>(),
+        );
+
+        assert_eq!(mate_cigar.try_into_value()?, ValueBuf::from("4S8M"));
+        assert!(MateCigar::default().try_into_value().is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+//! SAM record data field value for mismatched positions.
+
+use std::{error, fmt, io, str::FromStr};
+
+use super::typed_value::{get_string, TypedValue};
+use crate::alignment::{
+    record::data::field::{Tag, Value},
+    record_buf::data::field::Value as ValueBuf,
+};
+
+const DELETION_PREFIX: char = '^';
+
+/// A mismatched positions operation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Op {
+    /// A run of reference bases that match the read.
+    Match(usize),
+    /// A reference base that mismatches the read.
+    Mismatch(u8),
+    /// Reference bases that are deleted from the read.
+    Deletion(Vec<u8>),
+}
+
+/// The mismatched and deleted reference bases (`MD`).
+///
+/// Parsed operations do not include zero-length matches, which are only used as separators in the
+/// raw value.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MismatchedPositions(Vec<Op>);
+
+impl AsRef<[Op]> for MismatchedPositions {
+    fn as_ref(&self) -> &[Op] {
+        &self.0
+    }
+}
+
+impl AsMut<Vec<Op>> for MismatchedPositions {
+    fn as_mut(&mut self) -> &mut Vec<Op> {
+        &mut self.0
+    }
+}
+
+impl From<Vec<Op>> for MismatchedPositions {
+    fn from(ops: Vec<Op>) -> Self {
+        Self(ops)
+    }
+}
+
+impl From<MismatchedPositions> for Vec<Op> {
+    fn from(mismatched_positions: MismatchedPositions) -> Self {
+        mismatched_positions.0
+    }
+}
+
+/// An error returned when mismatched positions fail to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// A match length is missing.
+    MissingMatchLength,
+    /// A match length is invalid.
+    InvalidMatchLength,
+    /// A base is invalid.
+    InvalidBase,
+    /// A deletion is empty.
+    EmptyDeletion,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty input"),
+            Self::MissingMatchLength => write!(f, "missing match length"),
+            Self::InvalidMatchLength => write!(f, "invalid match length"),
+            Self::InvalidBase => write!(f, "invalid base"),
+            Self::EmptyDeletion => write!(f, "empty deletion"),
+        }
+    }
+}
+
+impl fmt::Display for MismatchedPositions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Every mismatch and deletion is surrounded by match lengths, which may be 0.
+        let mut match_len = 0;
+
+        for op in &self.0 {
+            match op {
+                Op::Match(n) => match_len += n,
+                Op::Mismatch(base) => {
+                    write!(f, "{match_len}{}", char::from(*base))?;
+                    match_len = 0;
+                }
+                Op::Deletion(bases) => {
+                    write!(f, "{match_len}{DELETION_PREFIX}")?;
+
+                    for &base in bases {
+                        write!(f, "{}", char::from(base))?;
+                    }
+
+                    match_len = 0;
+                }
+            }
+        }
+
+        write!(f, "{match_len}")
+    }
+}
+
+impl FromStr for MismatchedPositions {
+    type Err = ParseError;
+
+    // `[0-9]+(([A-Z]|\^[A-Z]+)[0-9]+)*`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut src = s.as_bytes();
+        let mut ops = Vec::new();
+
+        loop {
+            let n = parse_match_len(&mut src)?;
+
+            if n > 0 {
+                ops.push(Op::Match(n));
+            }
+
+            let Some((&b, rest)) = src.split_first() else {
+                break;
+            };
+
+            src = rest;
+
+            if char::from(b) == DELETION_PREFIX {
+                let len = src.iter().take_while(|b| b.is_ascii_uppercase()).count();
+
+                if len == 0 {
+                    return Err(ParseError::EmptyDeletion);
+                }
+
+                let (bases, rest) = src.split_at(len);
+                ops.push(Op::Deletion(bases.to_vec()));
+                src = rest;
+            } else if b.is_ascii_uppercase() {
+                ops.push(Op::Mismatch(b));
+            } else {
+                return Err(ParseError::InvalidBase);
+            }
+        }
+
+        Ok(Self(ops))
+    }
+}
+
+fn parse_match_len(src: &mut &[u8]) -> Result<usize, ParseError> {
+    let len = src.iter().take_while(|b| b.is_ascii_digit()).count();
+
+    if len == 0 {
+        return Err(ParseError::MissingMatchLength);
+    }
+
+    let (buf, rest) = src.split_at(len);
+    *src = rest;
+
+    lexical_core::parse(buf).map_err(|_| ParseError::InvalidMatchLength)
+}
+
+impl TypedValue for MismatchedPositions {
+    const TAG: Tag = Tag::MISMATCHED_POSITIONS;
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        let s = get_string(Self::TAG, &value)?;
+
+        std::str::from_utf8(s)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        for op in &self.0 {
+            let is_valid = match op {
+                Op::Match(_) => true,
+                Op::Mismatch(base) => base.is_ascii_uppercase(),
+                Op::Deletion(bases) => {
+                    if bases.is_empty() {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            ParseError::EmptyDeletion,
+                        ));
+                    }
+
+                    bases.iter().all(|b| b.is_ascii_uppercase())
+                }
+            };
+
+            if !is_valid {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    ParseError::InvalidBase,
+                ));
+            }
+        }
+
+        Ok(ValueBuf::from(self.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let mismatched_positions = MismatchedPositions::from(vec![
+            Op::Mismatch(b'A'),
+            Op::Match(2),
+            Op::Deletion(b"GT".to_vec()),
+            Op::Match(1),
+            Op::Mismatch(b'C'),
+            Op::Mismatch(b'T'),
+            Op::Match(2),
+        ]);
+
+        assert_eq!(mismatched_positions.to_string(), "0A2^GT1C0T2");
+        assert_eq!(MismatchedPositions::default().to_string(), "0");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "0A2^GT1C0T2".parse(),
+            Ok(MismatchedPositions::from(vec![
+                Op::Mismatch(b'A'),
+                Op::Match(2),
+                Op::Deletion(b"GT".to_vec()),
+                Op::Match(1),
+                Op::Mismatch(b'C'),
+                Op::Mismatch(b'T'),
+                Op::Match(2),
+            ]))
+        );
+
+        assert_eq!(
+            "8".parse(),
+            Ok(MismatchedPositions::from(vec![Op::Match(8)]))
+        );
+
+        assert_eq!("".parse::<MismatchedPositions>(), Err(ParseError::Empty));
+        assert_eq!(
+            "A8".parse::<MismatchedPositions>(),
+            Err(ParseError::MissingMatchLength)
+        );
+        assert_eq!(
+            "8A".parse::<MismatchedPositions>(),
+            Err(ParseError::MissingMatchLength)
+        );
+        assert_eq!(
+            "8^4".parse::<MismatchedPositions>(),
+            Err(ParseError::EmptyDeletion)
+        );
+        assert_eq!(
+            "8a4".parse::<MismatchedPositions>(),
+            Err(ParseError::InvalidBase)
+        );
+    }
+
+    #[test]
+    fn test_try_into_value() -> io::Result<()> {
+        let mismatched_positions =
+            MismatchedPositions::from(vec![Op::Match(3), Op::Mismatch(b'C'), Op::Match(4)]);
+        assert_eq!(
+            mismatched_positions.try_into_value()?,
+            ValueBuf::from("3C4")
+        );
+
+        let mismatched_positions = MismatchedPositions::from(vec![Op::Deletion(Vec::new())]);
+        assert!(matches!(
+            mismatched_positions.try_into_value(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+//! SAM record data field value for the original quality scores.
+
+use std::io;
+
+use super::typed_value::{get_string, invalid_value, TypedValue};
+use crate::alignment::{
+    record::data::field::{Tag, Value},
+    record_buf::{data::field::Value as ValueBuf, QualityScores},
+};
+
+const OFFSET: u8 = b'!';
+const MAX_SCORE: u8 = b'~' - OFFSET;
+
+/// The original base quality scores (`OQ`).
+///
+/// The scores are stored as raw Phred quality scores, i.e., without the `!` offset.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OriginalQualityScores(QualityScores);
+
+impl AsRef<QualityScores> for OriginalQualityScores {
+    fn as_ref(&self) -> &QualityScores {
+        &self.0
+    }
+}
+
+impl From<QualityScores> for OriginalQualityScores {
+    fn from(quality_scores: QualityScores) -> Self {
+        Self(quality_scores)
+    }
+}
+
+impl From<OriginalQualityScores> for QualityScores {
+    fn from(original_quality_scores: OriginalQualityScores) -> Self {
+        original_quality_scores.0
+    }
+}
+
+impl TypedValue for OriginalQualityScores {
+    const TAG: Tag = Tag::ORIGINAL_QUALITY_SCORES;
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        let s = get_string(Self::TAG, &value)?;
+
+        if s.is_empty() || !s.iter().all(|b| b.is_ascii_graphic()) {
+            return Err(invalid_value(io::ErrorKind::InvalidData, Self::TAG));
+        }
+
+        let scores: Vec<_> = s.iter().map(|&b| b - OFFSET).collect();
+
+        Ok(Self(QualityScores::from(scores)))
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        let scores = self.0.as_ref();
+
+        if scores.is_empty() || scores.iter().any(|&n| n > MAX_SCORE) {
+            return Err(invalid_value(io::ErrorKind::InvalidInput, Self::TAG));
+        }
+
+        let s: String = scores.iter().map(|&n| char::from(n + OFFSET)).collect();
+
+        Ok(ValueBuf::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::BStr;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from_value() -> io::Result<()> {
+        assert_eq!(
+            OriginalQualityScores::try_from_value(Value::String(BStr::new("NDLS")))?,
+            OriginalQualityScores::from(QualityScores::from(vec![45, 35, 43, 50]))
+        );
+
+        assert!(matches!(
+            OriginalQualityScores::try_from_value(Value::String(BStr::new("ND S"))),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_into_value() -> io::Result<()> {
+        let original_quality_scores =
+            OriginalQualityScores::from(QualityScores::from(vec![45, 35, 43, 50]));
+        assert_eq!(
+            original_quality_scores.try_into_value()?,
+            ValueBuf::from("NDLS")
+        );
+
+        let original_quality_scores = OriginalQualityScores::from(QualityScores::from(vec![94]));
+        assert!(matches!(
+            original_quality_scores.try_into_value(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,459 @@
+//! SAM record data field value for other canonical alignments in a chimeric alignment.
+
+use std::{error, fmt, io, str::FromStr};
+
+use bstr::{BStr, BString};
+use noodles_core::Position;
+
+use super::typed_value::{get_string, TypedValue};
+use crate::{
+    alignment::{
+        record::{data::field::Tag, data::field::Value, MappingQuality},
+        record_buf::{data::field::Value as ValueBuf, Cigar},
+        Record,
+    },
+    io::{reader::record_buf::parse_cigar, writer::record::write_cigar},
+};
+
+const ALIGNMENT_DELIMITER: char = ';';
+const FIELD_DELIMITER: char = ',';
+const MISSING_MAPPING_QUALITY: u8 = 255;
+
+/// An other canonical alignment in a chimeric alignment.
+///
+/// This is an entry in the other alignments (`SA`) data field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OtherAlignment {
+    reference_sequence_name: BString,
+    position: Position,
+    is_reverse_complemented: bool,
+    cigar: Cigar,
+    mapping_quality: Option<MappingQuality>,
+    edit_distance: u32,
+}
+
+impl OtherAlignment {
+    /// Creates an other alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::{
+    ///     alignment::{
+    ///         record::{cigar::{op::Kind, Op}, MappingQuality},
+    ///         record_buf::Cigar,
+    ///     },
+    ///     record::data::field::value::other_alignments::OtherAlignment,
+    /// };
+    ///
+    /// let alignment = OtherAlignment::new(
+    ///     "sq0",
+    ///     Position::try_from(8)?,
+    ///     false,
+    ///     [Op::new(Kind::Match, 4)].into_iter().collect(),
+    ///     MappingQuality::new(13),
+    ///     0,
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn new<N>(
+        reference_sequence_name: N,
+        position: Position,
+        is_reverse_complemented: bool,
+        cigar: Cigar,
+        mapping_quality: Option<MappingQuality>,
+        edit_distance: u32,
+    ) -> Self
+    where
+        N: Into<BString>,
+    {
+        Self {
+            reference_sequence_name: reference_sequence_name.into(),
+            position,
+            is_reverse_complemented,
+            cigar,
+            mapping_quality,
+            edit_distance,
+        }
+    }
+
+    /// Returns the reference sequence name.
+    pub fn reference_sequence_name(&self) -> &BStr {
+        self.reference_sequence_name.as_ref()
+    }
+
+    /// Returns the alignment start position.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns whether the alignment is on the reverse strand.
+    pub fn is_reverse_complemented(&self) -> bool {
+        self.is_reverse_complemented
+    }
+
+    /// Returns the CIGAR operations.
+    pub fn cigar(&self) -> &Cigar {
+        &self.cigar
+    }
+
+    /// Returns the mapping quality.
+    pub fn mapping_quality(&self) -> Option<MappingQuality> {
+        self.mapping_quality
+    }
+
+    /// Returns the edit distance (`NM`).
+    pub fn edit_distance(&self) -> u32 {
+        self.edit_distance
+    }
+}
+
+impl fmt::Display for OtherAlignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let strand = if self.is_reverse_complemented {
+            '-'
+        } else {
+            '+'
+        };
+
+        let mut cigar = Vec::new();
+        write_cigar(&mut cigar, &self.cigar).map_err(|_| fmt::Error)?;
+
+        let mapping_quality = self
+            .mapping_quality
+            .map(u8::from)
+            .unwrap_or(MISSING_MAPPING_QUALITY);
+
+        write!(
+            f,
+            "{}{FIELD_DELIMITER}{}{FIELD_DELIMITER}{strand}{FIELD_DELIMITER}{}{FIELD_DELIMITER}{mapping_quality}{FIELD_DELIMITER}{}",
+            self.reference_sequence_name,
+            self.position,
+            BStr::new(&cigar),
+            self.edit_distance,
+        )
+    }
+}
+
+/// An error returned when other alignments fail to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// A field is missing.
+    MissingField,
+    /// The reference sequence name is invalid.
+    InvalidReferenceSequenceName,
+    /// The position is invalid.
+    InvalidPosition,
+    /// The strand is invalid.
+    InvalidStrand,
+    /// The CIGAR is invalid.
+    InvalidCigar,
+    /// The mapping quality is invalid.
+    InvalidMappingQuality,
+    /// The edit distance is invalid.
+    InvalidEditDistance,
+    /// An alignment has unexpected trailing fields.
+    ExpectedEof,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty input"),
+            Self::MissingField => write!(f, "missing field"),
+            Self::InvalidReferenceSequenceName => write!(f, "invalid reference sequence name"),
+            Self::InvalidPosition => write!(f, "invalid position"),
+            Self::InvalidStrand => write!(f, "invalid strand"),
+            Self::InvalidCigar => write!(f, "invalid CIGAR"),
+            Self::InvalidMappingQuality => write!(f, "invalid mapping quality"),
+            Self::InvalidEditDistance => write!(f, "invalid edit distance"),
+            Self::ExpectedEof => write!(f, "expected EOF"),
+        }
+    }
+}
+
+impl FromStr for OtherAlignment {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(FIELD_DELIMITER);
+        let mut next_field = || fields.next().ok_or(ParseError::MissingField);
+
+        let reference_sequence_name = next_field()?;
+
+        if reference_sequence_name.is_empty() {
+            return Err(ParseError::InvalidReferenceSequenceName);
+        }
+
+        let position = next_field()?
+            .parse()
+            .map_err(|_| ParseError::InvalidPosition)?;
+
+        let is_reverse_complemented = match next_field()? {
+            "+" => false,
+            "-" => true,
+            _ => return Err(ParseError::InvalidStrand),
+        };
+
+        let mut cigar = Cigar::default();
+        parse_cigar(next_field()?.as_bytes(), &mut cigar).map_err(|_| ParseError::InvalidCigar)?;
+
+        let mapping_quality = next_field()?
+            .parse()
+            .map(MappingQuality::new)
+            .map_err(|_| ParseError::InvalidMappingQuality)?;
+
+        let edit_distance = next_field()?
+            .parse()
+            .map_err(|_| ParseError::InvalidEditDistance)?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::ExpectedEof);
+        }
+
+        Ok(Self::new(
+            reference_sequence_name,
+            position,
+            is_reverse_complemented,
+            cigar,
+            mapping_quality,
+            edit_distance,
+        ))
+    }
+}
+
+/// Other canonical alignments in a chimeric alignment.
+///
+/// This is the value of the other alignments (`SA`) data field.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OtherAlignments(Vec<OtherAlignment>);
+
+impl OtherAlignments {
+    /// Reads the other alignments of an alignment record.
+    ///
+    /// This returns `None` if the record does not have an other alignments (`SA`) data field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::{
+    ///     alignment::{
+    ///         record::data::field::Tag,
+    ///         record_buf::data::field::Value,
+    ///         RecordBuf,
+    ///     },
+    ///     record::data::field::value::OtherAlignments,
+    /// };
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_data(
+    ///         [(Tag::OTHER_ALIGNMENTS, Value::from("sq0,8,+,4S4M,13,0;"))]
+    ///             .into_iter()
+    ///             .collect(),
+    ///     )
+    ///     .build();
+    ///
+    /// let other_alignments = OtherAlignments::try_from_alignment_record(&record)?
+    ///     .expect("missing other alignments");
+    ///
+    /// assert_eq!(other_alignments.as_ref().len(), 1);
+    /// assert_eq!(other_alignments.as_ref()[0].position(), Position::try_from(8)?);
+    ///
+    /// assert!(OtherAlignments::try_from_alignment_record(&RecordBuf::default())?.is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_alignment_record(record: &dyn Record) -> io::Result<Option<Self>> {
+        <Self as TypedValue>::try_from_alignment_record(record)
+    }
+}
+
+impl TypedValue for OtherAlignments {
+    const TAG: Tag = Tag::OTHER_ALIGNMENTS;
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        let s = get_string(Self::TAG, &value)?;
+
+        std::str::from_utf8(s)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        if self.0.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                ParseError::Empty,
+            ));
+        }
+
+        Ok(ValueBuf::from(self.to_string()))
+    }
+}
+
+impl AsRef<[OtherAlignment]> for OtherAlignments {
+    fn as_ref(&self) -> &[OtherAlignment] {
+        &self.0
+    }
+}
+
+impl AsMut<Vec<OtherAlignment>> for OtherAlignments {
+    fn as_mut(&mut self) -> &mut Vec<OtherAlignment> {
+        &mut self.0
+    }
+}
+
+impl From<Vec<OtherAlignment>> for OtherAlignments {
+    fn from(alignments: Vec<OtherAlignment>) -> Self {
+        Self(alignments)
+    }
+}
+
+impl From<OtherAlignments> for Vec<OtherAlignment> {
+    fn from(other_alignments: OtherAlignments) -> Self {
+        other_alignments.0
+    }
+}
+
+impl fmt::Display for OtherAlignments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for alignment in &self.0 {
+            write!(f, "{alignment}{ALIGNMENT_DELIMITER}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for OtherAlignments {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        s.strip_suffix(ALIGNMENT_DELIMITER)
+            .unwrap_or(s)
+            .split(ALIGNMENT_DELIMITER)
+            .map(|t| t.parse())
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::record::cigar::{op::Kind, Op};
+
+    #[test]
+    fn test_fmt() -> Result<(), noodles_core::position::TryFromIntError> {
+        let other_alignments = OtherAlignments::from(vec![
+            OtherAlignment::new(
+                "sq0",
+                Position::try_from(8)?,
+                false,
+                [Op::new(Kind::SoftClip, 4), Op::new(Kind::Match, 4)]
+                    .into_iter()
+                    .collect(),
+                MappingQuality::new(13),
+                0,
+            ),
+            OtherAlignment::new(
+                "sq1",
+                Position::try_from(21)?,
+                true,
+                [Op::new(Kind::Match, 4), Op::new(Kind::HardClip, 4)]
+                    .into_iter()
+                    .collect(),
+                None,
+                2,
+            ),
+        ]);
+
+        assert_eq!(
+            other_alignments.to_string(),
+            "sq0,8,+,4S4M,13,0;sq1,21,-,4M4H,255,2;"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), Box<dyn std::error::Error>> {
+        let expected = OtherAlignments::from(vec![
+            OtherAlignment::new(
+                "sq0",
+                Position::try_from(8)?,
+                false,
+                [Op::new(Kind::SoftClip, 4), Op::new(Kind::Match, 4)]
+                    .into_iter()
+                    .collect(),
+                MappingQuality::new(13),
+                0,
+            ),
+            OtherAlignment::new(
+                "sq1",
+                Position::try_from(21)?,
+                true,
+                [Op::new(Kind::Match, 4), Op::new(Kind::HardClip, 4)]
+                    .into_iter()
+                    .collect(),
+                None,
+                2,
+            ),
+        ]);
+
+        assert_eq!(
+            "sq0,8,+,4S4M,13,0;sq1,21,-,4M4H,255,2;".parse::<OtherAlignments>()?,
+            expected
+        );
+        assert_eq!(
+            "sq0,8,+,4S4M,13,0;sq1,21,-,4M4H,255,2".parse::<OtherAlignments>()?,
+            expected
+        );
+
+        assert_eq!("".parse::<OtherAlignments>(), Err(ParseError::Empty));
+        assert_eq!(
+            "sq0,8,+,4S4M,13".parse::<OtherAlignments>(),
+            Err(ParseError::MissingField)
+        );
+        assert_eq!(
+            ",8,+,4S4M,13,0".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidReferenceSequenceName)
+        );
+        assert_eq!(
+            "sq0,0,+,4S4M,13,0".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidPosition)
+        );
+        assert_eq!(
+            "sq0,8,.,4S4M,13,0".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidStrand)
+        );
+        assert_eq!(
+            "sq0,8,+,*,13,0".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidCigar)
+        );
+        assert_eq!(
+            "sq0,8,+,4S4M,256,0".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidMappingQuality)
+        );
+        assert_eq!(
+            "sq0,8,+,4S4M,13,-1".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidEditDistance)
+        );
+        assert_eq!(
+            "sq0,8,+,4S4M,13,0,ndls".parse::<OtherAlignments>(),
+            Err(ParseError::ExpectedEof)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! SAM record data field value for the read group.
+
+use std::io;
+
+use super::typed_value::{is_printable, try_from_string, try_into_string, TypedValue};
+use crate::alignment::{
+    record::data::field::{Tag, Value},
+    record_buf::data::field::Value as ValueBuf,
+};
+
+/// The read group (`RG`).
+///
+/// This is the ID of a read group (`@RG`) header record.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ReadGroup(String);
+
+impl AsRef<str> for ReadGroup {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for ReadGroup {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<ReadGroup> for String {
+    fn from(read_group: ReadGroup) -> Self {
+        read_group.0
+    }
+}
+
+impl TypedValue for ReadGroup {
+    const TAG: Tag = Tag::READ_GROUP;
+
+    fn try_from_value(value: Value<'_>) -> io::Result<Self> {
+        try_into_string(Self::TAG, &value, is_printable).map(Self)
+    }
+
+    fn try_into_value(self) -> io::Result<ValueBuf> {
+        try_from_string(Self::TAG, self.0, is_printable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::BStr;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from_value() -> io::Result<()> {
+        assert_eq!(
+            ReadGroup::try_from_value(Value::String(BStr::new("rg 0")))?,
+            ReadGroup::from(String::from("rg 0"))
+        );
+
+        assert!(matches!(
+            ReadGroup::try_from_value(Value::String(BStr::new("rg\t0"))),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            ReadGroup::try_from_value(Value::Int32(0)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_into_value() -> io::Result<()> {
+        assert_eq!(
+            ReadGroup::from(String::from("rg0")).try_into_value()?,
+            ValueBuf::from("rg0")
+        );
+
+        assert!(matches!(
+            ReadGroup::from(String::from("rg\u{e9}")).try_into_value(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
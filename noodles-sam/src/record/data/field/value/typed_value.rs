@@ -0,0 +1,194 @@
+use std::io;
+
+use bstr::BStr;
+
+use crate::alignment::{
+    record::data::field::{Tag, Value},
+    record_buf::{self, data::field::Value as ValueBuf},
+    Record,
+};
+
+/// A typed value of a standard data field.
+///
+/// Values are validated against the SAM optional fields specification when they are converted
+/// from an alignment record data field value and when they are converted back to a record buffer
+/// data field value.
+pub trait TypedValue: Sized {
+    /// The data field tag.
+    const TAG: Tag;
+
+    /// Converts an alignment record data field value to a typed value.
+    fn try_from_value(value: Value<'_>) -> io::Result<Self>;
+
+    /// Converts the typed value to a record buffer data field value.
+    fn try_into_value(self) -> io::Result<ValueBuf>;
+
+    /// Reads the typed value from an alignment record.
+    ///
+    /// This returns `None` if the record does not have the data field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     alignment::{record::data::field::Tag, record_buf::data::field::Value, RecordBuf},
+    ///     record::data::field::value::{EditDistance, TypedValue},
+    /// };
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_data([(Tag::EDIT_DISTANCE, Value::from(2))].into_iter().collect())
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     EditDistance::try_from_alignment_record(&record)?,
+    ///     Some(EditDistance::from(2))
+    /// );
+    ///
+    /// assert!(EditDistance::try_from_alignment_record(&RecordBuf::default())?.is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    fn try_from_alignment_record(record: &dyn Record) -> io::Result<Option<Self>> {
+        let data = record.data();
+
+        let value = data
+            .get(&Self::TAG)
+            .transpose()?
+            .map(Self::try_from_value)
+            .transpose()?;
+
+        Ok(value)
+    }
+
+    /// Inserts the typed value into record buffer data.
+    ///
+    /// This returns the previous field with the same tag, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     alignment::{record::data::field::Tag, record_buf::{data::field::Value, Data}},
+    ///     record::data::field::value::{ReadGroup, TypedValue},
+    /// };
+    ///
+    /// let mut data = Data::default();
+    /// ReadGroup::from(String::from("rg0")).insert_into(&mut data)?;
+    /// assert_eq!(data.get(&Tag::READ_GROUP), Some(&Value::from("rg0")));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    fn insert_into(self, data: &mut record_buf::Data) -> io::Result<Option<(Tag, ValueBuf)>> {
+        let value = self.try_into_value()?;
+        Ok(data.insert(Self::TAG, value))
+    }
+}
+
+pub(super) fn get_int(tag: Tag, value: &Value<'_>) -> io::Result<i64> {
+    value.as_int().ok_or_else(|| invalid_type(tag))
+}
+
+pub(super) fn get_string<'a>(tag: Tag, value: &Value<'a>) -> io::Result<&'a BStr> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(invalid_type(tag)),
+    }
+}
+
+pub(super) fn invalid_type(tag: Tag) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid {} value type", BStr::new(tag.as_ref())),
+    )
+}
+
+pub(super) fn invalid_value(kind: io::ErrorKind, tag: Tag) -> io::Error {
+    io::Error::new(kind, format!("invalid {} value", BStr::new(tag.as_ref())))
+}
+
+// `[ !-~]*`
+pub(super) fn is_printable(s: &[u8]) -> bool {
+    s.iter().all(|b| matches!(b, b' '..=b'~'))
+}
+
+// `[!-~]+`
+pub(super) fn is_graphic(s: &[u8]) -> bool {
+    !s.is_empty() && s.iter().all(|b| b.is_ascii_graphic())
+}
+
+pub(super) fn try_into_string<P>(tag: Tag, value: &Value<'_>, is_valid: P) -> io::Result<String>
+where
+    P: Fn(&[u8]) -> bool,
+{
+    let s = get_string(tag, value)?;
+
+    if is_valid(s) {
+        // Valid values are ASCII, so this conversion is lossless.
+        Ok(String::from_utf8_lossy(s).into_owned())
+    } else {
+        Err(invalid_value(io::ErrorKind::InvalidData, tag))
+    }
+}
+
+pub(super) fn try_from_string<P>(tag: Tag, s: String, is_valid: P) -> io::Result<ValueBuf>
+where
+    P: Fn(&[u8]) -> bool,
+{
+    if is_valid(s.as_bytes()) {
+        Ok(ValueBuf::from(s))
+    } else {
+        Err(invalid_value(io::ErrorKind::InvalidInput, tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::data::field::value::{EditDistance, MismatchedPositions, ReadGroup};
+
+    #[test]
+    fn test_try_from_alignment_record() -> io::Result<()> {
+        use crate::{io::Reader, record::data::field::value::mismatched_positions::Op};
+
+        let src = b"r0\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\tNM:i:1\tMD:Z:3C4\tRG:i:0\n";
+        let mut reader = Reader::new(&src[..]);
+
+        let mut record = crate::Record::default();
+        reader.read_record(&mut record)?;
+
+        assert_eq!(
+            EditDistance::try_from_alignment_record(&record)?,
+            Some(EditDistance::from(1))
+        );
+
+        assert_eq!(
+            MismatchedPositions::try_from_alignment_record(&record)?,
+            Some(MismatchedPositions::from(vec![
+                Op::Match(3),
+                Op::Mismatch(b'C'),
+                Op::Match(4)
+            ]))
+        );
+
+        assert!(matches!(
+            ReadGroup::try_from_alignment_record(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_into() -> io::Result<()> {
+        let mut data = record_buf::Data::default();
+
+        assert!(EditDistance::from(1).insert_into(&mut data)?.is_none());
+        assert_eq!(data.get(&Tag::EDIT_DISTANCE), Some(&ValueBuf::Int32(1)));
+
+        assert!(matches!(
+            ReadGroup::from(String::from("rg\n0")).insert_into(&mut data),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert!(data.get(&Tag::READ_GROUP).is_none());
+
+        Ok(())
+    }
+}
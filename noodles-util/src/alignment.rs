@@ -1,4 +1,12 @@
 //! Alignment format utilities.
 
+pub mod chimeric;
+pub mod consensus;
+pub mod downsample;
+pub mod expression;
+pub mod fastq;
 pub mod io;
 pub mod iter;
+pub mod reference_sequences;
+pub mod stats;
+pub mod umi;
@@ -0,0 +1,291 @@
+//! Chimeric alignment reconstruction.
+
+use std::io::{self, Read, Seek};
+
+use noodles_core::Region;
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        record::{
+            cigar::{op::Kind, Op},
+            Flags,
+        },
+        Record, RecordBuf,
+    },
+    record::data::field::value::{other_alignments::OtherAlignment, OtherAlignments},
+};
+
+use super::io::IndexedReader;
+
+/// Reads the segments of a chimeric alignment.
+///
+/// The supplementary records listed in the other alignments (`SA`) data field of the given
+/// primary record are queried from the indexed reader. The primary record and its supplementary
+/// records are returned ordered by where they start in the original read, i.e., by the length of
+/// the leading clip in the read orientation.
+///
+/// If the primary record does not have an `SA` data field, only the primary record is returned.
+/// This returns an error if a listed supplementary record is not found.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_util::alignment::{self, chimeric::read_segments};
+///
+/// let mut reader = alignment::io::indexed_reader::Builder::default().build_from_path("sample.bam")?;
+/// let header = reader.read_header()?;
+///
+/// let records: Vec<_> = reader.records(&header).collect::<std::io::Result<_>>()?;
+///
+/// for record in &records {
+///     let flags = record.flags()?;
+///
+///     if flags.is_secondary() || flags.is_supplementary() {
+///         continue;
+///     }
+///
+///     let segments = read_segments(&mut reader, &header, record)?;
+///     println!("{}", segments.len());
+/// }
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn read_segments<R, P>(
+    reader: &mut IndexedReader<R>,
+    header: &sam::Header,
+    record: &P,
+) -> io::Result<Vec<RecordBuf>>
+where
+    R: Read + Seek,
+    P: Record,
+{
+    let primary_record = RecordBuf::try_from_alignment_record(header, record)?;
+
+    let other_alignments = OtherAlignments::try_from_alignment_record(record)?
+        .map(Vec::from)
+        .unwrap_or_default();
+
+    let mut segments = Vec::with_capacity(other_alignments.len() + 1);
+
+    for other_alignment in &other_alignments {
+        let segment = read_supplementary_record(reader, header, &primary_record, other_alignment)?
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("missing supplementary record: {other_alignment}"),
+                )
+            })?;
+
+        segments.push(segment);
+    }
+
+    segments.push(primary_record);
+    segments.sort_by_key(query_start);
+
+    Ok(segments)
+}
+
+fn read_supplementary_record<R>(
+    reader: &mut IndexedReader<R>,
+    header: &sam::Header,
+    primary_record: &RecordBuf,
+    other_alignment: &OtherAlignment,
+) -> io::Result<Option<RecordBuf>>
+where
+    R: Read + Seek,
+{
+    const SEGMENT_FLAGS: Flags = Flags::FIRST_SEGMENT.union(Flags::LAST_SEGMENT);
+
+    let region = Region::new(
+        other_alignment.reference_sequence_name(),
+        other_alignment.position()..=other_alignment.position(),
+    );
+
+    for result in reader.query(header, &region)? {
+        let record = result?;
+
+        let name = record.name();
+
+        if name.as_ref().map(|name| name.as_bytes())
+            != primary_record.name().map(|name| name.as_ref())
+        {
+            continue;
+        }
+
+        let flags = record.flags()?;
+
+        if !flags.is_supplementary()
+            || flags.is_secondary()
+            || flags.is_reverse_complemented() != other_alignment.is_reverse_complemented()
+            || flags & SEGMENT_FLAGS != primary_record.flags() & SEGMENT_FLAGS
+            || record.alignment_start().transpose()? != Some(other_alignment.position())
+        {
+            continue;
+        }
+
+        let record = RecordBuf::try_from_alignment_record(header, &record)?;
+
+        if record.cigar() == other_alignment.cigar() {
+            return Ok(Some(record));
+        }
+    }
+
+    Ok(None)
+}
+
+fn query_start(record: &RecordBuf) -> usize {
+    let ops = record.cigar().as_ref();
+
+    if record.flags().is_reverse_complemented() {
+        clip_len(ops.iter().rev())
+    } else {
+        clip_len(ops.iter())
+    }
+}
+
+fn clip_len<'a, I>(ops: I) -> usize
+where
+    I: Iterator<Item = &'a Op>,
+{
+    ops.map_while(|op| matches!(op.kind(), Kind::SoftClip | Kind::HardClip).then_some(op.len()))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_bam as bam;
+    use noodles_core::Position;
+    use sam::{
+        alignment::{
+            io::Write,
+            record::data::field::Tag,
+            record_buf::{data::field::Value, Cigar},
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+    use crate::alignment::io::indexed_reader;
+
+    #[test]
+    fn test_read_segments() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .build();
+
+        // 4M6S
+        let primary_cigar: Cigar = [Op::new(Kind::Match, 4), Op::new(Kind::SoftClip, 6)]
+            .into_iter()
+            .collect();
+
+        // 4S6M
+        let supplementary_cigar: Cigar = [Op::new(Kind::SoftClip, 4), Op::new(Kind::Match, 6)]
+            .into_iter()
+            .collect();
+
+        let build_record = |name: &[u8],
+                            flags,
+                            start,
+                            cigar: &Cigar,
+                            other_alignments: Option<&str>|
+         -> Result<_, Box<dyn std::error::Error>> {
+            let mut record = RecordBuf::builder()
+                .set_name(name.into())
+                .set_flags(flags)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar(cigar.clone())
+                .build();
+
+            if let Some(other_alignments) = other_alignments {
+                record
+                    .data_mut()
+                    .insert(Tag::OTHER_ALIGNMENTS, Value::from(other_alignments));
+            }
+
+            Ok(record)
+        };
+
+        let primary_record = build_record(
+            b"r0",
+            Flags::empty(),
+            8,
+            &primary_cigar,
+            Some("sq0,30,+,4S6M,60,0;"),
+        )?;
+
+        let records = [
+            primary_record.clone(),
+            build_record(b"r1", Flags::SUPPLEMENTARY, 30, &supplementary_cigar, None)?,
+            build_record(b"r0", Flags::SUPPLEMENTARY, 30, &supplementary_cigar, None)?,
+        ];
+
+        let mut writer = bam::io::IndexedWriter::new(Vec::new());
+        writer.write_alignment_header(&header)?;
+
+        for record in &records {
+            writer.write_alignment_record(&header, record)?;
+        }
+
+        let index = writer.finish(&header)?;
+        let data = writer.get_ref().get_ref().clone();
+
+        let mut reader = indexed_reader::Builder::default()
+            .set_index(index)
+            .build_from_reader(io::Cursor::new(data))?;
+        reader.read_header()?;
+
+        let segments = read_segments(&mut reader, &header, &primary_record)?;
+        assert_eq!(segments, [records[0].clone(), records[2].clone()]);
+
+        let record = build_record(
+            b"r0",
+            Flags::empty(),
+            8,
+            &primary_cigar,
+            Some("sq0,50,+,4S6M,60,0;"),
+        )?;
+
+        assert!(matches!(
+            read_segments(&mut reader, &header, &record),
+            Err(e) if e.kind() == io::ErrorKind::NotFound
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_start() {
+        let cigar: sam::alignment::record_buf::Cigar = [
+            Op::new(Kind::HardClip, 2),
+            Op::new(Kind::SoftClip, 3),
+            Op::new(Kind::Match, 4),
+            Op::new(Kind::SoftClip, 8),
+        ]
+        .into_iter()
+        .collect();
+
+        let record = RecordBuf::builder().set_cigar(cigar.clone()).build();
+        assert_eq!(query_start(&record), 5);
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::REVERSE_COMPLEMENTED)
+            .set_cigar(cigar)
+            .build();
+        assert_eq!(query_start(&record), 8);
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::REVERSE_COMPLEMENTED)
+            .set_cigar(
+                [Op::new(Kind::Match, 4), Op::new(Kind::HardClip, 13)]
+                    .into_iter()
+                    .collect(),
+            )
+            .build();
+        assert_eq!(query_start(&record), 13);
+    }
+}
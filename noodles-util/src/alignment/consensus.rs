@@ -0,0 +1,538 @@
+//! Consensus sequence calling.
+
+mod builder;
+mod column;
+
+pub use self::builder::Builder;
+
+use std::{collections::VecDeque, io};
+
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    alignment::{record::cigar::op::Kind, Record},
+};
+
+use self::column::Column;
+
+/// A consensus calling mode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Each read counts equally.
+    #[default]
+    Simple,
+    /// Each read is weighted by its quality scores.
+    ///
+    /// A base is weighted by its quality score; a deletion, by the quality score of the read base
+    /// preceding it; and an insertion, by the mean quality score of the inserted bases.
+    QualityWeighted,
+}
+
+/// A consensus caller.
+///
+/// This calls a consensus sequence for each reference sequence with used records, similar to the
+/// simple mode of `samtools consensus`. Unmapped, secondary, QC failed, and duplicate records are
+/// not used.
+///
+/// Each reference position is called as the most common base if its weight is at least the call
+/// fraction of the column. If the second most common base has at least the het fraction of the
+/// weight of the most common base, the position is instead called as the IUPAC ambiguity code of
+/// both bases, given that they together reach the call fraction. Positions that cannot be called
+/// or that have less than the minimum depth are called as `N`.
+///
+/// Insertions and deletions are called the same way, using the most common inserted sequence
+/// after a position and the weight of deletions at a position, respectively.
+#[derive(Debug)]
+pub struct Caller {
+    mode: Mode,
+    min_depth: usize,
+    min_mapping_quality: u8,
+    min_base_quality: u8,
+    call_fraction: f64,
+    het_fraction: f64,
+    use_ambiguity_codes: bool,
+    show_insertions: bool,
+    show_deletions: bool,
+}
+
+impl Caller {
+    /// Returns a builder to create a consensus caller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let builder = Caller::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the calling mode.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Returns the minimum depth of a column to be called.
+    pub fn min_depth(&self) -> usize {
+        self.min_depth
+    }
+
+    /// Returns the minimum mapping quality of a record to be used.
+    pub fn min_mapping_quality(&self) -> u8 {
+        self.min_mapping_quality
+    }
+
+    /// Returns the minimum quality score of a base to be used.
+    pub fn min_base_quality(&self) -> u8 {
+        self.min_base_quality
+    }
+
+    /// Returns the minimum fraction of the weight of a column that the called base must have.
+    pub fn call_fraction(&self) -> f64 {
+        self.call_fraction
+    }
+
+    /// Returns the minimum relative weight of the second most common base for a column to be
+    /// called heterozygous.
+    pub fn het_fraction(&self) -> f64 {
+        self.het_fraction
+    }
+
+    /// Returns whether heterozygous columns are called using IUPAC ambiguity codes.
+    pub fn uses_ambiguity_codes(&self) -> bool {
+        self.use_ambiguity_codes
+    }
+
+    /// Returns whether called insertions are included in the consensus sequence.
+    pub fn shows_insertions(&self) -> bool {
+        self.show_insertions
+    }
+
+    /// Returns whether called deletions are included in the consensus sequence as `*`.
+    pub fn shows_deletions(&self) -> bool {
+        self.show_deletions
+    }
+
+    /// Calls consensus sequences from coordinate-sorted alignment records.
+    ///
+    /// The consensus sequences are named after their reference sequences. Positions of a
+    /// reference sequence that are not covered are called as `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::num::NonZeroUsize;
+    /// use noodles_core::Position;
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::{
+    ///         record::{cigar::{op::Kind, Op}, Flags},
+    ///         record_buf::Sequence,
+    ///         RecordBuf,
+    ///     },
+    ///     header::record::value::{map::ReferenceSequence, Map},
+    /// };
+    /// use noodles_util::alignment::consensus::Caller;
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence(
+    ///         "sq0",
+    ///         Map::<ReferenceSequence>::new(NonZeroUsize::try_from(6)?),
+    ///     )
+    ///     .build();
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_flags(Flags::empty())
+    ///     .set_reference_sequence_id(0)
+    ///     .set_alignment_start(Position::try_from(2)?)
+    ///     .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+    ///     .set_sequence(Sequence::from(b"ACGT"))
+    ///     .build();
+    ///
+    /// let caller = Caller::default();
+    /// let records = caller.call(&header, [Ok::<_, std::io::Error>(record)])?;
+    ///
+    /// assert_eq!(records.len(), 1);
+    /// assert_eq!(records[0].name(), b"sq0");
+    /// assert_eq!(records[0].sequence().as_ref(), b"NACGTN");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn call<I, R>(&self, header: &sam::Header, records: I) -> io::Result<Vec<fasta::Record>>
+    where
+        I: IntoIterator<Item = io::Result<R>>,
+        R: Record,
+    {
+        let mut consensus_records = Vec::new();
+        let mut pileup: Option<Pileup> = None;
+
+        for result in records {
+            let record = result?;
+
+            if !self.is_used(&record)? {
+                continue;
+            }
+
+            let (Some(reference_sequence_id), Some(alignment_start)) = (
+                record.reference_sequence_id(header).transpose()?,
+                record.alignment_start().transpose()?,
+            ) else {
+                continue;
+            };
+
+            let start = usize::from(alignment_start) - 1;
+
+            let pileup = match &mut pileup {
+                Some(p) if p.reference_sequence_id == reference_sequence_id => p,
+                _ => {
+                    if let Some(p) = pileup.take() {
+                        if reference_sequence_id < p.reference_sequence_id {
+                            return Err(unsorted_error());
+                        }
+
+                        consensus_records.push(self.finish(header, p)?);
+                    }
+
+                    pileup.insert(Pileup::new(reference_sequence_id))
+                }
+            };
+
+            if start < pileup.start {
+                return Err(unsorted_error());
+            }
+
+            pileup.advance(self, start);
+            self.add_record(pileup, &record, start)?;
+        }
+
+        if let Some(p) = pileup {
+            consensus_records.push(self.finish(header, p)?);
+        }
+
+        Ok(consensus_records)
+    }
+
+    fn is_used<R>(&self, record: &R) -> io::Result<bool>
+    where
+        R: Record,
+    {
+        let flags = record.flags()?;
+
+        if flags.is_unmapped() || flags.is_secondary() || flags.is_qc_fail() || flags.is_duplicate()
+        {
+            return Ok(false);
+        }
+
+        match record.mapping_quality().transpose()? {
+            Some(mapping_quality) => Ok(u8::from(mapping_quality) >= self.min_mapping_quality),
+            None => Ok(true),
+        }
+    }
+
+    fn add_record<R>(&self, pileup: &mut Pileup, record: &R, start: usize) -> io::Result<()>
+    where
+        R: Record,
+    {
+        let sequence: Vec<_> = record.sequence().iter().collect();
+        let quality_scores: Vec<_> = record.quality_scores().iter().collect();
+
+        // Records without quality scores have a weight of 1 for every base.
+        let weight = |scores: &[u8]| -> f64 {
+            match self.mode {
+                Mode::Simple => 1.0,
+                Mode::QualityWeighted if scores.is_empty() => 1.0,
+                Mode::QualityWeighted => {
+                    let sum: f64 = scores.iter().map(|&n| f64::from(n)).sum();
+                    (sum / scores.len() as f64).max(1.0)
+                }
+            }
+        };
+
+        let is_low_quality = |scores: &[u8]| -> bool {
+            !scores.is_empty() && {
+                let sum: usize = scores.iter().map(|&n| usize::from(n)).sum();
+                sum < usize::from(self.min_base_quality) * scores.len()
+            }
+        };
+
+        let scores = |range: std::ops::Range<usize>| -> &[u8] {
+            quality_scores.get(range).unwrap_or_default()
+        };
+
+        let mut reference_position = start;
+        let mut read_position = 0;
+
+        for result in record.cigar().iter() {
+            let op = result?;
+            let len = op.len();
+
+            match op.kind() {
+                Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                    for i in 0..len {
+                        let j = read_position + i;
+
+                        let Some(&base) = sequence.get(j) else {
+                            break;
+                        };
+
+                        let s = scores(j..j + 1);
+
+                        if !is_low_quality(s) {
+                            pileup
+                                .column_mut(reference_position + i)
+                                .add_base(base, weight(s));
+                        }
+                    }
+
+                    reference_position += len;
+                    read_position += len;
+                }
+                Kind::Insertion => {
+                    let end = read_position + len;
+
+                    // Insertions before the first aligned base of a record are ignored.
+                    if reference_position > start {
+                        if let Some(bases) = sequence.get(read_position..end) {
+                            let s = scores(read_position..end);
+
+                            if !is_low_quality(s) {
+                                pileup
+                                    .column_mut(reference_position - 1)
+                                    .add_insertion(bases, weight(s));
+                            }
+                        }
+                    }
+
+                    read_position = end;
+                }
+                Kind::Deletion => {
+                    let s = read_position
+                        .checked_sub(1)
+                        .map(|j| scores(j..j + 1))
+                        .unwrap_or_default();
+
+                    let w = weight(s);
+
+                    for i in 0..len {
+                        pileup.column_mut(reference_position + i).add_deletion(w);
+                    }
+
+                    reference_position += len;
+                }
+                Kind::Skip => reference_position += len,
+                Kind::SoftClip => read_position += len,
+                Kind::HardClip | Kind::Pad => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(&self, header: &sam::Header, mut pileup: Pileup) -> io::Result<fasta::Record> {
+        let (name, reference_sequence) = header
+            .reference_sequences()
+            .get_index(pileup.reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+            })?;
+
+        let end = usize::from(reference_sequence.length()).max(pileup.end());
+        pileup.advance(self, end);
+
+        let definition = fasta::record::Definition::new(name.to_vec(), None);
+        let sequence = fasta::record::Sequence::from(pileup.sequence);
+
+        Ok(fasta::Record::new(definition, sequence))
+    }
+}
+
+impl Default for Caller {
+    fn default() -> Self {
+        Builder::default().build_unchecked()
+    }
+}
+
+// The columns of a reference sequence that can still be covered by records.
+struct Pileup {
+    reference_sequence_id: usize,
+    // The 0-based position of the first column
+    start: usize,
+    columns: VecDeque<Column>,
+    // The consensus sequence of positions before `start`
+    sequence: Vec<u8>,
+}
+
+impl Pileup {
+    fn new(reference_sequence_id: usize) -> Self {
+        Self {
+            reference_sequence_id,
+            start: 0,
+            columns: VecDeque::new(),
+            sequence: Vec::new(),
+        }
+    }
+
+    fn end(&self) -> usize {
+        self.start + self.columns.len()
+    }
+
+    fn column_mut(&mut self, position: usize) -> &mut Column {
+        let i = position - self.start;
+
+        if i >= self.columns.len() {
+            self.columns.resize(i + 1, Column::default());
+        }
+
+        &mut self.columns[i]
+    }
+
+    // Calls the columns before the given position.
+    fn advance(&mut self, caller: &Caller, position: usize) {
+        while self.start < position {
+            let column = self.columns.pop_front().unwrap_or_default();
+            column.call(caller, &mut self.sequence);
+            self.start += 1;
+        }
+    }
+}
+
+fn unsorted_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "records are not coordinate-sorted",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_core::Position;
+    use noodles_sam::{
+        alignment::{
+            record::{cigar::Op, Flags},
+            record_buf::{QualityScores, Sequence},
+            RecordBuf,
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        Ok(sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(4)?),
+            )
+            .build())
+    }
+
+    fn build_record(
+        reference_sequence_id: usize,
+        start: usize,
+        ops: &[(Kind, usize)],
+        sequence: &[u8],
+        quality_scores: &[u8],
+    ) -> Result<RecordBuf, Box<dyn std::error::Error>> {
+        Ok(RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(reference_sequence_id)
+            .set_alignment_start(Position::try_from(start)?)
+            .set_cigar(ops.iter().map(|&(kind, len)| Op::new(kind, len)).collect())
+            .set_sequence(Sequence::from(sequence))
+            .set_quality_scores(QualityScores::from(quality_scores.to_vec()))
+            .build())
+    }
+
+    #[test]
+    fn test_call() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let records = [
+            // 2M2I2M
+            build_record(
+                0,
+                2,
+                &[(Kind::Match, 2), (Kind::Insertion, 2), (Kind::Match, 2)],
+                b"ACttGT",
+                &[],
+            )?,
+            build_record(
+                0,
+                2,
+                &[(Kind::Match, 2), (Kind::Insertion, 2), (Kind::Match, 2)],
+                b"ACTTGA",
+                &[],
+            )?,
+            build_record(0, 6, &[(Kind::Match, 1)], b"C", &[])?,
+            // 1M1D1M
+            build_record(
+                0,
+                6,
+                &[(Kind::Match, 1), (Kind::Deletion, 1), (Kind::Match, 1)],
+                b"CA",
+                &[],
+            )?,
+            build_record(1, 1, &[(Kind::Match, 2)], b"GG", &[])?,
+        ];
+
+        let caller = Caller::default();
+        let consensus_records = caller.call(&header, records.into_iter().map(Ok))?;
+
+        assert_eq!(consensus_records.len(), 2);
+
+        // Position 5 has an A and a T (W), and position 7 is deleted.
+        assert_eq!(consensus_records[0].name(), b"sq0");
+        assert_eq!(consensus_records[0].sequence().as_ref(), b"NACTTGWCA");
+
+        assert_eq!(consensus_records[1].name(), b"sq1");
+        assert_eq!(consensus_records[1].sequence().as_ref(), b"GGNN");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_call_with_quality_weighted_mode() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let records = [
+            build_record(0, 1, &[(Kind::Match, 1)], b"A", &[40])?,
+            build_record(0, 1, &[(Kind::Match, 1)], b"C", &[5])?,
+            build_record(0, 1, &[(Kind::Match, 1)], b"C", &[5])?,
+        ];
+
+        let caller = Caller::default();
+        let consensus_records = caller.call(&header, records.clone().into_iter().map(Ok))?;
+        assert_eq!(&consensus_records[0].sequence().as_ref()[..1], b"M");
+
+        let caller = Caller::builder().set_mode(Mode::QualityWeighted).build()?;
+        let consensus_records = caller.call(&header, records.into_iter().map(Ok))?;
+        assert_eq!(&consensus_records[0].sequence().as_ref()[..1], b"A");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_call_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let records = [
+            build_record(0, 3, &[(Kind::Match, 1)], b"A", &[])?,
+            build_record(0, 1, &[(Kind::Match, 1)], b"A", &[])?,
+        ];
+
+        let caller = Caller::default();
+
+        assert!(matches!(
+            caller.call(&header, records.into_iter().map(Ok)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use std::io;
+
+use super::{Caller, Mode};
+
+/// A consensus caller builder.
+#[derive(Debug)]
+pub struct Builder {
+    mode: Mode,
+    min_depth: usize,
+    min_mapping_quality: u8,
+    min_base_quality: u8,
+    call_fraction: f64,
+    het_fraction: f64,
+    use_ambiguity_codes: bool,
+    show_insertions: bool,
+    show_deletions: bool,
+}
+
+impl Builder {
+    /// Sets the calling mode.
+    ///
+    /// The default is [`Mode::Simple`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::{Caller, Mode};
+    /// let caller = Caller::builder().set_mode(Mode::QualityWeighted).build()?;
+    /// assert_eq!(caller.mode(), Mode::QualityWeighted);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the minimum depth of a column to be called.
+    ///
+    /// Columns with fewer reads are called as `N`. The default is 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let caller = Caller::builder().set_min_depth(10).build()?;
+    /// assert_eq!(caller.min_depth(), 10);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_min_depth(mut self, min_depth: usize) -> Self {
+        self.min_depth = min_depth;
+        self
+    }
+
+    /// Sets the minimum mapping quality of a record to be used.
+    ///
+    /// Records with a missing mapping quality are always used. The default is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let caller = Caller::builder().set_min_mapping_quality(20).build()?;
+    /// assert_eq!(caller.min_mapping_quality(), 20);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_min_mapping_quality(mut self, min_mapping_quality: u8) -> Self {
+        self.min_mapping_quality = min_mapping_quality;
+        self
+    }
+
+    /// Sets the minimum quality score of a base to be used.
+    ///
+    /// Bases of records without quality scores are always used. The default is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let caller = Caller::builder().set_min_base_quality(13).build()?;
+    /// assert_eq!(caller.min_base_quality(), 13);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_min_base_quality(mut self, min_base_quality: u8) -> Self {
+        self.min_base_quality = min_base_quality;
+        self
+    }
+
+    /// Sets the minimum fraction of the weight of a column that the called base must have.
+    ///
+    /// This must be in the range (0.0, 1.0]. The default is 0.75.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let caller = Caller::builder().set_call_fraction(0.5).build()?;
+    /// assert_eq!(caller.call_fraction(), 0.5);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_call_fraction(mut self, call_fraction: f64) -> Self {
+        self.call_fraction = call_fraction;
+        self
+    }
+
+    /// Sets the minimum weight of the second most common base relative to the most common base
+    /// for a column to be called heterozygous.
+    ///
+    /// This must be in the range (0.0, 1.0]. The default is 0.5.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let caller = Caller::builder().set_het_fraction(0.25).build()?;
+    /// assert_eq!(caller.het_fraction(), 0.25);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_het_fraction(mut self, het_fraction: f64) -> Self {
+        self.het_fraction = het_fraction;
+        self
+    }
+
+    /// Sets whether heterozygous columns are called using IUPAC ambiguity codes.
+    ///
+    /// When disabled, only the most common base is considered. The default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let caller = Caller::builder().set_use_ambiguity_codes(false).build()?;
+    /// assert!(!caller.uses_ambiguity_codes());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_use_ambiguity_codes(mut self, value: bool) -> Self {
+        self.use_ambiguity_codes = value;
+        self
+    }
+
+    /// Sets whether called insertions are included in the consensus sequence.
+    ///
+    /// The default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let caller = Caller::builder().set_show_insertions(false).build()?;
+    /// assert!(!caller.shows_insertions());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_show_insertions(mut self, value: bool) -> Self {
+        self.show_insertions = value;
+        self
+    }
+
+    /// Sets whether called deletions are included in the consensus sequence as `*`.
+    ///
+    /// When disabled, called deletions are omitted. The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let caller = Caller::builder().set_show_deletions(true).build()?;
+    /// assert!(caller.shows_deletions());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn set_show_deletions(mut self, value: bool) -> Self {
+        self.show_deletions = value;
+        self
+    }
+
+    /// Builds a consensus caller.
+    ///
+    /// This returns an error if a fraction is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::consensus::Caller;
+    /// let caller = Caller::builder().build()?;
+    /// assert!(Caller::builder().set_call_fraction(1.5).build().is_err());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build(self) -> io::Result<Caller> {
+        let is_valid_fraction = |n: f64| n > 0.0 && n <= 1.0;
+
+        if !is_valid_fraction(self.call_fraction) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid call fraction: expected a value in (0.0, 1.0]",
+            ));
+        }
+
+        if !is_valid_fraction(self.het_fraction) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid het fraction: expected a value in (0.0, 1.0]",
+            ));
+        }
+
+        Ok(self.build_unchecked())
+    }
+
+    pub(super) fn build_unchecked(self) -> Caller {
+        Caller {
+            mode: self.mode,
+            min_depth: self.min_depth,
+            min_mapping_quality: self.min_mapping_quality,
+            min_base_quality: self.min_base_quality,
+            call_fraction: self.call_fraction,
+            het_fraction: self.het_fraction,
+            use_ambiguity_codes: self.use_ambiguity_codes,
+            show_insertions: self.show_insertions,
+            show_deletions: self.show_deletions,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            mode: Mode::default(),
+            min_depth: 1,
+            min_mapping_quality: 0,
+            min_base_quality: 0,
+            call_fraction: 0.75,
+            het_fraction: 0.5,
+            use_ambiguity_codes: true,
+            show_insertions: true,
+            show_deletions: false,
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::Caller;
+
+const DELETION: usize = 4;
+const BASES: [u8; 4] = [b'A', b'C', b'G', b'T'];
+
+/// The accumulated weights of a reference position.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Column {
+    // A, C, G, T, and deletion
+    weights: [f64; 5],
+    depth: usize,
+    // Inserted sequences after this position
+    insertions: Vec<(Vec<u8>, f64)>,
+}
+
+impl Column {
+    /// Adds a base. Bases other than A, C, G, and T are ignored.
+    pub fn add_base(&mut self, base: u8, weight: f64) {
+        let base = base.to_ascii_uppercase();
+
+        if let Some(i) = BASES.iter().position(|&b| b == base) {
+            self.weights[i] += weight;
+            self.depth += 1;
+        }
+    }
+
+    pub fn add_deletion(&mut self, weight: f64) {
+        self.weights[DELETION] += weight;
+        self.depth += 1;
+    }
+
+    pub fn add_insertion(&mut self, bases: &[u8], weight: f64) {
+        let bases = bases.to_ascii_uppercase();
+
+        match self.insertions.iter_mut().find(|(b, _)| *b == bases) {
+            Some((_, w)) => *w += weight,
+            None => self.insertions.push((bases, weight)),
+        }
+    }
+
+    /// Calls the consensus of the column and appends it to the destination.
+    pub fn call(&self, caller: &Caller, dst: &mut Vec<u8>) {
+        const MISSING: u8 = b'N';
+        const DELETED: u8 = b'*';
+
+        let total: f64 = self.weights.iter().sum();
+
+        if self.depth < caller.min_depth() || total <= 0.0 {
+            dst.push(MISSING);
+            return;
+        }
+
+        let is_called = |weight: f64| weight / total >= caller.call_fraction();
+
+        // Ties are broken by A, C, G, T, and deletion order.
+        let mut order = [0, 1, 2, 3, DELETION];
+        order.sort_by(|&a, &b| self.weights[b].total_cmp(&self.weights[a]));
+
+        let top = order[0];
+
+        if top == DELETION {
+            if !is_called(self.weights[DELETION]) {
+                dst.push(MISSING);
+            } else if caller.shows_deletions() {
+                dst.push(DELETED);
+            }
+        } else {
+            let second = order[1..]
+                .iter()
+                .copied()
+                .find(|&i| i != DELETION)
+                .filter(|&i| {
+                    caller.uses_ambiguity_codes()
+                        && self.weights[i] > 0.0
+                        && self.weights[i] >= caller.het_fraction() * self.weights[top]
+                });
+
+            let base = match second {
+                Some(i) if is_called(self.weights[top] + self.weights[i]) => {
+                    ambiguity_code(BASES[top], BASES[i])
+                }
+                Some(_) => MISSING,
+                None if is_called(self.weights[top]) => BASES[top],
+                None => MISSING,
+            };
+
+            dst.push(base);
+        }
+
+        if caller.shows_insertions() {
+            let insertion = self
+                .insertions
+                .iter()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            if let Some((bases, weight)) = insertion {
+                if is_called(*weight) {
+                    dst.extend(bases);
+                }
+            }
+        }
+    }
+}
+
+fn ambiguity_code(a: u8, b: u8) -> u8 {
+    match (a.min(b), a.max(b)) {
+        (b'A', b'G') => b'R',
+        (b'C', b'T') => b'Y',
+        (b'C', b'G') => b'S',
+        (b'A', b'T') => b'W',
+        (b'G', b'T') => b'K',
+        (b'A', b'C') => b'M',
+        _ => b'N',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(column: &Column, caller: &Caller) -> Vec<u8> {
+        let mut dst = Vec::new();
+        column.call(caller, &mut dst);
+        dst
+    }
+
+    #[test]
+    fn test_call() -> std::io::Result<()> {
+        let caller = Caller::builder().set_min_depth(2).build()?;
+
+        let mut column = Column::default();
+        column.add_base(b'a', 1.0);
+        assert_eq!(call(&column, &caller), b"N");
+
+        column.add_base(b'A', 1.0);
+        column.add_base(b'A', 1.0);
+        column.add_base(b'C', 1.0);
+        assert_eq!(call(&column, &caller), b"A");
+
+        // C/A = 0.5
+        column.add_base(b'C', 1.0);
+        assert_eq!(call(&column, &caller), b"M");
+
+        let caller = Caller::builder().set_use_ambiguity_codes(false).build()?;
+        assert_eq!(call(&column, &caller), b"N");
+
+        let caller = Caller::builder()
+            .set_use_ambiguity_codes(false)
+            .set_call_fraction(0.5)
+            .build()?;
+        assert_eq!(call(&column, &caller), b"A");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_call_with_indels() -> std::io::Result<()> {
+        let caller = Caller::default();
+
+        let mut column = Column::default();
+        column.add_deletion(1.0);
+        column.add_deletion(1.0);
+        column.add_deletion(1.0);
+        column.add_base(b'G', 1.0);
+        assert!(call(&column, &caller).is_empty());
+
+        let caller = Caller::builder().set_show_deletions(true).build()?;
+        assert_eq!(call(&column, &caller), b"*");
+
+        let mut column = Column::default();
+
+        for _ in 0..4 {
+            column.add_base(b'G', 1.0);
+        }
+
+        for _ in 0..3 {
+            column.add_insertion(b"tt", 1.0);
+        }
+
+        column.add_insertion(b"T", 1.0);
+
+        assert_eq!(call(&column, &caller), b"GTT");
+
+        let caller = Caller::builder().set_show_insertions(false).build()?;
+        assert_eq!(call(&column, &caller), b"G");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,579 @@
+//! Template-consistent downsampling.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    io,
+};
+
+use noodles_core::Position;
+use noodles_sam::{
+    alignment::{record::Flags, Record},
+    Header,
+};
+
+/// A deterministic template sampler.
+///
+/// Records are sampled by a hash of their read name and a seed. All records of a template, i.e.,
+/// records with the same read name, are either all kept or all discarded. Records without a read
+/// name are treated as having an empty read name.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sampler {
+    fraction: f64,
+    seed: u64,
+}
+
+impl Sampler {
+    /// Creates a sampler that keeps the given fraction of templates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::downsample::Sampler;
+    /// let sampler = Sampler::new(0.25, 8)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn new(fraction: f64, seed: u64) -> io::Result<Self> {
+        if (0.0..=1.0).contains(&fraction) {
+            Ok(Self { fraction, seed })
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid fraction: expected a value in [0.0, 1.0]",
+            ))
+        }
+    }
+
+    /// Returns the fraction of templates to keep.
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// Returns the seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns whether the template with the given read name is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::downsample::Sampler;
+    ///
+    /// let sampler = Sampler::new(1.0, 8)?;
+    /// assert!(sampler.keep_name(b"r0"));
+    ///
+    /// let sampler = Sampler::new(0.0, 8)?;
+    /// assert!(!sampler.keep_name(b"r0"));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn keep_name(&self, name: &[u8]) -> bool {
+        // The top 53 bits of the hash are mapped to [0.0, 1.0).
+        const SCALE: f64 = 1.0 / (1u64 << 53) as f64;
+
+        let n = hash(self.seed, name) >> 11;
+        (n as f64) * SCALE < self.fraction
+    }
+
+    /// Returns whether the given record is kept.
+    pub fn keep<R>(&self, record: &R) -> bool
+    where
+        R: Record + ?Sized,
+    {
+        match record.name() {
+            Some(name) => self.keep_name(name.as_bytes()),
+            None => self.keep_name(b""),
+        }
+    }
+}
+
+/// An iterator that downsamples alignment records.
+///
+/// Templates are first sampled using a [`Sampler`]. If a maximum depth is set, a sampled record is
+/// also discarded when the number of kept records overlapping its alignment start has reached
+/// the maximum depth, which requires the input to be coordinate-sorted. The decision for the first
+/// segment of a template seen is applied to the rest of its segments, so a locus can exceed the
+/// maximum depth by mates of records kept at earlier positions.
+///
+/// With a maximum depth, templates are decided by their first primary record, and the decision is
+/// held until all of its primary records and the supplementary records listed in their other
+/// alignments (`SA`) data fields have been seen. Secondary and supplementary records only follow
+/// the decision of their template and never start one, so those seen while no decision is held,
+/// e.g., secondary records after the template is complete, are discarded. Without a maximum
+/// depth, the sampler alone is template-consistent, and no decisions are held.
+///
+/// Unmapped records are only sampled by read name and are not counted toward the depth.
+pub struct Downsample<'h, I> {
+    header: &'h Header,
+    records: I,
+    sampler: Sampler,
+    max_depth: Option<usize>,
+    active_ends: BinaryHeap<Reverse<(usize, Position)>>,
+    template_decisions: HashMap<Vec<u8>, TemplateDecision>,
+}
+
+struct TemplateDecision {
+    is_kept: Option<bool>,
+    pending_record_count: isize,
+}
+
+impl<'h, I, R> Downsample<'h, I>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    /// Creates a downsampling iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, alignment::RecordBuf};
+    /// use noodles_util::alignment::downsample::{Downsample, Sampler};
+    ///
+    /// let header = sam::Header::default();
+    /// let records = [RecordBuf::default(), RecordBuf::default()];
+    /// let sampler = Sampler::new(1.0, 8)?;
+    ///
+    /// let downsample = Downsample::new(&header, records.into_iter().map(Ok), sampler, None);
+    /// assert_eq!(downsample.count(), 2);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn new(header: &'h Header, records: I, sampler: Sampler, max_depth: Option<usize>) -> Self {
+        Self {
+            header,
+            records,
+            sampler,
+            max_depth,
+            active_ends: BinaryHeap::new(),
+            template_decisions: HashMap::new(),
+        }
+    }
+
+    fn keep(&mut self, record: &R) -> io::Result<bool> {
+        let Some(max_depth) = self.max_depth else {
+            return Ok(self.sampler.keep(record));
+        };
+
+        let flags = record.flags()?;
+        let is_primary = !flags.is_secondary() && !flags.is_supplementary();
+
+        let name = record.name();
+
+        let Some(name) = name.as_ref().map(|name| name.as_bytes()) else {
+            // Without a read name, a record cannot be matched to the rest of its template.
+            return if is_primary {
+                self.sample(record, max_depth)
+            } else {
+                Ok(false)
+            };
+        };
+
+        let (is_kept, pending_record_count) = {
+            let decision = self
+                .template_decisions
+                .entry(name.to_vec())
+                .or_insert_with(|| TemplateDecision {
+                    is_kept: None,
+                    pending_record_count: primary_record_count(flags),
+                });
+
+            decision.pending_record_count += expected_supplementary_record_count(record)?;
+            decision.pending_record_count -= seen_record_count(record)?;
+
+            (decision.is_kept, decision.pending_record_count)
+        };
+
+        let is_kept = match is_kept {
+            Some(true) => {
+                self.activate(record)?;
+                Some(true)
+            }
+            Some(false) => Some(false),
+            None if is_primary => {
+                let is_kept = self.sample(record, max_depth)?;
+
+                if let Some(decision) = self.template_decisions.get_mut(name) {
+                    decision.is_kept = Some(is_kept);
+                }
+
+                Some(is_kept)
+            }
+            None => None,
+        };
+
+        if is_kept.is_some() && pending_record_count <= 0 {
+            self.template_decisions.remove(name);
+        }
+
+        Ok(is_kept.unwrap_or(false))
+    }
+
+    fn sample(&mut self, record: &R, max_depth: usize) -> io::Result<bool> {
+        let mut is_kept = self.sampler.keep(record);
+
+        if is_kept {
+            if let Some(start) = self.evict(record)? {
+                is_kept = self.active_ends.len() < max_depth;
+
+                if is_kept {
+                    let end = record.alignment_end().transpose()?.unwrap_or(start.1);
+                    self.active_ends.push(Reverse((start.0, end)));
+                }
+            }
+        }
+
+        Ok(is_kept)
+    }
+
+    fn activate(&mut self, record: &R) -> io::Result<()> {
+        if let Some((reference_sequence_id, start)) = self.evict(record)? {
+            let end = record.alignment_end().transpose()?.unwrap_or(start);
+            self.active_ends.push(Reverse((reference_sequence_id, end)));
+        }
+
+        Ok(())
+    }
+
+    // Removes the kept records that end before the start of the given record and returns its
+    // start. This returns `None` if the record is unmapped.
+    fn evict(&mut self, record: &R) -> io::Result<Option<(usize, Position)>> {
+        if record.flags()?.is_unmapped() {
+            return Ok(None);
+        }
+
+        let Some(reference_sequence_id) = record.reference_sequence_id(self.header).transpose()?
+        else {
+            return Ok(None);
+        };
+
+        let Some(start) = record.alignment_start().transpose()? else {
+            return Ok(None);
+        };
+
+        let position = (reference_sequence_id, start);
+
+        while let Some(Reverse(end)) = self.active_ends.peek() {
+            if *end < position {
+                self.active_ends.pop();
+            } else {
+                break;
+            }
+        }
+
+        Ok(Some(position))
+    }
+}
+
+impl<'h, I, R> Iterator for Downsample<'h, I>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    type Item = io::Result<R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let record = match self.records.next()? {
+                Ok(record) => record,
+                Err(e) => return Some(Err(e)),
+            };
+
+            match self.keep(&record) {
+                Ok(true) => return Some(Ok(record)),
+                Ok(false) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+// Returns the number of primary records in a template.
+fn primary_record_count(flags: Flags) -> isize {
+    if flags.is_segmented() {
+        2
+    } else {
+        1
+    }
+}
+
+// Returns the number of primary and supplementary records the given record accounts for.
+fn seen_record_count<R>(record: &R) -> io::Result<isize>
+where
+    R: Record,
+{
+    let flags = record.flags()?;
+    Ok(isize::from(!flags.is_secondary()))
+}
+
+// Returns the number of supplementary records listed by a primary record.
+fn expected_supplementary_record_count<R>(record: &R) -> io::Result<isize>
+where
+    R: Record,
+{
+    use noodles_sam::alignment::record::data::field::{Tag, Value};
+
+    let flags = record.flags()?;
+
+    if flags.is_secondary() || flags.is_supplementary() {
+        return Ok(0);
+    }
+
+    match record.data().get(&Tag::OTHER_ALIGNMENTS).transpose()? {
+        Some(Value::String(s)) => {
+            let n = s.split(|&b| b == b';').filter(|t| !t.is_empty()).count();
+            isize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+        _ => Ok(0),
+    }
+}
+
+// 64-bit FNV-1a seeded with the given seed and finalized with the SplitMix64 mixer.
+fn hash(seed: u64, buf: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut h = OFFSET_BASIS;
+
+    for &b in seed.to_le_bytes().iter().chain(buf) {
+        h ^= u64::from(b);
+        h = h.wrapping_mul(PRIME);
+    }
+
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xbf58476d1ce4e5b9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94d049bb133111eb);
+    h ^ (h >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::{
+        alignment::{
+            record::{
+                cigar::{op::Kind, Op},
+                Flags,
+            },
+            record_buf::Cigar,
+            RecordBuf,
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    #[test]
+    fn test_sampler_new() {
+        assert!(Sampler::new(0.5, 0).is_ok());
+        assert!(matches!(
+            Sampler::new(1.5, 0),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert!(Sampler::new(f64::NAN, 0).is_err());
+    }
+
+    #[test]
+    fn test_sampler_keep_name() -> io::Result<()> {
+        let names: Vec<_> = (0..10000).map(|i| format!("r{i}")).collect();
+
+        let sampler = Sampler::new(0.25, 8)?;
+        let n = names
+            .iter()
+            .filter(|name| sampler.keep_name(name.as_bytes()))
+            .count();
+        assert!((2250..2750).contains(&n));
+
+        // The sample is deterministic for a seed and differs between seeds.
+        let sampler_2 = Sampler::new(0.25, 13)?;
+        let a: Vec<_> = names
+            .iter()
+            .map(|name| sampler.keep_name(name.as_bytes()))
+            .collect();
+        let b: Vec<_> = names
+            .iter()
+            .map(|name| sampler.keep_name(name.as_bytes()))
+            .collect();
+        let c: Vec<_> = names
+            .iter()
+            .map(|name| sampler_2.keep_name(name.as_bytes()))
+            .collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_max_depth() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .build();
+
+        let build_record = |name: &[u8], flags, start| -> Result<_, std::num::TryFromIntError> {
+            Ok(RecordBuf::builder()
+                .set_name(name.into())
+                .set_flags(flags)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 4)]))
+                .build())
+        };
+
+        let records = [
+            build_record(b"r0", Flags::SEGMENTED, 1)?,
+            build_record(b"r1", Flags::SEGMENTED, 2)?,
+            build_record(b"r2", Flags::SEGMENTED, 3)?,
+            build_record(b"r0", Flags::SEGMENTED, 4)?,
+            build_record(b"r3", Flags::empty(), 5)?,
+            build_record(b"r2", Flags::SEGMENTED, 6)?,
+            build_record(b"r4", Flags::empty(), 8)?,
+        ];
+
+        let sampler = Sampler::new(1.0, 0)?;
+        let downsample = Downsample::new(&header, records.into_iter().map(Ok), sampler, Some(2));
+
+        let names: Vec<_> = downsample
+            .map(|result| result.map(|record| record.name().cloned().map(Vec::from)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(
+            names,
+            [
+                Some(b"r0".to_vec()),
+                Some(b"r1".to_vec()),
+                Some(b"r0".to_vec()),
+                Some(b"r4".to_vec()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_max_depth_and_supplementary_records() -> Result<(), Box<dyn std::error::Error>>
+    {
+        use noodles_sam::alignment::{record::data::field::Tag, record_buf::data::field::Value};
+
+        let header = Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .build();
+
+        let build_record =
+            |name: &[u8], flags, start, sa: Option<&str>| -> Result<_, std::num::TryFromIntError> {
+                let mut builder = RecordBuf::builder()
+                    .set_name(name.into())
+                    .set_flags(flags)
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::try_from(start)?)
+                    .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 4)]));
+
+                if let Some(sa) = sa {
+                    builder = builder.set_data(
+                        [(Tag::OTHER_ALIGNMENTS, Value::from(sa))]
+                            .into_iter()
+                            .collect(),
+                    );
+                }
+
+                Ok(builder.build())
+            };
+
+        let records = [
+            build_record(b"r0", Flags::SEGMENTED, 1, None)?,
+            build_record(b"r1", Flags::SEGMENTED, 1, Some("sq0,60,+,4M,60,0;"))?,
+            build_record(b"r0", Flags::SEGMENTED, 2, None)?,
+            build_record(b"r1", Flags::SEGMENTED, 3, None)?,
+            // The depth at 60 is 0, but r1 was discarded.
+            build_record(b"r1", Flags::SEGMENTED | Flags::SUPPLEMENTARY, 60, None)?,
+        ];
+
+        let sampler = Sampler::new(1.0, 0)?;
+        let mut downsample =
+            Downsample::new(&header, records.into_iter().map(Ok), sampler, Some(1));
+
+        let names: Vec<_> = downsample
+            .by_ref()
+            .map(|result| result.map(|record| record.name().cloned().map(Vec::from)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(names, [Some(b"r0".to_vec()), Some(b"r0".to_vec())]);
+        assert!(downsample.template_decisions.is_empty());
+
+        let records = [
+            // The supplementary record precedes its primary records.
+            build_record(b"r0", Flags::SEGMENTED | Flags::SUPPLEMENTARY, 1, None)?,
+            build_record(b"r0", Flags::SEGMENTED, 2, Some("sq0,1,+,4M,60,0;"))?,
+            build_record(b"r0", Flags::SEGMENTED, 3, None)?,
+        ];
+
+        let mut downsample =
+            Downsample::new(&header, records.into_iter().map(Ok), sampler, Some(1));
+
+        let names: Vec<_> = downsample
+            .by_ref()
+            .map(|result| result.map(|record| record.name().cloned().map(Vec::from)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(names, [Some(b"r0".to_vec()), Some(b"r0".to_vec())]);
+        assert!(downsample.template_decisions.is_empty());
+
+        let records = [build_record(b"r0", Flags::SEGMENTED, 1, None)?];
+        let mut downsample = Downsample::new(&header, records.into_iter().map(Ok), sampler, None);
+        assert!(downsample.next().transpose()?.is_some());
+        assert!(downsample.template_decisions.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_max_depth_and_secondary_records() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .build();
+
+        let build_record = |name: &[u8], flags, start| -> Result<_, std::num::TryFromIntError> {
+            Ok(RecordBuf::builder()
+                .set_name(name.into())
+                .set_flags(flags)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar(Cigar::from(vec![Op::new(Kind::Match, 4)]))
+                .build())
+        };
+
+        let records = [
+            build_record(b"r0", Flags::SEGMENTED, 1)?,
+            build_record(b"r1", Flags::SEGMENTED, 1)?,
+            build_record(b"r0", Flags::SEGMENTED, 2)?,
+            build_record(b"r1", Flags::SEGMENTED, 3)?,
+            // The depth at 60 is 0, but the primary records of r1 were capped.
+            build_record(b"r1", Flags::SEGMENTED | Flags::SECONDARY, 60)?,
+            build_record(b"r2", Flags::SEGMENTED | Flags::SECONDARY, 70)?,
+        ];
+
+        let sampler = Sampler::new(1.0, 0)?;
+        let mut downsample =
+            Downsample::new(&header, records.into_iter().map(Ok), sampler, Some(1));
+
+        let names: Vec<_> = downsample
+            .by_ref()
+            .map(|result| result.map(|record| record.name().cloned().map(Vec::from)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(names, [Some(b"r0".to_vec()), Some(b"r0".to_vec())]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,364 @@
+//! Alignment record filter expressions.
+
+mod node;
+mod parser;
+
+pub use self::parser::ParseError;
+
+use std::{cmp::Ordering, io, str::FromStr};
+
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        record::{data::field::Value as FieldValue, Cigar as _},
+        Record,
+    },
+};
+
+use self::node::{Field, Node, Operator};
+
+/// An alignment record filter expression.
+///
+/// An expression is a boolean combination (`!`, `&&`, `||`, and parentheses) of comparisons
+/// (`==`, `!=`, `<`, `<=`, `>`, and `>=`) between record fields, data fields, and literals, e.g.,
+/// `mapq>=20 && !flag.duplicate && [NM]<5 && rname=="chr1"`.
+///
+/// `!` binds tighter than comparisons, which bind tighter than `&&`, which binds tighter than
+/// `||`, e.g., `!mapq > 20` is `(!mapq) > 20`.
+///
+/// The record fields are
+///
+///   * `qname`: read name (`*` if missing),
+///   * `flag`: flags, as an integer,
+///   * `flag.<name>`: a flag, where `<name>` is one of `paired`, `proper_pair`, `unmap`,
+///     `munmap`, `reverse`, `mreverse`, `read1`, `read2`, `secondary`, `qcfail`, `duplicate`, or
+///     `supplementary`,
+///   * `rname` and `rnext`: (mate) reference sequence name (`*` if missing),
+///   * `pos`, `endpos`, and `pnext`: 1-based (mate) alignment start or end (0 if missing),
+///   * `mapq`: mapping quality (255 if missing),
+///   * `ncigar`: number of CIGAR operations,
+///   * `tlen`: template length,
+///   * `seq`: sequence, and
+///   * `qlen`: read length.
+///
+/// A data field is referenced by its tag in brackets, e.g., `[NM]`. Integer and floating-point
+/// values compare numerically, and character, string, and hex values compare as strings.
+/// Comparing a missing data field is always false, and comparing a number with a string is an
+/// error.
+///
+/// An operand used as a boolean is true if it is a nonzero number or a nonempty string, e.g.,
+/// `[SA]` is true when a record has a nonempty `SA` data field.
+///
+/// This works over [`Record`], so expressions evaluate the same for SAM, BAM, and CRAM records.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression(Node);
+
+impl Expression {
+    /// Evaluates the expression on the given record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::{
+    ///         record::{data::field::Tag, Flags},
+    ///         record_buf::data::field::Value,
+    ///         RecordBuf,
+    ///     },
+    /// };
+    /// use noodles_util::alignment::expression::Expression;
+    ///
+    /// let header = sam::Header::default();
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_flags(Flags::DUPLICATE)
+    ///     .set_data([(Tag::EDIT_DISTANCE, Value::from(2))].into_iter().collect())
+    ///     .build();
+    ///
+    /// let expression: Expression = "[NM] < 5".parse()?;
+    /// assert!(expression.evaluate(&header, &record)?);
+    ///
+    /// let expression: Expression = "[NM] < 5 && !flag.duplicate".parse()?;
+    /// assert!(!expression.evaluate(&header, &record)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn evaluate<R>(&self, header: &sam::Header, record: &R) -> io::Result<bool>
+    where
+        R: Record + ?Sized,
+    {
+        evaluate(&self.0, header, record).map(|value| value.is_truthy())
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse(s).map(Self)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Missing,
+    Integer(i64),
+    Float(f64),
+    String(Vec<u8>),
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Self::Missing => false,
+            Self::Integer(n) => *n != 0,
+            Self::Float(n) => *n != 0.0,
+            Self::String(s) => !s.is_empty(),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Self::Integer(i64::from(b))
+    }
+}
+
+fn evaluate<R>(node: &Node, header: &sam::Header, record: &R) -> io::Result<Value>
+where
+    R: Record + ?Sized,
+{
+    match node {
+        Node::Or(lhs, rhs) => {
+            let b = evaluate(lhs, header, record)?.is_truthy()
+                || evaluate(rhs, header, record)?.is_truthy();
+
+            Ok(Value::from(b))
+        }
+        Node::And(lhs, rhs) => {
+            let b = evaluate(lhs, header, record)?.is_truthy()
+                && evaluate(rhs, header, record)?.is_truthy();
+
+            Ok(Value::from(b))
+        }
+        Node::Not(node) => {
+            let b = !evaluate(node, header, record)?.is_truthy();
+            Ok(Value::from(b))
+        }
+        Node::Comparison(operator, lhs, rhs) => {
+            let lhs = evaluate(lhs, header, record)?;
+            let rhs = evaluate(rhs, header, record)?;
+            compare(*operator, &lhs, &rhs).map(Value::from)
+        }
+        Node::Field(field) => evaluate_field(*field, header, record),
+        Node::Tag(tag) => match record.data().get(tag).transpose()? {
+            Some(value) => Value::try_from(value),
+            None => Ok(Value::Missing),
+        },
+        Node::Integer(n) => Ok(Value::Integer(*n)),
+        Node::Float(n) => Ok(Value::Float(*n)),
+        Node::String(s) => Ok(Value::String(s.clone())),
+    }
+}
+
+fn evaluate_field<R>(field: Field, header: &sam::Header, record: &R) -> io::Result<Value>
+where
+    R: Record + ?Sized,
+{
+    fn position(position: Option<io::Result<noodles_core::Position>>) -> io::Result<Value> {
+        let n = position.transpose()?.map(usize::from).unwrap_or_default();
+        i64::try_from(n)
+            .map(Value::Integer)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn len(n: usize) -> io::Result<Value> {
+        i64::try_from(n)
+            .map(Value::Integer)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    const MISSING: &[u8] = b"*";
+
+    match field {
+        Field::Name => {
+            let name = record.name().map(|name| name.as_bytes().to_vec());
+            Ok(Value::String(name.unwrap_or_else(|| MISSING.to_vec())))
+        }
+        Field::Flags => {
+            let flags = record.flags()?;
+            Ok(Value::Integer(i64::from(u16::from(flags))))
+        }
+        Field::Flag(flag) => {
+            let flags = record.flags()?;
+            Ok(Value::from(flags.contains(flag)))
+        }
+        Field::ReferenceSequenceName => {
+            let name = record
+                .reference_sequence(header)
+                .transpose()?
+                .map(|(name, _)| name.to_vec());
+
+            Ok(Value::String(name.unwrap_or_else(|| MISSING.to_vec())))
+        }
+        Field::AlignmentStart => position(record.alignment_start()),
+        Field::AlignmentEnd => position(record.alignment_end()),
+        Field::MappingQuality => {
+            let mapping_quality = record
+                .mapping_quality()
+                .transpose()?
+                .map(u8::from)
+                .unwrap_or(255);
+
+            Ok(Value::Integer(i64::from(mapping_quality)))
+        }
+        Field::CigarOpCount => len(record.cigar().len()),
+        Field::MateReferenceSequenceName => {
+            let name = record
+                .mate_reference_sequence(header)
+                .transpose()?
+                .map(|(name, _)| name.to_vec());
+
+            Ok(Value::String(name.unwrap_or_else(|| MISSING.to_vec())))
+        }
+        Field::MateAlignmentStart => position(record.mate_alignment_start()),
+        Field::TemplateLength => record
+            .template_length()
+            .map(|n| Value::Integer(i64::from(n))),
+        Field::Sequence => Ok(Value::String(record.sequence().iter().collect())),
+        Field::ReadLength => len(record.sequence().len()),
+    }
+}
+
+impl TryFrom<FieldValue<'_>> for Value {
+    type Error = io::Error;
+
+    fn try_from(value: FieldValue<'_>) -> Result<Self, Self::Error> {
+        match value {
+            FieldValue::Character(c) => Ok(Self::String(vec![c])),
+            FieldValue::Float(n) => Ok(Self::Float(f64::from(n))),
+            FieldValue::String(s) | FieldValue::Hex(s) => Ok(Self::String(s.to_vec())),
+            FieldValue::Array(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "array data field values cannot be compared",
+            )),
+            value => value
+                .as_int()
+                .map(Self::Integer)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid integer")),
+        }
+    }
+}
+
+fn compare(operator: Operator, lhs: &Value, rhs: &Value) -> io::Result<bool> {
+    let ordering = match (lhs, rhs) {
+        (Value::Missing, _) | (_, Value::Missing) => return Ok(false),
+        (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+        (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+        (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+        (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot compare a number with a string",
+            ))
+        }
+    };
+
+    // Comparisons with NaN are false, except for `!=`.
+    let Some(ordering) = ordering else {
+        return Ok(operator == Operator::NotEqual);
+    };
+
+    Ok(match operator {
+        Operator::Equal => ordering == Ordering::Equal,
+        Operator::NotEqual => ordering != Ordering::Equal,
+        Operator::LessThan => ordering == Ordering::Less,
+        Operator::LessThanOrEqual => ordering != Ordering::Greater,
+        Operator::GreaterThan => ordering == Ordering::Greater,
+        Operator::GreaterThanOrEqual => ordering != Ordering::Less,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_core::Position;
+    use noodles_sam::{
+        alignment::{
+            record::{
+                cigar::{op::Kind, Op},
+                data::field::Tag,
+                Flags, MappingQuality,
+            },
+            record_buf::{data::field::Value as FieldValueBuf, Sequence},
+            RecordBuf,
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_evaluate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "chr1",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .build();
+
+        let record = RecordBuf::builder()
+            .set_name(b"r0".into())
+            .set_flags(Flags::SEGMENTED | Flags::FIRST_SEGMENT)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(8)?)
+            .set_mapping_quality(MappingQuality::try_from(30)?)
+            .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+            .set_template_length(-10)
+            .set_sequence(Sequence::from(b"ACGT"))
+            .set_data(
+                [
+                    (Tag::EDIT_DISTANCE, FieldValueBuf::from(2)),
+                    (Tag::ALIGNMENT_SCORE, FieldValueBuf::from(1.5)),
+                    (Tag::READ_GROUP, FieldValueBuf::from("rg0")),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .build();
+
+        let eval = |s: &str| -> Result<bool, Box<dyn std::error::Error>> {
+            let expression: Expression = s.parse()?;
+            Ok(expression.evaluate(&header, &record)?)
+        };
+
+        assert!(eval(
+            r#"mapq>=20 && !flag.duplicate && [NM]<5 && rname=="chr1""#
+        )?);
+        assert!(eval("flag.paired && flag.read1 && !flag.read2")?);
+        assert!(eval("flag == 65")?);
+        assert!(eval(r#"qname == "r0" && seq == "ACGT" && qlen == 4"#)?);
+        assert!(eval("pos == 8 && endpos == 11 && ncigar == 1")?);
+        assert!(eval(r#"rnext == "*" && pnext == 0 && tlen < -5"#)?);
+        assert!(eval("[AS] > 1 && [AS] < 2 && [NM] >= 2.0")?);
+        assert!(eval(r#"[RG] == "rg0" && [RG] < "rg1""#)?);
+        assert!(eval("[NM] && !([NM] == 2 || mapq < 20) || pos == 8")?);
+
+        // Missing data fields compare false.
+        assert!(!eval("[XS] == 0")?);
+        assert!(!eval("[XS] != 0")?);
+        assert!(!eval("[XS]")?);
+
+        assert!(matches!(
+            eval(r#"[NM] == "2""#),
+            Err(e) if e.downcast_ref::<io::Error>()
+                .map(|e| e.kind() == io::ErrorKind::InvalidInput)
+                .unwrap_or(false)
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use noodles_sam::alignment::record::{data::field::Tag, Flags};
+
+/// An expression syntax tree node.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Node {
+    Or(Box<Node>, Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Not(Box<Node>),
+    Comparison(Operator, Box<Node>, Box<Node>),
+    Field(Field),
+    Tag(Tag),
+    Integer(i64),
+    Float(f64),
+    String(Vec<u8>),
+}
+
+/// A comparison operator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Operator {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+/// A record field.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Field {
+    Name,
+    Flags,
+    Flag(Flags),
+    ReferenceSequenceName,
+    AlignmentStart,
+    AlignmentEnd,
+    MappingQuality,
+    CigarOpCount,
+    MateReferenceSequenceName,
+    MateAlignmentStart,
+    TemplateLength,
+    Sequence,
+    ReadLength,
+}
+
+impl Field {
+    pub fn from_name(s: &str) -> Option<Self> {
+        if let Some(name) = s.strip_prefix("flag.") {
+            return flag_from_name(name).map(Self::Flag);
+        }
+
+        match s {
+            "qname" => Some(Self::Name),
+            "flag" => Some(Self::Flags),
+            "rname" => Some(Self::ReferenceSequenceName),
+            "pos" => Some(Self::AlignmentStart),
+            "endpos" => Some(Self::AlignmentEnd),
+            "mapq" => Some(Self::MappingQuality),
+            "ncigar" => Some(Self::CigarOpCount),
+            "rnext" => Some(Self::MateReferenceSequenceName),
+            "pnext" => Some(Self::MateAlignmentStart),
+            "tlen" => Some(Self::TemplateLength),
+            "seq" => Some(Self::Sequence),
+            "qlen" => Some(Self::ReadLength),
+            _ => None,
+        }
+    }
+}
+
+fn flag_from_name(s: &str) -> Option<Flags> {
+    match s {
+        "paired" => Some(Flags::SEGMENTED),
+        "proper_pair" => Some(Flags::PROPERLY_SEGMENTED),
+        "unmap" => Some(Flags::UNMAPPED),
+        "munmap" => Some(Flags::MATE_UNMAPPED),
+        "reverse" => Some(Flags::REVERSE_COMPLEMENTED),
+        "mreverse" => Some(Flags::MATE_REVERSE_COMPLEMENTED),
+        "read1" => Some(Flags::FIRST_SEGMENT),
+        "read2" => Some(Flags::LAST_SEGMENT),
+        "secondary" => Some(Flags::SECONDARY),
+        "qcfail" => Some(Flags::QC_FAIL),
+        "duplicate" => Some(Flags::DUPLICATE),
+        "supplementary" => Some(Flags::SUPPLEMENTARY),
+        _ => None,
+    }
+}
@@ -0,0 +1,391 @@
+use std::{error, fmt, iter::Peekable, str::CharIndices};
+
+use noodles_sam::alignment::record::data::field::Tag;
+
+use super::node::{Field, Node, Operator};
+
+/// An error returned when an expression fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input ended unexpectedly.
+    UnexpectedEof,
+    /// A character is unexpected.
+    UnexpectedCharacter(char),
+    /// A token is unexpected.
+    UnexpectedToken(String),
+    /// A string literal is not terminated.
+    UnterminatedString,
+    /// A number is invalid.
+    InvalidNumber(String),
+    /// A data field tag is invalid.
+    InvalidTag(String),
+    /// A field name is invalid.
+    InvalidField(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::UnexpectedEof => f.write_str("unexpected end of input"),
+            Self::UnexpectedCharacter(c) => write!(f, "unexpected character: {c:?}"),
+            Self::UnexpectedToken(s) => write!(f, "unexpected token: {s}"),
+            Self::UnterminatedString => f.write_str("unterminated string"),
+            Self::InvalidNumber(s) => write!(f, "invalid number: {s}"),
+            Self::InvalidTag(s) => write!(f, "invalid tag: {s}"),
+            Self::InvalidField(s) => write!(f, "invalid field: {s}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LeftParenthesis,
+    RightParenthesis,
+    Not,
+    And,
+    Or,
+    Operator(Operator),
+    Integer(i64),
+    Float(f64),
+    String(Vec<u8>),
+    Identifier(String),
+    Tag(Tag),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LeftParenthesis => f.write_str("("),
+            Self::RightParenthesis => f.write_str(")"),
+            Self::Not => f.write_str("!"),
+            Self::And => f.write_str("&&"),
+            Self::Or => f.write_str("||"),
+            Self::Operator(Operator::Equal) => f.write_str("=="),
+            Self::Operator(Operator::NotEqual) => f.write_str("!="),
+            Self::Operator(Operator::LessThan) => f.write_str("<"),
+            Self::Operator(Operator::LessThanOrEqual) => f.write_str("<="),
+            Self::Operator(Operator::GreaterThan) => f.write_str(">"),
+            Self::Operator(Operator::GreaterThanOrEqual) => f.write_str(">="),
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Float(n) => write!(f, "{n}"),
+            Self::String(s) => write!(f, "{:?}", String::from_utf8_lossy(s)),
+            Self::Identifier(s) => f.write_str(s),
+            Self::Tag(tag) => {
+                let [a, b] = <[u8; 2]>::from(*tag);
+                write!(f, "[{}{}]", char::from(a), char::from(b))
+            }
+        }
+    }
+}
+
+pub(super) fn parse(s: &str) -> Result<Node, ParseError> {
+    let tokens = tokenize(s)?;
+
+    if tokens.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let mut parser = Parser { tokens, i: 0 };
+    let node = parser.parse_or()?;
+
+    match parser.next() {
+        Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+        None => Ok(node),
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            '!' => {
+                if next_if_eq(&mut chars, '=') {
+                    Token::Operator(Operator::NotEqual)
+                } else {
+                    Token::Not
+                }
+            }
+            '&' => {
+                expect(&mut chars, '&')?;
+                Token::And
+            }
+            '|' => {
+                expect(&mut chars, '|')?;
+                Token::Or
+            }
+            '=' => {
+                expect(&mut chars, '=')?;
+                Token::Operator(Operator::Equal)
+            }
+            '<' => {
+                if next_if_eq(&mut chars, '=') {
+                    Token::Operator(Operator::LessThanOrEqual)
+                } else {
+                    Token::Operator(Operator::LessThan)
+                }
+            }
+            '>' => {
+                if next_if_eq(&mut chars, '=') {
+                    Token::Operator(Operator::GreaterThanOrEqual)
+                } else {
+                    Token::Operator(Operator::GreaterThan)
+                }
+            }
+            '"' => tokenize_string(&mut chars)?,
+            '[' => {
+                let end = s[i..]
+                    .find(']')
+                    .map(|j| i + j)
+                    .ok_or(ParseError::UnexpectedEof)?;
+
+                let raw_tag = &s[i + 1..end];
+
+                while chars.next_if(|&(j, _)| j <= end).is_some() {}
+
+                parse_tag(raw_tag).map(Token::Tag)?
+            }
+            '-' | '.' | '0'..='9' => {
+                let end = take_while(&mut chars, |c| c.is_ascii_alphanumeric() || c == '.');
+                parse_number(&s[i..end.unwrap_or(s.len())])?
+            }
+            _ if c.is_ascii_alphabetic() => {
+                let end = take_while(&mut chars, |c| {
+                    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+                });
+
+                Token::Identifier(s[i..end.unwrap_or(s.len())].into())
+            }
+            _ => return Err(ParseError::UnexpectedCharacter(c)),
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn next_if_eq(chars: &mut Peekable<CharIndices<'_>>, expected: char) -> bool {
+    chars.next_if(|&(_, c)| c == expected).is_some()
+}
+
+fn expect(chars: &mut Peekable<CharIndices<'_>>, expected: char) -> Result<(), ParseError> {
+    match chars.next() {
+        Some((_, c)) if c == expected => Ok(()),
+        Some((_, c)) => Err(ParseError::UnexpectedCharacter(c)),
+        None => Err(ParseError::UnexpectedEof),
+    }
+}
+
+// Consumes the characters that satisfy the predicate and returns the index of the next character,
+// if any.
+fn take_while<P>(chars: &mut Peekable<CharIndices<'_>>, predicate: P) -> Option<usize>
+where
+    P: Fn(char) -> bool,
+{
+    while chars.next_if(|&(_, c)| predicate(c)).is_some() {}
+    chars.peek().map(|&(i, _)| i)
+}
+
+fn tokenize_string(chars: &mut Peekable<CharIndices<'_>>) -> Result<Token, ParseError> {
+    let mut buf = String::new();
+
+    loop {
+        match chars.next() {
+            Some((_, '"')) => return Ok(Token::String(buf.into_bytes())),
+            Some((_, '\\')) => match chars.next() {
+                Some((_, c)) => buf.push(c),
+                None => return Err(ParseError::UnterminatedString),
+            },
+            Some((_, c)) => buf.push(c),
+            None => return Err(ParseError::UnterminatedString),
+        }
+    }
+}
+
+fn parse_tag(s: &str) -> Result<Tag, ParseError> {
+    match s.as_bytes() {
+        &[a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphanumeric() => Ok(Tag::new(a, b)),
+        _ => Err(ParseError::InvalidTag(s.into())),
+    }
+}
+
+fn parse_number(s: &str) -> Result<Token, ParseError> {
+    let token = if s.contains(['.', 'e', 'E']) {
+        s.parse().ok().map(Token::Float)
+    } else {
+        s.parse().ok().map(Token::Integer)
+    };
+
+    token.ok_or_else(|| ParseError::InvalidNumber(s.into()))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    i: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.i)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.i).cloned();
+        self.i += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Node, ParseError> {
+        let mut node = self.parse_and()?;
+
+        while self.peek() == Some(&Token::Or) {
+            self.i += 1;
+            let rhs = self.parse_and()?;
+            node = Node::Or(Box::new(node), Box::new(rhs));
+        }
+
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, ParseError> {
+        let mut node = self.parse_comparison()?;
+
+        while self.peek() == Some(&Token::And) {
+            self.i += 1;
+            let rhs = self.parse_comparison()?;
+            node = Node::And(Box::new(node), Box::new(rhs));
+        }
+
+        Ok(node)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Node, ParseError> {
+        let lhs = self.parse_unary()?;
+
+        if let Some(&Token::Operator(operator)) = self.peek() {
+            self.i += 1;
+            let rhs = self.parse_unary()?;
+            Ok(Node::Comparison(operator, Box::new(lhs), Box::new(rhs)))
+        } else {
+            Ok(lhs)
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Node, ParseError> {
+        if self.peek() == Some(&Token::Not) {
+            self.i += 1;
+            let node = self.parse_unary()?;
+            Ok(Node::Not(Box::new(node)))
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, ParseError> {
+        match self.next().ok_or(ParseError::UnexpectedEof)? {
+            Token::LeftParenthesis => {
+                let node = self.parse_or()?;
+
+                match self.next() {
+                    Some(Token::RightParenthesis) => Ok(node),
+                    Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+                    None => Err(ParseError::UnexpectedEof),
+                }
+            }
+            Token::Integer(n) => Ok(Node::Integer(n)),
+            Token::Float(n) => Ok(Node::Float(n)),
+            Token::String(s) => Ok(Node::String(s)),
+            Token::Tag(tag) => Ok(Node::Tag(tag)),
+            Token::Identifier(s) => Field::from_name(&s)
+                .map(Node::Field)
+                .ok_or(ParseError::InvalidField(s)),
+            token => Err(ParseError::UnexpectedToken(token.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::alignment::record::Flags;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(r#"mapq>=20 && !flag.duplicate || rname=="chr\"1""#),
+            Ok(Node::Or(
+                Box::new(Node::And(
+                    Box::new(Node::Comparison(
+                        Operator::GreaterThanOrEqual,
+                        Box::new(Node::Field(Field::MappingQuality)),
+                        Box::new(Node::Integer(20)),
+                    )),
+                    Box::new(Node::Not(Box::new(Node::Field(Field::Flag(
+                        Flags::DUPLICATE
+                    ))))),
+                )),
+                Box::new(Node::Comparison(
+                    Operator::Equal,
+                    Box::new(Node::Field(Field::ReferenceSequenceName)),
+                    Box::new(Node::String(b"chr\"1".to_vec())),
+                )),
+            ))
+        );
+
+        assert_eq!(
+            parse("([NM] < 5) && tlen > -1.5"),
+            Ok(Node::And(
+                Box::new(Node::Comparison(
+                    Operator::LessThan,
+                    Box::new(Node::Tag(Tag::EDIT_DISTANCE)),
+                    Box::new(Node::Integer(5)),
+                )),
+                Box::new(Node::Comparison(
+                    Operator::GreaterThan,
+                    Box::new(Node::Field(Field::TemplateLength)),
+                    Box::new(Node::Float(-1.5)),
+                )),
+            ))
+        );
+
+        assert_eq!(
+            parse("!mapq > 20"),
+            Ok(Node::Comparison(
+                Operator::GreaterThan,
+                Box::new(Node::Not(Box::new(Node::Field(Field::MappingQuality)))),
+                Box::new(Node::Integer(20)),
+            ))
+        );
+
+        assert_eq!(parse(""), Err(ParseError::Empty));
+        assert_eq!(parse("mapq >="), Err(ParseError::UnexpectedEof));
+        assert_eq!(parse("(mapq"), Err(ParseError::UnexpectedEof));
+        assert_eq!(parse("mapq = 1"), Err(ParseError::UnexpectedCharacter(' ')));
+        assert_eq!(
+            parse("mapq 1"),
+            Err(ParseError::UnexpectedToken(String::from("1")))
+        );
+        assert_eq!(parse("\"chr1"), Err(ParseError::UnterminatedString));
+        assert_eq!(
+            parse("1x"),
+            Err(ParseError::InvalidNumber(String::from("1x")))
+        );
+        assert_eq!(parse("[N]"), Err(ParseError::InvalidTag(String::from("N"))));
+        assert_eq!(
+            parse("flag.dup"),
+            Err(ParseError::InvalidField(String::from("flag.dup")))
+        );
+        assert_eq!(parse("mapq # 1"), Err(ParseError::UnexpectedCharacter('#')));
+    }
+}
@@ -0,0 +1,526 @@
+//! Conversions between alignment records and FASTQ records.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Write},
+};
+
+use noodles_fastq as fastq;
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        record::{data::field::Tag, Flags},
+        record_buf::{data::field::Value, QualityScores, Sequence},
+        Record, RecordBuf,
+    },
+};
+
+use super::io::Reader;
+
+// § 1.4.11 "QUAL" (2024-05-16): "...QUAL plus 33..."
+const QUALITY_SCORE_OFFSET: u8 = b'!';
+
+const DEFAULT_QUALITY_SCORE: u8 = 1;
+const MISSING_NAME: &[u8] = b"*";
+
+/// A FASTQ writer for alignment records.
+///
+/// Paired segments are written to the read 1 and read 2 writers, and all other primary records
+/// are written to the singletons writer. Mates are paired by read name, so input need not be
+/// name-grouped, but segments whose mate is never seen are only written to the singletons writer
+/// when [`Self::finish`] is called.
+///
+/// Secondary and supplementary records are skipped.
+pub struct Writer<W> {
+    read_1: fastq::Writer<W>,
+    read_2: fastq::Writer<W>,
+    singletons: fastq::Writer<W>,
+    tags: Vec<Tag>,
+    unpaired: BTreeMap<Vec<u8>, (Flags, fastq::Record)>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a FASTQ writer for alignment records.
+    ///
+    /// The data fields with the given tags are copied into the FASTQ record description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::record::data::field::Tag;
+    /// use noodles_util::alignment::fastq;
+    ///
+    /// let writer = fastq::Writer::new(Vec::new(), Vec::new(), Vec::new(), vec![Tag::READ_GROUP]);
+    /// ```
+    pub fn new(read_1: W, read_2: W, singletons: W, tags: Vec<Tag>) -> Self {
+        Self {
+            read_1: fastq::Writer::new(read_1),
+            read_2: fastq::Writer::new(read_2),
+            singletons: fastq::Writer::new(singletons),
+            tags,
+            unpaired: BTreeMap::new(),
+        }
+    }
+
+    /// Writes an alignment record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::{record::Flags, RecordBuf};
+    /// use noodles_util::alignment::fastq;
+    ///
+    /// let mut writer = fastq::Writer::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_name(b"r0".into())
+    ///     .set_flags(Flags::SEGMENTED | Flags::UNMAPPED | Flags::FIRST_SEGMENT)
+    ///     .build();
+    /// writer.write_record(&record)?;
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_name(b"r0".into())
+    ///     .set_flags(Flags::SEGMENTED | Flags::UNMAPPED | Flags::LAST_SEGMENT)
+    ///     .build();
+    /// writer.write_record(&record)?;
+    ///
+    /// writer.finish()?;
+    ///
+    /// let (read_1, read_2, singletons) = writer.into_inner();
+    /// assert_eq!(read_1, b"@r0\n\n+\n\n");
+    /// assert_eq!(read_2, b"@r0\n\n+\n\n");
+    /// assert!(singletons.is_empty());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn write_record<R>(&mut self, record: &R) -> io::Result<()>
+    where
+        R: Record + ?Sized,
+    {
+        let flags = record.flags()?;
+
+        if flags.is_secondary() || flags.is_supplementary() {
+            return Ok(());
+        }
+
+        let fastq_record = alignment_record_to_fastq_record(record, &self.tags)?;
+
+        if !flags.is_segmented() || fastq_record.name() == MISSING_NAME {
+            return self.singletons.write_record(&fastq_record);
+        }
+
+        if let Some((mate_flags, mate_fastq_record)) = self.unpaired.remove(fastq_record.name()) {
+            match (flags.is_first_segment(), mate_flags.is_first_segment()) {
+                (true, false) => self.write_pair(&fastq_record, &mate_fastq_record),
+                (false, true) => self.write_pair(&mate_fastq_record, &fastq_record),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid segment pair",
+                )),
+            }
+        } else {
+            self.unpaired
+                .insert(fastq_record.name().to_vec(), (flags, fastq_record));
+
+            Ok(())
+        }
+    }
+
+    /// Writes segments whose mate was not found to the singletons writer.
+    pub fn finish(&mut self) -> io::Result<()> {
+        for (_, (_, fastq_record)) in std::mem::take(&mut self.unpaired) {
+            self.singletons.write_record(&fastq_record)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the underlying read 1, read 2, and singletons writers.
+    pub fn into_inner(self) -> (W, W, W) {
+        (
+            self.read_1.into_inner(),
+            self.read_2.into_inner(),
+            self.singletons.into_inner(),
+        )
+    }
+
+    fn write_pair(&mut self, read_1: &fastq::Record, read_2: &fastq::Record) -> io::Result<()> {
+        self.read_1.write_record(read_1)?;
+        self.read_2.write_record(read_2)?;
+        Ok(())
+    }
+}
+
+/// Writes all records of an alignment reader as FASTQ records.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use noodles_util::alignment::{self, fastq};
+///
+/// let data = Cursor::new(b"@HD\tVN:1.6
+/// r0\t77\t*\t0\t255\t*\t*\t0\t0\tAC\tND\tRG:Z:rg0
+/// r0\t157\t*\t0\t255\t*\t*\t0\t0\tAC\tLS\tRG:Z:rg0
+/// r1\t4\t*\t0\t255\t*\t*\t0\t0\tGT\t*
+/// ");
+///
+/// let mut reader = alignment::io::reader::Builder::default().build_from_reader(data)?;
+/// let header = reader.read_header()?;
+///
+/// let mut writer = fastq::Writer::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+/// fastq::write_records(&mut reader, &header, &mut writer)?;
+///
+/// let (read_1, read_2, singletons) = writer.into_inner();
+/// assert_eq!(read_1, b"@r0\nAC\n+\nND\n");
+/// assert_eq!(read_2, b"@r0\nGT\n+\nSL\n");
+/// assert_eq!(singletons, b"@r1\nGT\n+\n\"\"\n");
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn write_records<R, W>(
+    reader: &mut Reader<R>,
+    header: &sam::Header,
+    writer: &mut Writer<W>,
+) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    for result in reader.records(header) {
+        let record = result?;
+        writer.write_record(&record)?;
+    }
+
+    writer.finish()
+}
+
+/// Converts an alignment record to a FASTQ record.
+///
+/// The sequence and quality scores are restored to the original read orientation, i.e., they are
+/// reverse complemented and reversed, respectively, if the record is reverse complemented.
+/// Missing quality scores are set to a score of 1.
+///
+/// The data fields with the given tags are copied into the FASTQ record description, formatted
+/// as in SAM and separated by tabs.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::alignment::{
+///     record::{data::field::Tag, Flags},
+///     record_buf::{data::field::Value, QualityScores, Sequence},
+///     RecordBuf,
+/// };
+/// use noodles_util::alignment::fastq::alignment_record_to_fastq_record;
+///
+/// let record = RecordBuf::builder()
+///     .set_name(b"r0".into())
+///     .set_flags(Flags::REVERSE_COMPLEMENTED)
+///     .set_sequence(Sequence::from(b"AACG"))
+///     .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50]))
+///     .set_data([(Tag::READ_GROUP, Value::from("rg0"))].into_iter().collect())
+///     .build();
+///
+/// let fastq_record = alignment_record_to_fastq_record(&record, &[Tag::READ_GROUP])?;
+///
+/// assert_eq!(fastq_record.name(), b"r0");
+/// assert_eq!(fastq_record.description(), b"RG:Z:rg0");
+/// assert_eq!(fastq_record.sequence(), b"CGTT");
+/// assert_eq!(fastq_record.quality_scores(), b"SLDN");
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn alignment_record_to_fastq_record<R>(record: &R, tags: &[Tag]) -> io::Result<fastq::Record>
+where
+    R: Record + ?Sized,
+{
+    const DESCRIPTION_DELIMITER: u8 = b'\t';
+
+    let flags = record.flags()?;
+
+    let name = record
+        .name()
+        .map(|name| name.as_bytes().to_vec())
+        .unwrap_or_else(|| MISSING_NAME.to_vec());
+
+    let mut description = Vec::new();
+    let data = record.data();
+
+    for tag in tags {
+        if let Some(value) = data.get(tag).transpose()? {
+            if !description.is_empty() {
+                description.push(DESCRIPTION_DELIMITER);
+            }
+
+            sam::io::writer::record::write_field(&mut description, *tag, &value)?;
+        }
+    }
+
+    let mut sequence: Vec<_> = record.sequence().iter().collect();
+
+    let quality_scores = record.quality_scores();
+    let mut quality_scores: Vec<_> = if quality_scores.is_empty() {
+        vec![DEFAULT_QUALITY_SCORE; sequence.len()]
+    } else {
+        quality_scores.iter().collect()
+    };
+
+    if flags.is_reverse_complemented() {
+        sequence.reverse();
+        sequence
+            .iter_mut()
+            .for_each(|base| *base = complement(*base));
+        quality_scores.reverse();
+    }
+
+    let quality_scores: Vec<_> = quality_scores
+        .into_iter()
+        .map(|score| {
+            score
+                .checked_add(QUALITY_SCORE_OFFSET)
+                .filter(|n| n.is_ascii_graphic())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid quality score"))
+        })
+        .collect::<io::Result<_>>()?;
+
+    Ok(fastq::Record::new(
+        fastq::record::Definition::new(name, description),
+        sequence,
+        quality_scores,
+    ))
+}
+
+/// Converts a FASTQ record to an unmapped alignment record.
+///
+/// A trailing `/1` or `/2` is removed from the read name. If a read group ID is given, it is set
+/// as the read group (`RG`) data field.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq as fastq;
+/// use noodles_sam::alignment::{
+///     record::{data::field::Tag, Flags},
+///     record_buf::{data::field::Value, QualityScores, Sequence},
+/// };
+/// use noodles_util::alignment::fastq::fastq_record_to_alignment_record;
+///
+/// let fastq_record = fastq::Record::new(
+///     fastq::record::Definition::new("r0/1", ""),
+///     "ACGT",
+///     "NDLS",
+/// );
+///
+/// let record = fastq_record_to_alignment_record(&fastq_record, Flags::UNMAPPED, Some("rg0"))?;
+///
+/// assert_eq!(record.name().map(|name| name.as_ref()), Some(&b"r0"[..]));
+/// assert_eq!(record.flags(), Flags::UNMAPPED);
+/// assert_eq!(record.sequence(), &Sequence::from(b"ACGT"));
+/// assert_eq!(record.quality_scores(), &QualityScores::from(vec![45, 35, 43, 50]));
+/// assert_eq!(record.data().get(&Tag::READ_GROUP), Some(&Value::from("rg0")));
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn fastq_record_to_alignment_record(
+    fastq_record: &fastq::Record,
+    flags: Flags,
+    read_group_id: Option<&str>,
+) -> io::Result<RecordBuf> {
+    let name = strip_segment_suffix(fastq_record.name());
+
+    let quality_scores: Vec<_> = fastq_record
+        .quality_scores()
+        .iter()
+        .map(|&n| {
+            n.checked_sub(QUALITY_SCORE_OFFSET)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid quality score"))
+        })
+        .collect::<io::Result<_>>()?;
+
+    let mut builder = RecordBuf::builder()
+        .set_name(name.into())
+        .set_flags(flags)
+        .set_sequence(Sequence::from(fastq_record.sequence().to_vec()))
+        .set_quality_scores(QualityScores::from(quality_scores));
+
+    if let Some(id) = read_group_id {
+        builder = builder.set_data([(Tag::READ_GROUP, Value::from(id))].into_iter().collect());
+    }
+
+    Ok(builder.build())
+}
+
+/// Returns an iterator over pairs of unmapped alignment records read from paired FASTQ readers.
+///
+/// Each pair of FASTQ records must have the same read name, ignoring a trailing `/1` or `/2`.
+/// The records are flagged as segmented and unmapped with an unmapped mate, and as the first and
+/// last segment, respectively.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq as fastq;
+/// use noodles_sam::alignment::record::Flags;
+/// use noodles_util::alignment::fastq::read_unmapped_pairs;
+///
+/// let mut read_1 = fastq::Reader::new(&b"@r0/1\nAC\n+\nND\n"[..]);
+/// let mut read_2 = fastq::Reader::new(&b"@r0/2\nGT\n+\nLS\n"[..]);
+///
+/// let mut pairs = read_unmapped_pairs(&mut read_1, &mut read_2, Some("rg0"));
+///
+/// let (record_1, record_2) = pairs.next().transpose()?.expect("missing pair");
+/// assert!(record_1.flags().is_first_segment());
+/// assert!(record_2.flags().is_last_segment());
+///
+/// assert!(pairs.next().is_none());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn read_unmapped_pairs<'a, R>(
+    read_1: &'a mut fastq::Reader<R>,
+    read_2: &'a mut fastq::Reader<R>,
+    read_group_id: Option<&'a str>,
+) -> impl Iterator<Item = io::Result<(RecordBuf, RecordBuf)>> + 'a
+where
+    R: io::BufRead,
+{
+    const FLAGS: Flags = Flags::SEGMENTED
+        .union(Flags::UNMAPPED)
+        .union(Flags::MATE_UNMAPPED);
+
+    let mut fastq_record_1 = fastq::Record::default();
+    let mut fastq_record_2 = fastq::Record::default();
+
+    std::iter::from_fn(move || {
+        let n = match read_1.read_record(&mut fastq_record_1) {
+            Ok(n) => n,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let m = match read_2.read_record(&mut fastq_record_2) {
+            Ok(m) => m,
+            Err(e) => return Some(Err(e)),
+        };
+
+        match (n, m) {
+            (0, 0) => return None,
+            (0, _) | (_, 0) => {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "paired FASTQ files have different record counts",
+                )))
+            }
+            _ => {}
+        }
+
+        if strip_segment_suffix(fastq_record_1.name())
+            != strip_segment_suffix(fastq_record_2.name())
+        {
+            return Some(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "paired FASTQ records have different names",
+            )));
+        }
+
+        let result = fastq_record_to_alignment_record(
+            &fastq_record_1,
+            FLAGS | Flags::FIRST_SEGMENT,
+            read_group_id,
+        )
+        .and_then(|record_1| {
+            fastq_record_to_alignment_record(
+                &fastq_record_2,
+                FLAGS | Flags::LAST_SEGMENT,
+                read_group_id,
+            )
+            .map(|record_2| (record_1, record_2))
+        });
+
+        Some(result)
+    })
+}
+
+fn strip_segment_suffix(name: &[u8]) -> &[u8] {
+    name.strip_suffix(b"/1")
+        .or_else(|| name.strip_suffix(b"/2"))
+        .unwrap_or(name)
+}
+
+fn complement(base: u8) -> u8 {
+    match base {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        b'U' => b'A',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        b'a' => b't',
+        b'c' => b'g',
+        b'g' => b'c',
+        b't' => b'a',
+        b'u' => b'a',
+        _ => base,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_record() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+
+        let build_record = |name: &[u8], flags| {
+            RecordBuf::builder()
+                .set_name(name.into())
+                .set_flags(flags)
+                .set_sequence(Sequence::from(b"AC"))
+                .set_quality_scores(QualityScores::from(vec![45, 35]))
+                .build()
+        };
+
+        let flags = Flags::SEGMENTED | Flags::UNMAPPED | Flags::MATE_UNMAPPED;
+        writer.write_record(&build_record(b"r0", flags | Flags::LAST_SEGMENT))?;
+        writer.write_record(&build_record(b"r1", flags | Flags::FIRST_SEGMENT))?;
+        writer.write_record(&build_record(b"r0", flags | Flags::FIRST_SEGMENT))?;
+        writer.write_record(&build_record(b"r2", Flags::UNMAPPED))?;
+        writer.write_record(&build_record(b"r3", Flags::SECONDARY))?;
+        writer.finish()?;
+
+        let (read_1, read_2, singletons) = writer.into_inner();
+        assert_eq!(read_1, b"@r0\nAC\n+\nND\n");
+        assert_eq!(read_2, b"@r0\nAC\n+\nND\n");
+        assert_eq!(singletons, b"@r2\nAC\n+\nND\n@r1\nAC\n+\nND\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_unmapped_pairs() {
+        let mut read_1 = fastq::Reader::new(&b"@r0\nAC\n+\nND\n@r1\nAC\n+\nND\n"[..]);
+        let mut read_2 = fastq::Reader::new(&b"@r0\nGT\n+\nLS\n"[..]);
+        let mut pairs = read_unmapped_pairs(&mut read_1, &mut read_2, None);
+        assert!(matches!(pairs.next(), Some(Ok(_))));
+        assert!(matches!(pairs.next(), Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData));
+
+        let mut read_1 = fastq::Reader::new(&b"@r0\nAC\n+\nND\n"[..]);
+        let mut read_2 = fastq::Reader::new(&b"@r1\nGT\n+\nLS\n"[..]);
+        let mut pairs = read_unmapped_pairs(&mut read_1, &mut read_2, None);
+        assert!(matches!(pairs.next(), Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn test_strip_segment_suffix() {
+        assert_eq!(strip_segment_suffix(b"r0/1"), b"r0");
+        assert_eq!(strip_segment_suffix(b"r0/2"), b"r0");
+        assert_eq!(strip_segment_suffix(b"r0/3"), b"r0/3");
+        assert_eq!(strip_segment_suffix(b"r0"), b"r0");
+    }
+}
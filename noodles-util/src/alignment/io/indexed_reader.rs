@@ -1,17 +1,21 @@
 //! Indexed alignment reader.
 
 mod builder;
+mod targets;
 
 pub use self::builder::Builder;
 
-use std::io::{self, Read, Seek};
+use std::io::{self, Read, Seek, SeekFrom};
 
 use noodles_bam as bam;
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
 use noodles_cram as cram;
+use noodles_csi as csi;
 use noodles_sam::{self as sam, alignment::Record};
 
+use self::targets::Targets;
+
 /// An indexed alignment reader.
 pub enum IndexedReader<R> {
     /// SAM.
@@ -108,4 +112,371 @@ where
 
         Ok(records)
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// Unlike querying each region separately, records are read once and returned in file order,
+    /// even if they intersect multiple regions.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_util::alignment;
+    ///
+    /// let mut reader = alignment::io::indexed_reader::Builder::default()
+    ///     .build_from_path("sample.bam")?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let regions = ["sq0:8-13".parse()?, "sq0:10-21".parse()?];
+    ///
+    /// for result in reader.query_regions(&header, &regions)? {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<Box<dyn Record>>> + 'r> {
+        let targets = Targets::new(header, regions)?;
+        self.query_targets(header, targets)
+    }
+
+    /// Returns an iterator over records that intersect any of the given regions and their mates.
+    ///
+    /// This is the same as [`Self::query_regions`] but also includes the mates of the records in
+    /// the regions, even if the mates do not intersect any of the regions. This requires reading
+    /// the records in the regions twice.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_util::alignment;
+    ///
+    /// let mut reader = alignment::io::indexed_reader::Builder::default()
+    ///     .build_from_path("sample.bam")?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let regions = ["sq0:8-13".parse()?];
+    ///
+    /// for result in reader.query_regions_with_mates(&header, &regions)? {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions_with_mates<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<Box<dyn Record>>> + 'r> {
+        let mut targets = Targets::new(header, regions)?;
+
+        for result in self.query_targets(header, Targets::new(header, regions)?)? {
+            let record = result?;
+            targets.add_mate(header, &record)?;
+        }
+
+        self.query_targets(header, targets)
+    }
+
+    fn query_targets<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        targets: Targets,
+    ) -> io::Result<impl Iterator<Item = io::Result<Box<dyn Record>>> + 'r> {
+        let records: Box<dyn Iterator<Item = io::Result<Box<dyn Record>>>> = match self {
+            Self::Sam(reader) => {
+                let chunks = targets.chunks(reader.index())?;
+                let mut reader =
+                    sam::io::Reader::from(csi::io::Query::new(reader.get_mut(), chunks));
+
+                Box::new(std::iter::from_fn(move || {
+                    let mut record = sam::Record::default();
+
+                    match reader.read_record(&mut record) {
+                        Ok(0) => None,
+                        Ok(_) => Some(Ok(Box::new(record) as Box<dyn Record>)),
+                        Err(e) => Some(Err(e)),
+                    }
+                }))
+            }
+            Self::Bam(reader) => {
+                let chunks = targets.chunks(reader.index())?;
+                let mut reader =
+                    bam::io::Reader::from(csi::io::Query::new(reader.get_mut(), chunks));
+
+                Box::new(std::iter::from_fn(move || {
+                    let mut record = bam::Record::default();
+
+                    match reader.read_record(&mut record) {
+                        Ok(0) => None,
+                        Ok(_) => Some(Ok(Box::new(record) as Box<dyn Record>)),
+                        Err(e) => Some(Err(e)),
+                    }
+                }))
+            }
+            Self::Cram(reader) => {
+                let mut offsets = targets.container_offsets(reader.index()).into_iter();
+
+                Box::new(
+                    std::iter::from_fn(move || {
+                        let offset = offsets.next()?;
+                        Some(read_cram_container_records(reader, header, offset))
+                    })
+                    .flat_map(|result| match result {
+                        Ok(records) => records.into_iter().map(Ok).collect(),
+                        Err(e) => vec![Err(e)],
+                    }),
+                )
+            }
+        };
+
+        Ok(records.filter_map(move |result| {
+            result
+                .and_then(|record| {
+                    targets
+                        .contains(header, &record)
+                        .map(|is_target| is_target.then_some(record))
+                })
+                .transpose()
+        }))
+    }
+}
+
+fn read_cram_container_records<R>(
+    reader: &mut cram::io::IndexedReader<R>,
+    header: &sam::Header,
+    offset: u64,
+) -> io::Result<Vec<Box<dyn Record>>>
+where
+    R: Read + Seek,
+{
+    reader.get_mut().seek(SeekFrom::Start(offset))?;
+
+    let Some(container) = reader.read_data_container()? else {
+        return Ok(Vec::new());
+    };
+
+    let compression_header = container.compression_header();
+    let mut alignment_records = Vec::new();
+
+    for slice in container.slices() {
+        let mut records = slice.records(compression_header)?;
+
+        slice.resolve_records(
+            reader.reference_sequence_repository(),
+            header,
+            compression_header,
+            &mut records,
+        )?;
+
+        for record in records {
+            let alignment_record = record.try_into_alignment_record(header)?;
+            alignment_records.push(Box::new(alignment_record) as Box<dyn Record>);
+        }
+    }
+
+    Ok(alignment_records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_core::Position;
+    use noodles_csi::binning_index::{
+        index::reference_sequence::{bin::Chunk, index::BinnedIndex},
+        Indexer,
+    };
+    use noodles_fasta::{
+        self as fasta,
+        record::{Definition, Sequence},
+    };
+    use noodles_sam::{
+        alignment::{
+            io::Write,
+            record::{
+                cigar::{op::Kind, Op},
+                Flags,
+            },
+            RecordBuf,
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+
+    type TestIndexedReader = IndexedReader<io::BufReader<io::Cursor<Vec<u8>>>>;
+
+    const SQ0_LN: usize = 100;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        Ok(sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(SQ0_LN)?),
+            )
+            .build())
+    }
+
+    fn build_reference_sequence_repository() -> fasta::Repository {
+        fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(vec![b'A'; SQ0_LN]),
+        )])
+    }
+
+    fn build_records() -> Result<Vec<RecordBuf>, Box<dyn std::error::Error>> {
+        let build_record =
+            |name: &[u8], flags, start, mate_start| -> Result<_, std::num::TryFromIntError> {
+                Ok(RecordBuf::builder()
+                    .set_name(name.into())
+                    .set_flags(flags)
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::try_from(start)?)
+                    .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                    .set_mate_reference_sequence_id(0)
+                    .set_mate_alignment_start(Position::try_from(mate_start)?)
+                    .set_sequence(b"AAAA".to_vec().into())
+                    .set_quality_scores(vec![45, 35, 43, 50].into())
+                    .build())
+            };
+
+        Ok(vec![
+            build_record(b"r0", Flags::SEGMENTED | Flags::FIRST_SEGMENT, 1, 50)?,
+            build_record(b"r1", Flags::empty(), 8, 8)?,
+            build_record(b"r2", Flags::empty(), 20, 20)?,
+            build_record(b"r0", Flags::SEGMENTED | Flags::LAST_SEGMENT, 50, 1)?,
+        ])
+    }
+
+    fn build_bam_reader() -> Result<(TestIndexedReader, sam::Header), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let mut writer = bam::io::IndexedWriter::new(Vec::new());
+        writer.write_alignment_header(&header)?;
+
+        for record in &build_records()? {
+            writer.write_alignment_record(&header, record)?;
+        }
+
+        let index = writer.finish(&header)?;
+        let data = writer.get_ref().get_ref().clone();
+
+        let mut reader = Builder::default()
+            .set_index(index)
+            .build_from_reader(io::Cursor::new(data))?;
+
+        reader.read_header()?;
+
+        Ok((reader, header))
+    }
+
+    fn build_sam_reader() -> Result<(TestIndexedReader, sam::Header), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let mut writer = sam::io::Writer::new(bgzf::Writer::new(Vec::new()));
+        writer.write_header(&header)?;
+
+        let mut indexer = Indexer::<BinnedIndex>::new(14, 5);
+
+        for record in &build_records()? {
+            let start_position = writer.get_ref().virtual_position();
+            writer.write_alignment_record(&header, record)?;
+            let end_position = writer.get_ref().virtual_position();
+
+            let context = match (
+                record.reference_sequence_id(),
+                record.alignment_start(),
+                record.alignment_end(),
+            ) {
+                (Some(id), Some(start), Some(end)) => Some((id, start, end, true)),
+                _ => None,
+            };
+
+            indexer.add_record(context, Chunk::new(start_position, end_position))?;
+        }
+
+        let index = indexer.build(header.reference_sequences().len());
+        let data = writer.into_inner().finish()?;
+
+        let mut reader = Builder::default()
+            .set_index(index)
+            .build_from_reader(io::Cursor::new(data))?;
+
+        reader.read_header()?;
+
+        Ok((reader, header))
+    }
+
+    fn build_cram_reader() -> Result<(TestIndexedReader, sam::Header), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let repository = build_reference_sequence_repository();
+
+        let builder = cram::io::writer::Builder::default()
+            .set_reference_sequence_repository(repository.clone());
+        let mut writer = cram::io::IndexedWriter::from_builder(builder, Vec::new());
+        writer.write_alignment_header(&header)?;
+
+        for record in &build_records()? {
+            writer.write_alignment_record(&header, record)?;
+        }
+
+        let index = cram::io::IndexedWriter::finish(&mut writer, &header)?;
+        let data = writer.get_ref().clone();
+
+        let mut reader = Builder::default()
+            .set_reference_sequence_repository(repository)
+            .set_index(index)
+            .build_from_reader(io::Cursor::new(data))?;
+
+        reader.read_header()?;
+
+        Ok((reader, header))
+    }
+
+    fn names<I>(records: I) -> io::Result<Vec<Vec<u8>>>
+    where
+        I: Iterator<Item = io::Result<Box<dyn Record>>>,
+    {
+        records
+            .map(|result| {
+                result.map(|record| {
+                    record
+                        .name()
+                        .map(|name| name.as_bytes().to_vec())
+                        .unwrap_or_default()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_query_regions() -> Result<(), Box<dyn std::error::Error>> {
+        let builders = [build_bam_reader, build_sam_reader, build_cram_reader];
+
+        for build_reader in builders {
+            let (mut reader, header) = build_reader()?;
+            let regions = ["sq0:1-10".parse()?, "sq0:5-25".parse()?];
+
+            let actual = names(reader.query_regions(&header, &regions)?)?;
+            assert_eq!(actual, [b"r0".to_vec(), b"r1".to_vec(), b"r2".to_vec()]);
+
+            let actual = names(reader.query_regions_with_mates(&header, &regions)?)?;
+            assert_eq!(
+                actual,
+                [
+                    b"r0".to_vec(),
+                    b"r1".to_vec(),
+                    b"r2".to_vec(),
+                    b"r0".to_vec()
+                ]
+            );
+        }
+
+        Ok(())
+    }
 }
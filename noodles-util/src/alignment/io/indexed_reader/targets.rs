@@ -0,0 +1,154 @@
+use std::{collections::HashSet, io};
+
+use noodles_core::{region::Interval, Position, Region};
+use noodles_cram::crai;
+use noodles_csi::{binning_index::index::reference_sequence::bin::Chunk, BinningIndex};
+use noodles_sam::{self as sam, alignment::Record};
+
+type Mate = (Vec<u8>, usize, Position);
+
+/// The query targets of a multi-region query.
+///
+/// A record is a target if it intersects any of the regions or if it is a requested mate, i.e., it
+/// has a given read name and starts at a given position.
+pub(super) struct Targets {
+    intervals: Vec<(usize, Interval)>,
+    mates: HashSet<Mate>,
+}
+
+impl Targets {
+    pub fn new(header: &sam::Header, regions: &[Region]) -> io::Result<Self> {
+        let intervals = regions
+            .iter()
+            .map(|region| {
+                header
+                    .reference_sequences()
+                    .get_index_of(region.name())
+                    .map(|id| (id, region.interval()))
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "invalid reference sequence name",
+                        )
+                    })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            intervals,
+            mates: HashSet::new(),
+        })
+    }
+
+    /// Adds the mate of the given record if the mate does not start in any of the regions.
+    pub fn add_mate(&mut self, header: &sam::Header, record: &dyn Record) -> io::Result<()> {
+        let flags = record.flags()?;
+
+        if !flags.is_segmented() || flags.is_mate_unmapped() {
+            return Ok(());
+        }
+
+        let Some(name) = record.name() else {
+            return Ok(());
+        };
+
+        let (Some(mate_reference_sequence_id), Some(mate_alignment_start)) = (
+            record.mate_reference_sequence_id(header).transpose()?,
+            record.mate_alignment_start().transpose()?,
+        ) else {
+            return Ok(());
+        };
+
+        let interval = Interval::from(mate_alignment_start..=mate_alignment_start);
+
+        if !self.intersects(mate_reference_sequence_id, interval) {
+            self.mates.insert((
+                name.as_bytes().to_vec(),
+                mate_reference_sequence_id,
+                mate_alignment_start,
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn chunks(&self, index: &dyn BinningIndex) -> io::Result<Vec<Chunk>> {
+        let mut chunks = Vec::new();
+
+        for (reference_sequence_id, interval) in self.intervals() {
+            chunks.extend(index.query(reference_sequence_id, interval)?);
+        }
+
+        Ok(noodles_csi::binning_index::merge_chunks(&chunks))
+    }
+
+    /// Returns the sorted, unique offsets of the containers that may hold targets.
+    pub fn container_offsets(&self, index: &crai::Index) -> Vec<u64> {
+        let intervals: Vec<_> = self.intervals().collect();
+
+        let mut offsets: Vec<_> = index
+            .iter()
+            .filter(|record| {
+                let Some(id) = record.reference_sequence_id() else {
+                    return false;
+                };
+
+                let record_interval = record
+                    .alignment_start()
+                    .and_then(|start| {
+                        let end = start.checked_add(record.alignment_span().saturating_sub(1))?;
+                        Some(Interval::from(start..=end))
+                    })
+                    .unwrap_or_else(|| Interval::from(..));
+
+                intervals.iter().any(|&(reference_sequence_id, interval)| {
+                    id == reference_sequence_id && interval.intersects(record_interval)
+                })
+            })
+            .map(|record| record.offset())
+            .collect();
+
+        offsets.sort_unstable();
+        offsets.dedup();
+
+        offsets
+    }
+
+    pub fn contains(&self, header: &sam::Header, record: &dyn Record) -> io::Result<bool> {
+        let (Some(reference_sequence_id), Some(start)) = (
+            record.reference_sequence_id(header).transpose()?,
+            record.alignment_start().transpose()?,
+        ) else {
+            return Ok(false);
+        };
+
+        if !self.mates.is_empty() {
+            if let Some(name) = record.name() {
+                let mate = (name.as_bytes().to_vec(), reference_sequence_id, start);
+
+                if self.mates.contains(&mate) {
+                    return Ok(true);
+                }
+            }
+        }
+
+        let end = record.alignment_end().transpose()?.unwrap_or(start);
+
+        Ok(self.intersects(reference_sequence_id, (start..=end).into()))
+    }
+
+    fn intersects(&self, reference_sequence_id: usize, interval: Interval) -> bool {
+        self.intervals.iter().any(|&(id, region_interval)| {
+            id == reference_sequence_id && region_interval.intersects(interval)
+        })
+    }
+
+    fn intervals(&self) -> impl Iterator<Item = (usize, Interval)> + '_ {
+        let mate_intervals = self
+            .mates
+            .iter()
+            .map(|(_, id, position)| (*id, Interval::from(*position..=*position)));
+
+        self.intervals.iter().copied().chain(mate_intervals)
+    }
+}
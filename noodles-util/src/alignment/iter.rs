@@ -1,5 +1,9 @@
 //! Composable iterators for alignment records.
 
 mod pileup;
+mod templates;
 
-pub use self::pileup::Pileup as Depth;
+pub use self::{
+    pileup::Pileup as Depth,
+    templates::{Template, Templates},
+};
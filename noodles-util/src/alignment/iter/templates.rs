@@ -0,0 +1,354 @@
+use std::io;
+
+use noodles_sam::alignment::Record;
+
+/// The records of a template, i.e., records with the same read name.
+#[derive(Debug)]
+pub struct Template<R> {
+    name: Option<Vec<u8>>,
+    records: Vec<R>,
+    read_1: Option<usize>,
+    read_2: Option<usize>,
+}
+
+impl<R> Template<R>
+where
+    R: Record,
+{
+    fn new(name: Option<Vec<u8>>) -> Self {
+        Self {
+            name,
+            records: Vec::new(),
+            read_1: None,
+            read_2: None,
+        }
+    }
+
+    /// Returns the read name.
+    pub fn name(&self) -> Option<&[u8]> {
+        self.name.as_deref()
+    }
+
+    /// Returns all records in input order.
+    pub fn records(&self) -> &[R] {
+        &self.records
+    }
+
+    /// Returns the primary record of read 1.
+    ///
+    /// This is the primary record that is the first segment or, for single-end reads, that is
+    /// not segmented.
+    pub fn read_1(&self) -> Option<&R> {
+        self.read_1.map(|i| &self.records[i])
+    }
+
+    /// Returns the primary record of read 2.
+    ///
+    /// This is the primary record that is the last segment.
+    pub fn read_2(&self) -> Option<&R> {
+        self.read_2.map(|i| &self.records[i])
+    }
+
+    /// Returns an iterator over secondary records.
+    pub fn secondary_records(&self) -> impl Iterator<Item = io::Result<&R>> {
+        self.records
+            .iter()
+            .filter_map(|record| match record.flags() {
+                Ok(flags) => flags.is_secondary().then_some(Ok(record)),
+                Err(e) => Some(Err(e)),
+            })
+    }
+
+    /// Returns an iterator over supplementary records.
+    pub fn supplementary_records(&self) -> impl Iterator<Item = io::Result<&R>> {
+        self.records
+            .iter()
+            .filter_map(|record| match record.flags() {
+                Ok(flags) => flags.is_supplementary().then_some(Ok(record)),
+                Err(e) => Some(Err(e)),
+            })
+    }
+
+    /// Returns the records.
+    pub fn into_records(self) -> Vec<R> {
+        self.records
+    }
+
+    fn push(&mut self, record: R) -> io::Result<()> {
+        let flags = record.flags()?;
+
+        if !flags.is_secondary() && !flags.is_supplementary() {
+            let slot = if !flags.is_segmented() || flags.is_first_segment() {
+                Some(&mut self.read_1)
+            } else if flags.is_last_segment() {
+                Some(&mut self.read_2)
+            } else {
+                None
+            };
+
+            if let Some(slot) = slot {
+                if slot.is_some() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "duplicate primary record: {}",
+                            String::from_utf8_lossy(self.name.as_deref().unwrap_or(b"*"))
+                        ),
+                    ));
+                }
+
+                *slot = Some(self.records.len());
+            }
+        }
+
+        self.records.push(record);
+
+        Ok(())
+    }
+}
+
+/// An iterator over templates.
+///
+/// This takes an iterator of records grouped by read name, e.g., queryname-sorted records, and
+/// emits the records of each template. Records without a read name are each emitted as their own
+/// template.
+///
+/// This returns an error if a template has more than one primary record for read 1 or read 2 or
+/// if a template has the same read name as the previous named template, i.e., the records of a
+/// template are separated by records without a read name. Only the previous read name is kept, so
+/// a read name that reappears after another named template starts a new template.
+pub struct Templates<I, R> {
+    records: I,
+    next_record: Option<R>,
+    prev_name: Option<Vec<u8>>,
+}
+
+impl<I, R> Templates<I, R>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    /// Creates a templates iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::{record::Flags, RecordBuf};
+    /// use noodles_util::alignment::iter::Templates;
+    ///
+    /// let records = [
+    ///     RecordBuf::builder()
+    ///         .set_name(b"r0".into())
+    ///         .set_flags(Flags::SEGMENTED | Flags::FIRST_SEGMENT)
+    ///         .build(),
+    ///     RecordBuf::builder()
+    ///         .set_name(b"r0".into())
+    ///         .set_flags(Flags::SEGMENTED | Flags::LAST_SEGMENT)
+    ///         .build(),
+    ///     RecordBuf::builder()
+    ///         .set_name(b"r1".into())
+    ///         .set_flags(Flags::empty())
+    ///         .build(),
+    /// ];
+    ///
+    /// let mut templates = Templates::new(records.into_iter().map(Ok));
+    ///
+    /// let template = templates.next().transpose()?.expect("missing template");
+    /// assert_eq!(template.name(), Some(&b"r0"[..]));
+    /// assert!(template.read_1().is_some());
+    /// assert!(template.read_2().is_some());
+    ///
+    /// let template = templates.next().transpose()?.expect("missing template");
+    /// assert_eq!(template.name(), Some(&b"r1"[..]));
+    /// assert!(template.read_1().is_some());
+    /// assert!(template.read_2().is_none());
+    ///
+    /// assert!(templates.next().is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn new(records: I) -> Self {
+        Self {
+            records,
+            next_record: None,
+            prev_name: None,
+        }
+    }
+
+    fn read_template(&mut self) -> io::Result<Option<Template<R>>> {
+        let record = match self.next_record.take() {
+            Some(record) => record,
+            None => match self.records.next().transpose()? {
+                Some(record) => record,
+                None => return Ok(None),
+            },
+        };
+
+        let name = read_name(&record);
+        let mut template = Template::new(name.clone());
+        template.push(record)?;
+
+        let Some(name) = name else {
+            return Ok(Some(template));
+        };
+
+        for result in &mut self.records {
+            let record = result?;
+
+            if read_name(&record).as_ref() == Some(&name) {
+                template.push(record)?;
+            } else {
+                self.next_record = Some(record);
+                break;
+            }
+        }
+
+        if self.prev_name.as_ref() == Some(&name) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "records are not grouped by name: {}",
+                    String::from_utf8_lossy(&name)
+                ),
+            ));
+        }
+
+        self.prev_name = Some(name);
+
+        Ok(Some(template))
+    }
+}
+
+impl<I, R> Iterator for Templates<I, R>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    type Item = io::Result<Template<R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_template().transpose()
+    }
+}
+
+fn read_name<R>(record: &R) -> Option<Vec<u8>>
+where
+    R: Record,
+{
+    record.name().map(|name| name.as_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::alignment::{record::Flags, RecordBuf};
+
+    use super::*;
+
+    fn build_record(name: &[u8], flags: Flags) -> RecordBuf {
+        RecordBuf::builder()
+            .set_name(name.into())
+            .set_flags(flags)
+            .build()
+    }
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        let records = [
+            build_record(b"r0", Flags::SEGMENTED | Flags::LAST_SEGMENT),
+            build_record(b"r0", Flags::SEGMENTED | Flags::FIRST_SEGMENT),
+            build_record(
+                b"r0",
+                Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::SUPPLEMENTARY,
+            ),
+            build_record(
+                b"r0",
+                Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::SECONDARY,
+            ),
+            RecordBuf::default(),
+            RecordBuf::default(),
+            build_record(b"r1", Flags::empty()),
+        ];
+
+        let templates: Vec<_> =
+            Templates::new(records.into_iter().map(Ok)).collect::<io::Result<_>>()?;
+        assert_eq!(templates.len(), 4);
+
+        let template = &templates[0];
+        assert_eq!(template.records().len(), 4);
+        assert_eq!(
+            template.read_1().map(|record| record.flags()),
+            Some(Flags::SEGMENTED | Flags::FIRST_SEGMENT)
+        );
+        assert_eq!(
+            template.read_2().map(|record| record.flags()),
+            Some(Flags::SEGMENTED | Flags::LAST_SEGMENT)
+        );
+        assert_eq!(
+            template
+                .secondary_records()
+                .collect::<io::Result<Vec<_>>>()?
+                .len(),
+            1
+        );
+        assert_eq!(
+            template
+                .supplementary_records()
+                .collect::<io::Result<Vec<_>>>()?
+                .len(),
+            1
+        );
+
+        assert!(templates[1].name().is_none());
+        assert_eq!(templates[1].records().len(), 1);
+        assert!(templates[2].name().is_none());
+
+        assert_eq!(templates[3].name(), Some(&b"r1"[..]));
+        assert!(templates[3].read_1().is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_ungrouped_records() -> io::Result<()> {
+        let records = [
+            build_record(b"r0", Flags::empty()),
+            build_record(b"r1", Flags::empty()),
+            build_record(b"r0", Flags::SECONDARY),
+        ];
+
+        let templates: Vec<_> =
+            Templates::new(records.into_iter().map(Ok)).collect::<io::Result<_>>()?;
+        assert_eq!(templates.len(), 3);
+
+        let records = [
+            build_record(b"r0", Flags::empty()),
+            RecordBuf::default(),
+            build_record(b"r0", Flags::SECONDARY),
+        ];
+
+        let mut templates = Templates::new(records.into_iter().map(Ok));
+
+        assert!(matches!(templates.next(), Some(Ok(_))));
+        assert!(matches!(templates.next(), Some(Ok(_))));
+        assert!(matches!(
+            templates.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_duplicate_primary_records() {
+        let records = [
+            build_record(b"r0", Flags::SEGMENTED | Flags::FIRST_SEGMENT),
+            build_record(b"r0", Flags::SEGMENTED | Flags::FIRST_SEGMENT),
+        ];
+
+        let mut templates = Templates::new(records.into_iter().map(Ok));
+
+        assert!(matches!(
+            templates.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+//! Alignment header reference sequences.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Seek},
+    num::NonZeroUsize,
+    path::Path,
+};
+
+use noodles_core::Region;
+use noodles_cram::calculate_normalized_sequence_digest;
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    header::{
+        record::value::{
+            map::{
+                reference_sequence::{tag, Md5Checksum},
+                ReferenceSequence,
+            },
+            Map,
+        },
+        ReferenceSequences,
+    },
+};
+
+/// An alignment header reference sequences builder.
+///
+/// This builds SAM header reference sequence records (`@SQ`) with lengths (`LN`) and MD5
+/// checksums (`M5`) from either an indexed FASTA or a sequence dictionary (`.dict`).
+#[derive(Debug, Default)]
+pub struct Builder {
+    assembly_id: Option<String>,
+    species: Option<String>,
+    uri: Option<String>,
+}
+
+impl Builder {
+    /// Sets the genome assembly ID (`AS`) of all reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::reference_sequences::Builder;
+    /// let builder = Builder::default().set_assembly_id("GRCh38");
+    /// ```
+    pub fn set_assembly_id<S>(mut self, assembly_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.assembly_id = Some(assembly_id.into());
+        self
+    }
+
+    /// Sets the species (`SP`) of all reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::reference_sequences::Builder;
+    /// let builder = Builder::default().set_species("Homo sapiens");
+    /// ```
+    pub fn set_species<S>(mut self, species: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.species = Some(species.into());
+        self
+    }
+
+    /// Sets the URI (`UR`) of all reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::reference_sequences::Builder;
+    /// let builder = Builder::default().set_uri("file:///tmp/reference.fa");
+    /// ```
+    pub fn set_uri<S>(mut self, uri: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.uri = Some(uri.into());
+        self
+    }
+
+    /// Builds reference sequences from an indexed FASTA reader.
+    ///
+    /// Reference sequences are built in index order. Each sequence is read to calculate its
+    /// normalized MD5 checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use noodles_fasta::{self as fasta, fai};
+    /// use noodles_sam::header::record::value::map::reference_sequence::tag;
+    /// use noodles_util::alignment::reference_sequences::Builder;
+    ///
+    /// let data = b">sq0\nACGT\n";
+    /// let index = vec![fai::Record::new("sq0", 4, 5, 4, 5)];
+    /// let mut reader = fasta::IndexedReader::new(Cursor::new(data), index);
+    ///
+    /// let reference_sequences = Builder::default().build_from_indexed_reader(&mut reader)?;
+    ///
+    /// let reference_sequence = &reference_sequences[&b"sq0"[..]];
+    /// assert_eq!(usize::from(reference_sequence.length()), 4);
+    /// assert_eq!(
+    ///     reference_sequence.other_fields().get(&tag::MD5_CHECKSUM).map(|s| s.as_ref()),
+    ///     Some(&b"f1f8f4bf413b16ad135722aa4591043e"[..])
+    /// );
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_indexed_reader<R>(
+        self,
+        reader: &mut fasta::IndexedReader<R>,
+    ) -> io::Result<ReferenceSequences>
+    where
+        R: BufRead + Seek,
+    {
+        let names: Vec<Vec<u8>> = reader
+            .index()
+            .iter()
+            .map(|record| record.name().to_vec())
+            .collect();
+
+        let mut reference_sequences = ReferenceSequences::default();
+
+        for name in names {
+            let region = Region::new(name.clone(), ..);
+            let record = reader.query(&region)?;
+            let sequence = record.sequence().as_ref();
+
+            let length = NonZeroUsize::try_from(sequence.len())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            let mut map = Map::<ReferenceSequence>::new(length);
+
+            let checksum = Md5Checksum::from(calculate_normalized_sequence_digest(sequence));
+
+            map.other_fields_mut()
+                .insert(tag::MD5_CHECKSUM, checksum.to_string().into());
+
+            self.set_fields(&mut map, true);
+
+            reference_sequences.insert(name.into(), map);
+        }
+
+        Ok(reference_sequences)
+    }
+
+    /// Builds reference sequences from a sequence dictionary reader.
+    ///
+    /// A sequence dictionary is a SAM header with a reference sequence record (`@SQ`) for each
+    /// sequence, e.g., as created by `samtools dict` or Picard `CreateSequenceDictionary`. Fields
+    /// set on this builder do not override fields in the dictionary.
+    ///
+    /// This returns an error if any reference sequence is missing an MD5 checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::reference_sequences::Builder;
+    ///
+    /// let data = b"@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:4\tM5:f1f8f4bf413b16ad135722aa4591043e\n";
+    /// let reference_sequences = Builder::default()
+    ///     .set_species("Homo sapiens")
+    ///     .build_from_dict_reader(&data[..])?;
+    ///
+    /// assert_eq!(reference_sequences.len(), 1);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_dict_reader<R>(self, reader: R) -> io::Result<ReferenceSequences>
+    where
+        R: BufRead,
+    {
+        let header = sam::io::Reader::new(reader).read_header()?;
+        let mut reference_sequences = header.reference_sequences().clone();
+
+        for (name, map) in &mut reference_sequences {
+            if !map.other_fields().contains_key(&tag::MD5_CHECKSUM) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("missing MD5 checksum: {name}"),
+                ));
+            }
+
+            self.set_fields(map, false);
+        }
+
+        Ok(reference_sequences)
+    }
+
+    /// Builds reference sequences from a sequence dictionary file.
+    ///
+    /// See [`Self::build_from_dict_reader`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_util::alignment::reference_sequences::Builder;
+    /// let reference_sequences = Builder::default().build_from_dict_path("reference.dict")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_dict_path<P>(self, src: P) -> io::Result<ReferenceSequences>
+    where
+        P: AsRef<Path>,
+    {
+        let reader = File::open(src).map(BufReader::new)?;
+        self.build_from_dict_reader(reader)
+    }
+
+    fn set_fields(&self, map: &mut Map<ReferenceSequence>, overwrite: bool) {
+        let fields = [
+            (tag::ASSEMBLY_ID, &self.assembly_id),
+            (tag::SPECIES, &self.species),
+            (tag::URI, &self.uri),
+        ];
+
+        for (key, value) in fields {
+            let Some(value) = value else {
+                continue;
+            };
+
+            if overwrite {
+                map.other_fields_mut().insert(key, value.as_str().into());
+            } else {
+                map.other_fields_mut()
+                    .entry(key)
+                    .or_insert_with(|| value.as_str().into());
+            }
+        }
+    }
+}
+
+// § 1.3.2 "Reference MD5 calculation" (2024-05-16): "...all characters outside of the inclusive
+// range 33 ('!') to 126 ('~') are stripped out and all lowercase characters converted to
+// uppercase."
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_fasta::fai;
+
+    use super::*;
+
+    #[test]
+    fn test_build_from_indexed_reader() -> io::Result<()> {
+        let data = b">sq0\nACGT\nacgt\n>sq1\nNNAC\nGT\n";
+        let index = vec![
+            fai::Record::new("sq0", 8, 5, 4, 5),
+            fai::Record::new("sq1", 6, 20, 4, 5),
+        ];
+        let mut reader = fasta::IndexedReader::new(Cursor::new(data), index);
+
+        let reference_sequences = Builder::default()
+            .set_assembly_id("ndls")
+            .build_from_indexed_reader(&mut reader)?;
+
+        let expected: ReferenceSequences = [
+            ("sq0", 8, "cc0af3a4fedb18378b4b57b98068e69f"),
+            ("sq1", 6, "071ca92810280a288449bcc47bf92858"),
+        ]
+        .into_iter()
+        .map(|(name, length, checksum)| {
+            let mut map = Map::<ReferenceSequence>::new(NonZeroUsize::new(length).unwrap());
+            map.other_fields_mut()
+                .insert(tag::MD5_CHECKSUM, checksum.into());
+            map.other_fields_mut()
+                .insert(tag::ASSEMBLY_ID, "ndls".into());
+            (name.into(), map)
+        })
+        .collect();
+
+        assert_eq!(reference_sequences, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_from_dict_reader() -> io::Result<()> {
+        let data =
+            b"@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:8\tM5:cc0af3a4fedb18378b4b57b98068e69f\tSP:ndls\n";
+
+        let reference_sequences = Builder::default()
+            .set_species("Homo sapiens")
+            .set_uri("file:///tmp/ndls.fa")
+            .build_from_dict_reader(&data[..])?;
+
+        let map = &reference_sequences[&b"sq0"[..]];
+        assert_eq!(
+            map.other_fields().get(&tag::SPECIES).map(|s| s.as_ref()),
+            Some(&b"ndls"[..])
+        );
+        assert_eq!(
+            map.other_fields().get(&tag::URI).map(|s| s.as_ref()),
+            Some(&b"file:///tmp/ndls.fa"[..])
+        );
+
+        let data = b"@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:8\n";
+        assert!(matches!(
+            Builder::default().build_from_dict_reader(&data[..]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,633 @@
+//! Alignment statistics.
+
+mod md;
+mod summary;
+mod writer;
+
+pub use self::{summary::Summary, writer::write_stats};
+
+use std::{collections::BTreeMap, io};
+
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        record::{data::field::Tag, Cigar as _, Flags},
+        Record,
+    },
+};
+
+/// The size of the windows used to collect GC-depth.
+pub const GC_DEPTH_WINDOW_SIZE: usize = 20000;
+
+const BASES: [u8; 5] = [b'A', b'C', b'G', b'T', b'N'];
+
+/// Base composition counts: A, C, G, T, N, and other.
+pub type BaseCounts = [u64; 6];
+
+/// The orientation counts of read pairs: inward, outward, and other.
+pub type PairOrientationCounts = [u64; 3];
+
+/// GC-depth counts of a window: the number of read bases and the number of G and C read bases.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Window {
+    bases: u64,
+    gc_bases: u64,
+}
+
+impl Window {
+    /// Returns the number of read bases.
+    pub fn bases(&self) -> u64 {
+        self.bases
+    }
+
+    /// Returns the GC content of the read bases.
+    pub fn gc_content(&self) -> f64 {
+        if self.bases == 0 {
+            0.0
+        } else {
+            self.gc_bases as f64 / self.bases as f64
+        }
+    }
+
+    /// Returns the average depth of the window.
+    pub fn depth(&self) -> f64 {
+        self.bases as f64 / GC_DEPTH_WINDOW_SIZE as f64
+    }
+}
+
+/// An alignment statistics collector.
+///
+/// This accumulates statistics similar to those of `samtools stats`. Secondary and supplementary
+/// records are only counted in the summary numbers. Per-cycle statistics are in the original read
+/// orientation, i.e., cycles are reversed for reverse complemented records.
+///
+/// Mismatches per cycle are found by comparing read bases to the reference sequence, if a
+/// reference sequence repository is set, or otherwise, using the `MD` data field.
+#[derive(Debug, Default)]
+pub struct Collector {
+    reference_sequence_repository: Option<fasta::Repository>,
+    summary: Summary,
+    insert_sizes: BTreeMap<usize, PairOrientationCounts>,
+    read_lengths: BTreeMap<usize, u64>,
+    mapping_qualities: BTreeMap<u8, u64>,
+    base_composition: Vec<BaseCounts>,
+    first_fragment_qualities: Vec<Vec<u64>>,
+    last_fragment_qualities: Vec<Vec<u64>>,
+    mismatches: Vec<u64>,
+    gc_depth: BTreeMap<(usize, usize), Window>,
+}
+
+impl Collector {
+    /// Creates an alignment statistics collector that uses the given reference sequence
+    /// repository to find mismatches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// use noodles_util::alignment::stats::Collector;
+    /// let collector = Collector::with_reference_sequence_repository(fasta::Repository::default());
+    /// ```
+    pub fn with_reference_sequence_repository(
+        reference_sequence_repository: fasta::Repository,
+    ) -> Self {
+        Self {
+            reference_sequence_repository: Some(reference_sequence_repository),
+            ..Default::default()
+        }
+    }
+
+    /// Adds an alignment record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, alignment::{record_buf::Sequence, RecordBuf}};
+    /// use noodles_util::alignment::stats::Collector;
+    ///
+    /// let header = sam::Header::default();
+    /// let record = RecordBuf::builder().set_sequence(Sequence::from(b"ACGT")).build();
+    ///
+    /// let mut collector = Collector::default();
+    /// collector.add(&header, &record)?;
+    ///
+    /// assert_eq!(collector.summary().raw_total_sequences, 1);
+    /// assert_eq!(collector.summary().reads_unmapped, 1);
+    /// assert_eq!(collector.read_lengths().get(&4), Some(&1));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn add<R>(&mut self, header: &sam::Header, record: &R) -> io::Result<()>
+    where
+        R: Record + ?Sized,
+    {
+        let flags = record.flags()?;
+
+        if flags.is_secondary() {
+            self.summary.non_primary_alignments += 1;
+            return Ok(());
+        } else if flags.is_supplementary() {
+            self.summary.supplementary_alignments += 1;
+            return Ok(());
+        }
+
+        let sequence: Vec<_> = record.sequence().iter().collect();
+        let quality_scores: Vec<_> = record.quality_scores().iter().collect();
+        let read_length = sequence.len();
+
+        self.add_summary(flags, record, &quality_scores, read_length)?;
+
+        *self.read_lengths.entry(read_length).or_default() += 1;
+
+        if !flags.is_unmapped() {
+            if let Some(mapping_quality) = record.mapping_quality().transpose()? {
+                *self
+                    .mapping_qualities
+                    .entry(u8::from(mapping_quality))
+                    .or_default() += 1;
+            } else {
+                *self.mapping_qualities.entry(255).or_default() += 1;
+            }
+        }
+
+        let is_reverse_complemented = flags.is_reverse_complemented();
+        let cycle = |i: usize| {
+            if is_reverse_complemented {
+                read_length - i - 1
+            } else {
+                i
+            }
+        };
+
+        grow(&mut self.base_composition, read_length);
+
+        for (i, base) in sequence.iter().enumerate() {
+            let base = if is_reverse_complemented {
+                complement(*base)
+            } else {
+                *base
+            };
+
+            let j = BASES
+                .iter()
+                .position(|b| *b == base.to_ascii_uppercase())
+                .unwrap_or(BASES.len());
+
+            self.base_composition[cycle(i)][j] += 1;
+        }
+
+        let qualities = if flags.is_segmented() && flags.is_last_segment() {
+            &mut self.last_fragment_qualities
+        } else {
+            &mut self.first_fragment_qualities
+        };
+
+        grow(qualities, quality_scores.len());
+
+        for (i, score) in quality_scores.iter().enumerate() {
+            let counts = &mut qualities[cycle(i)];
+            let score = usize::from(*score);
+
+            if counts.len() <= score {
+                counts.resize(score + 1, 0);
+            }
+
+            counts[score] += 1;
+        }
+
+        if !flags.is_unmapped() {
+            self.add_insert_size(header, flags, record)?;
+            self.add_mismatches(header, record, &sequence, cycle)?;
+            self.add_gc_depth(header, record, &sequence)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the summary numbers.
+    pub fn summary(&self) -> &Summary {
+        &self.summary
+    }
+
+    /// Returns the insert size histogram.
+    ///
+    /// Insert sizes are the absolute template lengths of the first segments of pairs that are
+    /// mapped to the same reference sequence.
+    pub fn insert_sizes(&self) -> &BTreeMap<usize, PairOrientationCounts> {
+        &self.insert_sizes
+    }
+
+    /// Returns the read length histogram.
+    pub fn read_lengths(&self) -> &BTreeMap<usize, u64> {
+        &self.read_lengths
+    }
+
+    /// Returns the mapping quality histogram of mapped records.
+    ///
+    /// Missing mapping qualities are counted as 255.
+    pub fn mapping_qualities(&self) -> &BTreeMap<u8, u64> {
+        &self.mapping_qualities
+    }
+
+    /// Returns the base composition by cycle.
+    pub fn base_composition(&self) -> &[BaseCounts] {
+        &self.base_composition
+    }
+
+    /// Returns the quality score histograms by cycle of first fragments.
+    ///
+    /// Records that are not segmented are counted as first fragments.
+    pub fn first_fragment_qualities(&self) -> &[Vec<u64>] {
+        &self.first_fragment_qualities
+    }
+
+    /// Returns the quality score histograms by cycle of last fragments.
+    pub fn last_fragment_qualities(&self) -> &[Vec<u64>] {
+        &self.last_fragment_qualities
+    }
+
+    /// Returns the number of mismatches by cycle.
+    pub fn mismatches(&self) -> &[u64] {
+        &self.mismatches
+    }
+
+    /// Returns the GC-depth windows, keyed by reference sequence ID and window index.
+    pub fn gc_depth(&self) -> &BTreeMap<(usize, usize), Window> {
+        &self.gc_depth
+    }
+
+    fn add_summary<R>(
+        &mut self,
+        flags: Flags,
+        record: &R,
+        quality_scores: &[u8],
+        read_length: usize,
+    ) -> io::Result<()>
+    where
+        R: Record + ?Sized,
+    {
+        let summary = &mut self.summary;
+        let read_length = read_length as u64;
+
+        summary.raw_total_sequences += 1;
+
+        if flags.is_segmented() && flags.is_last_segment() {
+            summary.last_fragments += 1;
+        } else {
+            summary.first_fragments += 1;
+        }
+
+        if flags.is_segmented() {
+            summary.reads_paired += 1;
+        }
+
+        if flags.is_duplicate() {
+            summary.reads_duplicated += 1;
+        }
+
+        if flags.is_qc_fail() {
+            summary.reads_qc_failed += 1;
+        }
+
+        summary.total_length += read_length;
+        summary.maximum_length = summary.maximum_length.max(read_length);
+        summary.quality_score_sum += quality_scores.iter().map(|&n| u64::from(n)).sum::<u64>();
+
+        if flags.is_unmapped() {
+            summary.reads_unmapped += 1;
+            return Ok(());
+        }
+
+        summary.reads_mapped += 1;
+        summary.bases_mapped += read_length;
+
+        if flags.is_segmented() && !flags.is_mate_unmapped() {
+            summary.reads_mapped_and_paired += 1;
+        }
+
+        if flags.is_segmented() && flags.is_properly_segmented() {
+            summary.reads_properly_paired += 1;
+        }
+
+        if record
+            .mapping_quality()
+            .transpose()?
+            .map(|mapping_quality| u8::from(mapping_quality) == 0)
+            .unwrap_or(false)
+        {
+            summary.reads_mq0 += 1;
+        }
+
+        for result in record.cigar().iter() {
+            let op = result?;
+
+            if op.kind().consumes_read() && op.kind().consumes_reference() {
+                summary.bases_mapped_cigar += op.len() as u64;
+            }
+        }
+
+        if let Some(n) = record
+            .data()
+            .get(&Tag::EDIT_DISTANCE)
+            .transpose()?
+            .and_then(|value| value.as_int())
+        {
+            summary.mismatches += u64::try_from(n).unwrap_or_default();
+        }
+
+        Ok(())
+    }
+
+    fn add_insert_size<R>(
+        &mut self,
+        header: &sam::Header,
+        flags: Flags,
+        record: &R,
+    ) -> io::Result<()>
+    where
+        R: Record + ?Sized,
+    {
+        if !flags.is_segmented() || flags.is_mate_unmapped() || !flags.is_first_segment() {
+            return Ok(());
+        }
+
+        let (Some(id), Some(mate_id), Some(start), Some(mate_start)) = (
+            record.reference_sequence_id(header).transpose()?,
+            record.mate_reference_sequence_id(header).transpose()?,
+            record.alignment_start().transpose()?,
+            record.mate_alignment_start().transpose()?,
+        ) else {
+            return Ok(());
+        };
+
+        if id != mate_id {
+            return Ok(());
+        }
+
+        let insert_size = record.template_length()?.unsigned_abs() as usize;
+
+        if insert_size == 0 {
+            return Ok(());
+        }
+
+        let (leftmost_is_reverse, rightmost_is_reverse) = if start <= mate_start {
+            (
+                flags.is_reverse_complemented(),
+                flags.is_mate_reverse_complemented(),
+            )
+        } else {
+            (
+                flags.is_mate_reverse_complemented(),
+                flags.is_reverse_complemented(),
+            )
+        };
+
+        let i = match (leftmost_is_reverse, rightmost_is_reverse) {
+            (false, true) => 0,
+            (true, false) => 1,
+            _ => 2,
+        };
+
+        self.insert_sizes.entry(insert_size).or_default()[i] += 1;
+
+        Ok(())
+    }
+
+    fn add_mismatches<R, F>(
+        &mut self,
+        header: &sam::Header,
+        record: &R,
+        sequence: &[u8],
+        cycle: F,
+    ) -> io::Result<()>
+    where
+        R: Record + ?Sized,
+        F: Fn(usize) -> usize,
+    {
+        let Some(alignment_start) = record.alignment_start().transpose()? else {
+            return Ok(());
+        };
+
+        let cigar = record.cigar();
+        let mut query_offsets = Vec::new();
+
+        if let Some(repository) = &self.reference_sequence_repository {
+            let Some((name, _)) = record.reference_sequence(header).transpose()? else {
+                return Ok(());
+            };
+
+            let reference_sequence = repository.get(name).transpose()?.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("missing reference sequence: {name}"),
+                )
+            })?;
+
+            let reference_bases = reference_sequence.as_ref();
+
+            for result in cigar.aligned_blocks(alignment_start) {
+                let block = result?;
+                let reference_start = usize::from(block.reference_start()) - 1;
+
+                for i in 0..block.len() {
+                    let query_offset = block.query_start() + i;
+
+                    let (Some(read_base), Some(reference_base)) = (
+                        sequence.get(query_offset),
+                        reference_bases.get(reference_start + i),
+                    ) else {
+                        continue;
+                    };
+
+                    let (a, b) = (
+                        read_base.to_ascii_uppercase(),
+                        reference_base.to_ascii_uppercase(),
+                    );
+
+                    if a != b && a != b'N' && b != b'N' {
+                        query_offsets.push(query_offset);
+                    }
+                }
+            }
+        } else {
+            let data = record.data();
+
+            let Some(value) = data.get(&Tag::MISMATCHED_POSITIONS).transpose()? else {
+                return Ok(());
+            };
+
+            let sam::alignment::record::data::field::Value::String(md) = value else {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid MD"));
+            };
+
+            for offset in md::parse_mismatch_offsets(md)? {
+                let position = alignment_start
+                    .checked_add(offset)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid MD"))?;
+
+                if let Some(query_offset) = cigar.query_offset(alignment_start, position)? {
+                    query_offsets.push(query_offset);
+                }
+            }
+        }
+
+        grow(&mut self.mismatches, sequence.len());
+
+        for query_offset in query_offsets {
+            if query_offset < sequence.len() {
+                self.mismatches[cycle(query_offset)] += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_gc_depth<R>(
+        &mut self,
+        header: &sam::Header,
+        record: &R,
+        sequence: &[u8],
+    ) -> io::Result<()>
+    where
+        R: Record + ?Sized,
+    {
+        let (Some(id), Some(start)) = (
+            record.reference_sequence_id(header).transpose()?,
+            record.alignment_start().transpose()?,
+        ) else {
+            return Ok(());
+        };
+
+        let i = (usize::from(start) - 1) / GC_DEPTH_WINDOW_SIZE;
+        let window = self.gc_depth.entry((id, i)).or_default();
+
+        window.bases += sequence.len() as u64;
+        window.gc_bases += sequence
+            .iter()
+            .filter(|b| matches!(b.to_ascii_uppercase(), b'C' | b'G'))
+            .count() as u64;
+
+        Ok(())
+    }
+}
+
+fn grow<T>(v: &mut Vec<T>, len: usize)
+where
+    T: Clone + Default,
+{
+    if v.len() < len {
+        v.resize(len, T::default());
+    }
+}
+
+fn complement(base: u8) -> u8 {
+    match base.to_ascii_uppercase() {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        b => b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_sam::alignment::{
+        record::cigar::{op::Kind, Op},
+        record_buf::{data::field::Value, QualityScores, Sequence},
+        RecordBuf,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_add() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use noodles_sam::header::record::value::{map::ReferenceSequence, Map};
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(100)?),
+            )
+            .build();
+
+        let build_record = |flags, start: usize, mate_start: usize, template_length| {
+            Ok::<_, Box<dyn std::error::Error>>(
+                RecordBuf::builder()
+                    .set_flags(flags)
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::try_from(start)?)
+                    .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                    .set_mate_reference_sequence_id(0)
+                    .set_mate_alignment_start(Position::try_from(mate_start)?)
+                    .set_template_length(template_length)
+                    .set_sequence(Sequence::from(b"ACGT"))
+                    .set_quality_scores(QualityScores::from(vec![30, 30, 20, 10]))
+                    .set_data(
+                        [
+                            (Tag::EDIT_DISTANCE, Value::from(1)),
+                            (Tag::MISMATCHED_POSITIONS, Value::from("1A2")),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    )
+                    .build(),
+            )
+        };
+
+        let mut collector = Collector::default();
+
+        collector.add(
+            &header,
+            &build_record(
+                Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::MATE_REVERSE_COMPLEMENTED,
+                1,
+                7,
+                10,
+            )?,
+        )?;
+
+        collector.add(
+            &header,
+            &build_record(
+                Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED,
+                7,
+                1,
+                -10,
+            )?,
+        )?;
+
+        collector.add(&header, &build_record(Flags::SECONDARY, 1, 1, 0)?)?;
+
+        let summary = collector.summary();
+        assert_eq!(summary.raw_total_sequences, 2);
+        assert_eq!(summary.first_fragments, 1);
+        assert_eq!(summary.last_fragments, 1);
+        assert_eq!(summary.reads_mapped_and_paired, 2);
+        assert_eq!(summary.non_primary_alignments, 1);
+        assert_eq!(summary.bases_mapped_cigar, 8);
+        assert_eq!(summary.mismatches, 2);
+
+        assert_eq!(collector.insert_sizes().get(&10), Some(&[1, 0, 0]));
+        assert_eq!(collector.read_lengths().get(&4), Some(&2));
+
+        // The reverse complemented record reads "ACGT" in its original orientation as well.
+        assert_eq!(collector.base_composition()[0], [2, 0, 0, 0, 0, 0]);
+
+        assert_eq!(collector.first_fragment_qualities()[0][30], 1);
+        assert_eq!(collector.last_fragment_qualities()[0][10], 1);
+
+        // The mismatch at read offset 1 is cycle 2 (0-based: 1) forward and cycle 3 (0-based: 2)
+        // reversed.
+        assert_eq!(collector.mismatches(), [0, 1, 1, 0]);
+
+        let window = collector.gc_depth()[&(0, 0)];
+        assert_eq!(window.bases(), 8);
+        assert_eq!(window.gc_content(), 0.5);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use std::io;
+
+/// Parses the 0-based reference offsets of the mismatches in a `MD` string.
+///
+/// Offsets are relative to the alignment start and include deleted reference bases.
+pub(super) fn parse_mismatch_offsets(src: &[u8]) -> io::Result<Vec<usize>> {
+    let mut offsets = Vec::new();
+    let mut offset = 0;
+    let mut is_deletion = false;
+    let mut n = 0;
+
+    for &b in src {
+        match b {
+            b'0'..=b'9' => {
+                n = n * 10 + usize::from(b - b'0');
+                is_deletion = false;
+            }
+            b'^' => {
+                offset += n;
+                n = 0;
+                is_deletion = true;
+            }
+            _ if b.is_ascii_alphabetic() => {
+                offset += n;
+                n = 0;
+
+                if !is_deletion {
+                    offsets.push(offset);
+                }
+
+                offset += 1;
+            }
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid MD")),
+        }
+    }
+
+    Ok(offsets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mismatch_offsets() -> io::Result<()> {
+        assert!(parse_mismatch_offsets(b"8")?.is_empty());
+        assert_eq!(parse_mismatch_offsets(b"3C4")?, [3]);
+        assert_eq!(parse_mismatch_offsets(b"0A2^GT1C0T2")?, [0, 6, 7]);
+        assert!(parse_mismatch_offsets(b"3-4").is_err());
+        Ok(())
+    }
+}
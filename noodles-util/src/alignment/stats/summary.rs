@@ -0,0 +1,67 @@
+/// Summary numbers of alignment statistics.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Summary {
+    /// The number of primary records.
+    pub raw_total_sequences: u64,
+    /// The number of primary records that are first segments or not segmented.
+    pub first_fragments: u64,
+    /// The number of primary records that are last segments.
+    pub last_fragments: u64,
+    /// The number of mapped primary records.
+    pub reads_mapped: u64,
+    /// The number of mapped primary records with a mapped mate.
+    pub reads_mapped_and_paired: u64,
+    /// The number of unmapped primary records.
+    pub reads_unmapped: u64,
+    /// The number of properly aligned primary records.
+    pub reads_properly_paired: u64,
+    /// The number of segmented primary records.
+    pub reads_paired: u64,
+    /// The number of primary records marked as duplicates.
+    pub reads_duplicated: u64,
+    /// The number of mapped primary records with a mapping quality of 0.
+    pub reads_mq0: u64,
+    /// The number of primary records that failed quality checks.
+    pub reads_qc_failed: u64,
+    /// The number of secondary records.
+    pub non_primary_alignments: u64,
+    /// The number of supplementary records.
+    pub supplementary_alignments: u64,
+    /// The sum of the read lengths of the primary records.
+    pub total_length: u64,
+    /// The sum of the read lengths of the mapped primary records.
+    pub bases_mapped: u64,
+    /// The number of read bases of the mapped primary records aligned to the reference sequence.
+    pub bases_mapped_cigar: u64,
+    /// The sum of the edit distances (`NM`) of the mapped primary records.
+    pub mismatches: u64,
+    /// The maximum read length.
+    pub maximum_length: u64,
+    /// The sum of the quality scores of the primary records.
+    pub quality_score_sum: u64,
+}
+
+impl Summary {
+    /// Returns the average read length.
+    pub fn average_length(&self) -> f64 {
+        ratio(self.total_length, self.raw_total_sequences)
+    }
+
+    /// Returns the average quality score.
+    pub fn average_quality(&self) -> f64 {
+        ratio(self.quality_score_sum, self.total_length)
+    }
+
+    /// Returns the error rate, i.e., the number of mismatches per aligned base.
+    pub fn error_rate(&self) -> f64 {
+        ratio(self.mismatches, self.bases_mapped_cigar)
+    }
+}
+
+fn ratio(n: u64, d: u64) -> f64 {
+    if d == 0 {
+        0.0
+    } else {
+        n as f64 / d as f64
+    }
+}
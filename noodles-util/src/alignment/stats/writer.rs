@@ -0,0 +1,201 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use super::Collector;
+
+/// Writes alignment statistics in the text format of `samtools stats`.
+///
+/// This writes the sections `SN` (summary numbers), `RL` (read lengths), `MAPQ` (mapping
+/// qualities), `IS` (insert sizes), `GCC` (base composition by cycle), `FFQ` and `LFQ` (first
+/// and last fragment qualities), `MPC` (mismatches by cycle), and `GCD` (GC-depth).
+///
+/// # Examples
+///
+/// ```
+/// use noodles_util::alignment::stats::{self, Collector};
+///
+/// let collector = Collector::default();
+///
+/// let mut buf = Vec::new();
+/// stats::write_stats(&mut buf, &collector)?;
+///
+/// assert!(buf.starts_with(b"SN\traw total sequences:\t0\n"));
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn write_stats<W>(writer: &mut W, collector: &Collector) -> io::Result<()>
+where
+    W: Write,
+{
+    write_summary(writer, collector)?;
+
+    for (len, count) in collector.read_lengths() {
+        writeln!(writer, "RL\t{len}\t{count}")?;
+    }
+
+    for (mapping_quality, count) in collector.mapping_qualities() {
+        writeln!(writer, "MAPQ\t{mapping_quality}\t{count}")?;
+    }
+
+    for (insert_size, [inward, outward, other]) in collector.insert_sizes() {
+        let total = inward + outward + other;
+        writeln!(
+            writer,
+            "IS\t{insert_size}\t{total}\t{inward}\t{outward}\t{other}"
+        )?;
+    }
+
+    for (i, counts) in collector.base_composition().iter().enumerate() {
+        let total: u64 = counts.iter().sum();
+        write!(writer, "GCC\t{}", i + 1)?;
+
+        for n in counts {
+            write!(writer, "\t{:.2}", percent(*n, total))?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    write_qualities(writer, "FFQ", collector.first_fragment_qualities())?;
+    write_qualities(writer, "LFQ", collector.last_fragment_qualities())?;
+
+    for (i, n) in collector.mismatches().iter().enumerate() {
+        writeln!(writer, "MPC\t{}\t{n}", i + 1)?;
+    }
+
+    write_gc_depth(writer, collector)?;
+
+    Ok(())
+}
+
+fn write_summary<W>(writer: &mut W, collector: &Collector) -> io::Result<()>
+where
+    W: Write,
+{
+    let summary = collector.summary();
+
+    let counts = [
+        ("raw total sequences", summary.raw_total_sequences),
+        ("1st fragments", summary.first_fragments),
+        ("last fragments", summary.last_fragments),
+        ("reads mapped", summary.reads_mapped),
+        ("reads mapped and paired", summary.reads_mapped_and_paired),
+        ("reads unmapped", summary.reads_unmapped),
+        ("reads properly paired", summary.reads_properly_paired),
+        ("reads paired", summary.reads_paired),
+        ("reads duplicated", summary.reads_duplicated),
+        ("reads MQ0", summary.reads_mq0),
+        ("reads QC failed", summary.reads_qc_failed),
+        ("non-primary alignments", summary.non_primary_alignments),
+        ("supplementary alignments", summary.supplementary_alignments),
+        ("total length", summary.total_length),
+        ("bases mapped", summary.bases_mapped),
+        ("bases mapped (cigar)", summary.bases_mapped_cigar),
+        ("mismatches", summary.mismatches),
+        ("maximum length", summary.maximum_length),
+    ];
+
+    for (key, n) in counts {
+        writeln!(writer, "SN\t{key}:\t{n}")?;
+    }
+
+    writeln!(writer, "SN\terror rate:\t{:e}", summary.error_rate())?;
+    writeln!(
+        writer,
+        "SN\taverage length:\t{:.0}",
+        summary.average_length()
+    )?;
+    writeln!(
+        writer,
+        "SN\taverage quality:\t{:.1}",
+        summary.average_quality()
+    )?;
+
+    Ok(())
+}
+
+fn write_qualities<W>(writer: &mut W, key: &str, qualities: &[Vec<u64>]) -> io::Result<()>
+where
+    W: Write,
+{
+    let width = qualities
+        .iter()
+        .map(|counts| counts.len())
+        .max()
+        .unwrap_or(0);
+
+    for (i, counts) in qualities.iter().enumerate() {
+        write!(writer, "{key}\t{}", i + 1)?;
+
+        for j in 0..width {
+            write!(writer, "\t{}", counts.get(j).copied().unwrap_or(0))?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+// Windows are grouped by their GC percentage. Each line has the GC percentage, the cumulative
+// percentage of windows, and the 10th, 25th, 50th, 75th, and 90th depth percentiles of the group.
+fn write_gc_depth<W>(writer: &mut W, collector: &Collector) -> io::Result<()>
+where
+    W: Write,
+{
+    const PERCENTILES: [f64; 5] = [10.0, 25.0, 50.0, 75.0, 90.0];
+
+    let windows = collector.gc_depth();
+    let mut groups: BTreeMap<u64, Vec<f64>> = BTreeMap::new();
+
+    for window in windows.values() {
+        let gc_percent = (window.gc_content() * 100.0).round() as u64;
+        groups.entry(gc_percent).or_default().push(window.depth());
+    }
+
+    let total = windows.len() as u64;
+    let mut n = 0;
+
+    for (gc_percent, mut depths) in groups {
+        depths.sort_by(|a, b| a.total_cmp(b));
+        n += depths.len() as u64;
+
+        write!(writer, "GCD\t{gc_percent}.0\t{:.3}", percent(n, total))?;
+
+        for p in PERCENTILES {
+            write!(writer, "\t{:.3}", percentile(&depths, p))?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+fn percent(n: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (n as f64 / total as f64) * 100.0
+    }
+}
+
+// Uses the nearest-rank method. `values` must be sorted and nonempty.
+fn percentile(values: &[f64], p: f64) -> f64 {
+    let rank = ((p / 100.0) * values.len() as f64).ceil() as usize;
+    values[rank.saturating_sub(1).min(values.len() - 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&values, 10.0), 1.0);
+        assert_eq!(percentile(&values, 50.0), 2.0);
+        assert_eq!(percentile(&values, 90.0), 4.0);
+    }
+}
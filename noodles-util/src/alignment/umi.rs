@@ -0,0 +1,522 @@
+//! UMI-aware read grouping.
+
+mod strategy;
+
+pub use self::strategy::Strategy;
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    io,
+};
+
+use noodles_core::Position;
+use noodles_sam::alignment::{record::data::field::Tag, record_buf::data::field::Value, RecordBuf};
+
+type Segment = (usize, Position, bool);
+type TemplateKey = (Segment, Option<Segment>);
+
+/// An iterator that groups templates by position and UMI.
+///
+/// Templates are keyed by the reference sequence IDs, alignment starts, and strands of their
+/// mapped segments. Templates with the same key are clustered by their UMI (`RX`) using a
+/// [`Strategy`], and each cluster is assigned a unique molecular identifier (`MI`), starting from
+/// 0.
+///
+/// The input records must be coordinate-sorted or sorted by template coordinate, i.e., by the
+/// leftmost segment of each template. Secondary, supplementary, and unplaced unmapped records are
+/// passed through without being assigned a molecular identifier. All other records must have a UMI
+/// and, if segmented with a mapped mate, a read name.
+pub struct Grouper<I> {
+    records: I,
+    strategy: Strategy,
+    max_edits: usize,
+    position: Option<(usize, Position)>,
+    batch: Vec<(Option<TemplateKey>, RecordBuf)>,
+    queue: VecDeque<RecordBuf>,
+    pending_mates: HashMap<Vec<u8>, usize>,
+    next_id: usize,
+    is_eof: bool,
+}
+
+impl<I> Grouper<I>
+where
+    I: Iterator<Item = io::Result<RecordBuf>>,
+{
+    /// Creates a UMI grouper.
+    ///
+    /// UMIs are grouped when they are within `max_edits` mismatches of each other, as determined
+    /// by the given strategy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::{
+    ///     record::{data::field::Tag, Flags},
+    ///     record_buf::data::field::Value,
+    ///     RecordBuf,
+    /// };
+    /// use noodles_util::alignment::umi::{Grouper, Strategy};
+    ///
+    /// let build_record = |umi: &str| {
+    ///     RecordBuf::builder()
+    ///         .set_flags(Flags::empty())
+    ///         .set_reference_sequence_id(0)
+    ///         .set_alignment_start(Position::MIN)
+    ///         .set_data([(Tag::UMI_SEQUENCE, Value::from(umi))].into_iter().collect())
+    ///         .build()
+    /// };
+    ///
+    /// let records = [build_record("AAAA"), build_record("AAAT"), build_record("CCCC")];
+    /// let grouper = Grouper::new(records.into_iter().map(Ok), Strategy::Adjacency, 1);
+    ///
+    /// let ids: Vec<_> = grouper
+    ///     .map(|result| result.map(|record| record.data().get(&Tag::UMI_ID).cloned()))
+    ///     .collect::<std::io::Result<_>>()?;
+    ///
+    /// assert_eq!(ids, [
+    ///     Some(Value::from("0")),
+    ///     Some(Value::from("0")),
+    ///     Some(Value::from("1")),
+    /// ]);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn new(records: I, strategy: Strategy, max_edits: usize) -> Self {
+        Self {
+            records,
+            strategy,
+            max_edits,
+            position: None,
+            batch: Vec::new(),
+            queue: VecDeque::new(),
+            pending_mates: HashMap::new(),
+            next_id: 0,
+            is_eof: false,
+        }
+    }
+
+    fn push(&mut self, record: RecordBuf) -> io::Result<()> {
+        let key = template_key(&record);
+
+        match key {
+            Some(((reference_sequence_id, start, _), _)) => {
+                let position = (reference_sequence_id, start);
+
+                if self.position.map(|p| position > p).unwrap_or(true) {
+                    self.flush()?;
+                    self.position = Some(position);
+                }
+            }
+            None if record.reference_sequence_id().is_none() => {
+                self.flush()?;
+                self.queue.push_back(record);
+                return Ok(());
+            }
+            None => {}
+        }
+
+        self.batch.push((key, record));
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let batch = std::mem::take(&mut self.batch);
+
+        let ids = self.assign_ids(&batch)?;
+
+        for (key, mut record) in batch {
+            let Some(key) = key else {
+                self.queue.push_back(record);
+                continue;
+            };
+
+            let id = if is_leftmost(&key, self.position) {
+                let umi = get_umi(&record)?;
+                let id = ids[&(key, umi.to_vec())];
+
+                if key.1.is_some() {
+                    let name = get_name(&record)?.to_vec();
+
+                    if self.pending_mates.remove(&name).is_none() {
+                        self.pending_mates.insert(name, id);
+                    }
+                }
+
+                id
+            } else {
+                let name = get_name(&record)?;
+
+                self.pending_mates.remove(name).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "missing mate: input must be coordinate-sorted",
+                    )
+                })?
+            };
+
+            record
+                .data_mut()
+                .insert(Tag::UMI_ID, Value::from(id.to_string()));
+
+            self.queue.push_back(record);
+        }
+
+        Ok(())
+    }
+
+    fn assign_ids(
+        &mut self,
+        batch: &[(Option<TemplateKey>, RecordBuf)],
+    ) -> io::Result<HashMap<(TemplateKey, Vec<u8>), usize>> {
+        let mut families: BTreeMap<TemplateKey, BTreeMap<Vec<u8>, usize>> = BTreeMap::new();
+
+        for (key, record) in batch {
+            let Some(key) = key else {
+                continue;
+            };
+
+            if !is_leftmost(key, self.position) {
+                continue;
+            }
+
+            let umi = get_umi(record)?;
+            let count = families
+                .entry(*key)
+                .or_default()
+                .entry(umi.to_vec())
+                .or_default();
+
+            if is_template_representative(key, record) {
+                *count += 1;
+            }
+        }
+
+        let mut ids = HashMap::new();
+
+        for (key, umi_counts) in families {
+            let umis: Vec<_> = umi_counts
+                .iter()
+                .map(|(umi, count)| (umi.as_slice(), *count))
+                .collect();
+
+            let clusters = self.strategy.cluster(&umis, self.max_edits);
+            let cluster_count = clusters.iter().max().map(|n| n + 1).unwrap_or_default();
+
+            for ((umi, _), cluster) in umi_counts.into_iter().zip(clusters) {
+                ids.insert((key, umi), self.next_id + cluster);
+            }
+
+            self.next_id += cluster_count;
+        }
+
+        Ok(ids)
+    }
+}
+
+impl<I> Iterator for Grouper<I>
+where
+    I: Iterator<Item = io::Result<RecordBuf>>,
+{
+    type Item = io::Result<RecordBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(record) = self.queue.pop_front() {
+                return Some(Ok(record));
+            }
+
+            if self.is_eof {
+                return None;
+            }
+
+            let result = match self.records.next() {
+                Some(Ok(record)) => self.push(record),
+                Some(Err(e)) => Err(e),
+                None => {
+                    self.is_eof = true;
+                    self.flush()
+                }
+            };
+
+            if let Err(e) = result {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+fn template_key(record: &RecordBuf) -> Option<TemplateKey> {
+    let flags = record.flags();
+
+    if flags.is_secondary() || flags.is_supplementary() {
+        return None;
+    }
+
+    let segment = segment(record);
+
+    let mate_segment = if has_mapped_mate(record) {
+        record
+            .mate_reference_sequence_id()
+            .zip(record.mate_alignment_start())
+            .map(|(id, start)| (id, start, flags.is_mate_reverse_complemented()))
+    } else {
+        None
+    };
+
+    match (segment, mate_segment) {
+        (Some(a), Some(b)) if b < a => Some((b, Some(a))),
+        (Some(a), b) => Some((a, b)),
+        (None, Some(b)) => Some((b, None)),
+        (None, None) => None,
+    }
+}
+
+fn is_leftmost(key: &TemplateKey, position: Option<(usize, Position)>) -> bool {
+    let (reference_sequence_id, start, _) = key.0;
+    position == Some((reference_sequence_id, start))
+}
+
+// Only one segment per template is counted: the leftmost mapped segment or, when both segments
+// start at the same position on the same strand, the first segment. This keeps the UMI counts the
+// same whether the input is coordinate-sorted or sorted by template coordinate.
+fn is_template_representative(key: &TemplateKey, record: &RecordBuf) -> bool {
+    if segment(record) != Some(key.0) {
+        return false;
+    }
+
+    match key.1 {
+        Some(mate_segment) if mate_segment == key.0 => !record.flags().is_last_segment(),
+        _ => true,
+    }
+}
+
+fn segment(record: &RecordBuf) -> Option<Segment> {
+    let flags = record.flags();
+
+    if flags.is_unmapped() {
+        return None;
+    }
+
+    record
+        .reference_sequence_id()
+        .zip(record.alignment_start())
+        .map(|(id, start)| (id, start, flags.is_reverse_complemented()))
+}
+
+fn has_mapped_mate(record: &RecordBuf) -> bool {
+    let flags = record.flags();
+    flags.is_segmented() && !flags.is_mate_unmapped()
+}
+
+fn get_umi(record: &RecordBuf) -> io::Result<&[u8]> {
+    match record.data().get(&Tag::UMI_SEQUENCE) {
+        Some(Value::String(umi)) => Ok(umi.as_ref()),
+        Some(_) => Err(io::Error::new(io::ErrorKind::InvalidData, "invalid UMI")),
+        None => Err(io::Error::new(io::ErrorKind::InvalidData, "missing UMI")),
+    }
+}
+
+fn get_name(record: &RecordBuf) -> io::Result<&[u8]> {
+    record
+        .name()
+        .map(|name| name.as_ref())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing read name"))
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::alignment::record::Flags;
+
+    use super::*;
+
+    #[test]
+    fn test_next_with_coordinate_sorted_pairs() -> io::Result<()> {
+        let build_record = |name: &[u8], flags, start: usize, mate_start: usize, umi: &str| {
+            RecordBuf::builder()
+                .set_name(name.into())
+                .set_flags(Flags::SEGMENTED | flags)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start).unwrap())
+                .set_mate_reference_sequence_id(0)
+                .set_mate_alignment_start(Position::try_from(mate_start).unwrap())
+                .set_data(
+                    [(Tag::UMI_SEQUENCE, Value::from(umi))]
+                        .into_iter()
+                        .collect(),
+                )
+                .build()
+        };
+
+        let r1 = Flags::FIRST_SEGMENT | Flags::MATE_REVERSE_COMPLEMENTED;
+        let r2 = Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED;
+
+        let records = [
+            build_record(b"r0", r1, 5, 13, "AAAA"),
+            build_record(b"r1", r1, 5, 13, "AAAT"),
+            build_record(b"r2", r1, 5, 13, "AAAA"),
+            build_record(b"r3", r1, 5, 13, "CCCC"),
+            build_record(b"r4", r1, 5, 21, "AAAA"),
+            build_record(b"r5", r1, 8, 13, "AAAA"),
+            build_record(b"r0", r2, 13, 5, "AAAA"),
+            build_record(b"r1", r2, 13, 5, "AAAT"),
+            build_record(b"r2", r2, 13, 5, "AAAA"),
+            build_record(b"r3", r2, 13, 5, "CCCC"),
+            build_record(b"r5", r2, 13, 8, "AAAA"),
+            build_record(b"r4", r2, 21, 5, "AAAA"),
+        ];
+
+        let grouper = Grouper::new(records.into_iter().map(Ok), Strategy::Directional, 1);
+
+        let actual: Vec<_> = grouper
+            .map(|result| {
+                result.map(|record| {
+                    let name = record.name().cloned().map(Vec::from);
+                    let id = record.data().get(&Tag::UMI_ID).cloned();
+                    (name, id)
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (b"r0", "0"),
+            (b"r1", "0"),
+            (b"r2", "0"),
+            (b"r3", "1"),
+            (b"r4", "2"),
+            (b"r5", "3"),
+            (b"r0", "0"),
+            (b"r1", "0"),
+            (b"r2", "0"),
+            (b"r3", "1"),
+            (b"r5", "3"),
+            (b"r4", "2"),
+        ]
+        .map(|(name, id)| (Some(name.to_vec()), Some(Value::from(id))));
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_template_coordinate_sorted_pairs() -> io::Result<()> {
+        fn build_record(
+            name: &[u8],
+            flags: Flags,
+            start: usize,
+            mate_start: usize,
+            umi: &str,
+        ) -> RecordBuf {
+            RecordBuf::builder()
+                .set_name(name.into())
+                .set_flags(Flags::SEGMENTED | flags)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start).unwrap())
+                .set_mate_reference_sequence_id(0)
+                .set_mate_alignment_start(Position::try_from(mate_start).unwrap())
+                .set_data(
+                    [(Tag::UMI_SEQUENCE, Value::from(umi))]
+                        .into_iter()
+                        .collect(),
+                )
+                .build()
+        }
+
+        fn group(records: Vec<RecordBuf>) -> io::Result<Vec<(Vec<u8>, Value)>> {
+            Grouper::new(records.into_iter().map(Ok), Strategy::Directional, 1)
+                .map(|result| {
+                    result.map(|record| {
+                        let name = record.name().cloned().map(Vec::from).unwrap_or_default();
+                        let id = record.data().get(&Tag::UMI_ID).cloned().unwrap();
+                        (name, id)
+                    })
+                })
+                .collect()
+        }
+
+        let r1 = Flags::FIRST_SEGMENT | Flags::MATE_REVERSE_COMPLEMENTED;
+        let r2 = Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED;
+
+        let coordinate_sorted_records = vec![
+            build_record(b"r0", r1, 5, 13, "AAAA"),
+            build_record(b"r1", r1, 5, 13, "AAAT"),
+            build_record(b"r2", r1, 5, 13, "CCCC"),
+            build_record(b"r3", r1, 8, 13, "AAAA"),
+            build_record(b"r0", r2, 13, 5, "AAAA"),
+            build_record(b"r1", r2, 13, 5, "AAAT"),
+            build_record(b"r2", r2, 13, 5, "CCCC"),
+            build_record(b"r3", r2, 13, 8, "AAAA"),
+        ];
+
+        let template_coordinate_sorted_records = vec![
+            build_record(b"r0", r1, 5, 13, "AAAA"),
+            build_record(b"r0", r2, 13, 5, "AAAA"),
+            build_record(b"r1", r1, 5, 13, "AAAT"),
+            build_record(b"r1", r2, 13, 5, "AAAT"),
+            build_record(b"r2", r1, 5, 13, "CCCC"),
+            build_record(b"r2", r2, 13, 5, "CCCC"),
+            build_record(b"r3", r1, 8, 13, "AAAA"),
+            build_record(b"r3", r2, 13, 8, "AAAA"),
+        ];
+
+        let expected = [(b"r0", "0"), (b"r1", "0"), (b"r2", "1"), (b"r3", "2")]
+            .map(|(name, id)| (name.to_vec(), Value::from(id)));
+
+        let mut actual = group(coordinate_sorted_records)?;
+        actual.sort_by(|(a, _), (b, _)| a.cmp(b));
+        actual.dedup();
+        assert_eq!(actual, expected);
+
+        let mut actual = group(template_coordinate_sorted_records)?;
+        actual.sort_by(|(a, _), (b, _)| a.cmp(b));
+        actual.dedup();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_missing_umi() {
+        let record = RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::MIN)
+            .build();
+
+        let mut grouper = Grouper::new([Ok(record)].into_iter(), Strategy::Identity, 0);
+
+        assert!(matches!(
+            grouper.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_template_key() {
+        const START: Position = Position::MIN;
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::UNMAPPED)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(START)
+            .build();
+        assert!(template_key(&record).is_none());
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::SEGMENTED | Flags::UNMAPPED)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(START)
+            .set_mate_reference_sequence_id(0)
+            .set_mate_alignment_start(START)
+            .build();
+        assert_eq!(template_key(&record), Some(((0, START, false), None)));
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::SUPPLEMENTARY)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(START)
+            .build();
+        assert!(template_key(&record).is_none());
+    }
+}
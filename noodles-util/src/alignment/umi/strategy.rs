@@ -0,0 +1,149 @@
+use std::collections::VecDeque;
+
+/// A UMI clustering strategy.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Strategy {
+    /// Only identical UMIs are grouped.
+    Identity,
+    /// Each UMI, in decreasing order of count, is grouped with the remaining UMIs within the
+    /// maximum edit distance of it.
+    Adjacency,
+    /// A UMI is grouped with the UMIs within the maximum edit distance of it that have a count of
+    /// at most half of its count (plus one), transitively.
+    #[default]
+    Directional,
+}
+
+impl Strategy {
+    /// Clusters UMIs.
+    ///
+    /// The given UMIs are expected to be unique. Each UMI is paired with its count, e.g., the
+    /// number of templates that have the UMI. The distance between two UMIs is the Hamming
+    /// distance; UMIs with different lengths are never grouped unless they are identical.
+    ///
+    /// This returns the cluster index of each UMI, in input order. Cluster indices are assigned in
+    /// increasing order starting from 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::umi::Strategy;
+    ///
+    /// let umis = [(&b"AAAA"[..], 10), (&b"AAAT"[..], 2), (&b"CCCC"[..], 1)];
+    ///
+    /// assert_eq!(Strategy::Identity.cluster(&umis, 1), [0, 1, 2]);
+    /// assert_eq!(Strategy::Directional.cluster(&umis, 1), [0, 0, 1]);
+    /// ```
+    pub fn cluster(self, umis: &[(&[u8], usize)], max_edits: usize) -> Vec<usize> {
+        match self {
+            Self::Identity => (0..umis.len()).collect(),
+            Self::Adjacency => cluster_by_adjacency(umis, max_edits),
+            Self::Directional => cluster_by_direction(umis, max_edits),
+        }
+    }
+}
+
+const UNASSIGNED: usize = usize::MAX;
+
+fn cluster_by_adjacency(umis: &[(&[u8], usize)], max_edits: usize) -> Vec<usize> {
+    let mut clusters = vec![UNASSIGNED; umis.len()];
+    let mut next_cluster = 0;
+
+    for i in sorted_indices_by_count(umis) {
+        if clusters[i] != UNASSIGNED {
+            continue;
+        }
+
+        clusters[i] = next_cluster;
+
+        for (j, (umi, _)) in umis.iter().enumerate() {
+            if clusters[j] == UNASSIGNED && is_within_edits(umis[i].0, umi, max_edits) {
+                clusters[j] = next_cluster;
+            }
+        }
+
+        next_cluster += 1;
+    }
+
+    clusters
+}
+
+fn cluster_by_direction(umis: &[(&[u8], usize)], max_edits: usize) -> Vec<usize> {
+    let mut clusters = vec![UNASSIGNED; umis.len()];
+    let mut next_cluster = 0;
+    let mut queue = VecDeque::new();
+
+    for i in sorted_indices_by_count(umis) {
+        if clusters[i] != UNASSIGNED {
+            continue;
+        }
+
+        clusters[i] = next_cluster;
+        queue.push_back(i);
+
+        while let Some(j) = queue.pop_front() {
+            let (src, src_count) = umis[j];
+
+            for (k, &(dst, dst_count)) in umis.iter().enumerate() {
+                if clusters[k] == UNASSIGNED
+                    && src_count >= (2 * dst_count).saturating_sub(1)
+                    && is_within_edits(src, dst, max_edits)
+                {
+                    clusters[k] = next_cluster;
+                    queue.push_back(k);
+                }
+            }
+        }
+
+        next_cluster += 1;
+    }
+
+    clusters
+}
+
+fn sorted_indices_by_count(umis: &[(&[u8], usize)]) -> Vec<usize> {
+    let mut indices: Vec<_> = (0..umis.len()).collect();
+    indices.sort_by(|&i, &j| {
+        umis[j]
+            .1
+            .cmp(&umis[i].1)
+            .then_with(|| umis[i].0.cmp(umis[j].0))
+    });
+    indices
+}
+
+fn is_within_edits(a: &[u8], b: &[u8], max_edits: usize) -> bool {
+    a.len() == b.len() && a.iter().zip(b).filter(|(x, y)| x != y).count() <= max_edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster() {
+        let umis = [
+            (&b"ACGT"[..], 1),
+            (&b"AAAA"[..], 8),
+            (&b"AAAT"[..], 4),
+            (&b"AATT"[..], 2),
+            (&b"AAA"[..], 1),
+        ];
+
+        assert_eq!(Strategy::Identity.cluster(&umis, 1), [0, 1, 2, 3, 4]);
+        assert_eq!(Strategy::Adjacency.cluster(&umis, 1), [3, 0, 0, 1, 2]);
+        assert_eq!(Strategy::Directional.cluster(&umis, 1), [2, 0, 0, 0, 1]);
+
+        let umis = [(&b"AAAA"[..], 4), (&b"AAAT"[..], 4)];
+        assert_eq!(Strategy::Directional.cluster(&umis, 1), [0, 1]);
+        assert_eq!(Strategy::Adjacency.cluster(&umis, 1), [0, 0]);
+    }
+
+    #[test]
+    fn test_is_within_edits() {
+        assert!(is_within_edits(b"ACGT", b"ACGT", 0));
+        assert!(is_within_edits(b"ACGT", b"ACGA", 1));
+        assert!(!is_within_edits(b"ACGT", b"ACAA", 1));
+        assert!(!is_within_edits(b"ACGT", b"ACG", 1));
+    }
+}
@@ -0,0 +1,185 @@
+//! Indexed VCF writer.
+
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, binning_index::index::reference_sequence::bin::Chunk};
+use noodles_tabix as tabix;
+
+use super::Writer;
+use crate::{variant::io::Write as _, Header, Record};
+
+/// An indexed VCF writer.
+///
+/// This writes bgzipped VCF and builds a tabix index as records are written, avoiding a second
+/// pass over the output. Records must be written in coordinate-sorted order.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::Position;
+/// use noodles_vcf::{self as vcf, variant::{io::Write, RecordBuf}};
+///
+/// let mut writer = vcf::io::IndexedWriter::new(Vec::new());
+///
+/// let header = vcf::Header::default();
+/// writer.write_header(&header)?;
+///
+/// let record = RecordBuf::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_variant_start(Position::MIN)
+///     .set_reference_bases("A")
+///     .build();
+///
+/// writer.write_variant_record(&header, &record)?;
+///
+/// let index = writer.finish()?;
+/// assert_eq!(index.reference_sequences().len(), 1);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::Writer<W>>,
+    indexer: tabix::index::Indexer,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexed VCF writer with a default compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let writer = vcf::io::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(bgzf::Writer::new(inner)))
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let writer = vcf::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &bgzf::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Writes a VCF header.
+    pub fn write_header(&mut self, header: &Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a VCF record.
+    pub fn write_record(&mut self, header: &Header, record: &Record) -> io::Result<()> {
+        self.write_variant_record(header, record)
+    }
+
+    /// Finishes the output stream and returns the built index.
+    pub fn finish(&mut self) -> io::Result<tabix::Index> {
+        self.inner.get_mut().try_finish()?;
+
+        let indexer = mem::take(&mut self.indexer);
+        Ok(indexer.build())
+    }
+}
+
+impl<W> From<Writer<bgzf::Writer<W>>> for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: Writer<bgzf::Writer<W>>) -> Self {
+        let mut indexer = tabix::index::Indexer::default();
+        indexer.set_header(csi::binning_index::index::header::Builder::vcf().build());
+
+        Self { inner, indexer }
+    }
+}
+
+impl<W> crate::variant::io::Write for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn write_variant_header(&mut self, header: &Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_variant_record(
+        &mut self,
+        header: &Header,
+        record: &dyn crate::variant::Record,
+    ) -> io::Result<()> {
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_variant_record(header, record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        let chunk = Chunk::new(start_position, end_position);
+
+        let reference_sequence_name = record.reference_sequence_name(header)?;
+
+        let start = record
+            .variant_start()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing position"))?;
+
+        let end = record.variant_end(header)?;
+
+        self.indexer
+            .add_record(reference_sequence_name, start, end, chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::variant::RecordBuf;
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::default();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (name, start) in [("sq0", 8), ("sq0", 13), ("sq1", 5)] {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_name(name)
+                .set_variant_start(Position::try_from(start)?)
+                .set_reference_bases("A")
+                .build();
+
+            writer.write_variant_record(&header, &record)?;
+        }
+
+        let index = writer.finish()?;
+
+        assert_eq!(index.reference_sequences().len(), 2);
+
+        let src = writer.get_ref().get_ref();
+        let mut reader = crate::io::IndexedReader::new(Cursor::new(src), index);
+        let header = reader.read_header()?;
+
+        let region = "sq0:10-20".parse()?;
+        let records: Vec<_> = reader.query(&header, &region)?.collect::<io::Result<_>>()?;
+        assert_eq!(records.len(), 1);
+
+        Ok(())
+    }
+}
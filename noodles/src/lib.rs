@@ -2,7 +2,7 @@
 
 //! **noodles** attempts to provide correct implementations of libraries for handling various
 //! bioinformatics file formats. It currently supports BAM 1.6, BCF 2.2, BED, BGZF, CRAM 3.0/3.1,
-//! CSI, FASTA, FASTQ, GFF3, GTF 2.2, htsget 1.3, refget 2.0, SAM 1.6, tabix, and VCF 4.3/4.4.
+//! CSI, FASTA, FASTQ, GFF3, GTF 2.2, htsget 1.3, PAF, refget 2.0, SAM 1.6, tabix, and VCF 4.3/4.4.
 
 #[cfg(feature = "bam")]
 #[doc(inline)]
@@ -52,6 +52,10 @@ pub use noodles_gtf as gtf;
 #[doc(inline)]
 pub use noodles_htsget as htsget;
 
+#[cfg(feature = "paf")]
+#[doc(inline)]
+pub use noodles_paf as paf;
+
 #[cfg(feature = "refget")]
 #[doc(inline)]
 pub use noodles_refget as refget;